@@ -95,9 +95,8 @@ fn poll_for_bookmark_key(context: &mut AppContext, backend: &mut AppBackend) ->
     bookmarks.sort();
     let bookmarks_str: Vec<&str> = bookmarks.iter().map(|s| s.as_str()).collect();
 
-    let terminal = backend.terminal_mut();
     loop {
-        let _ = terminal.draw(|frame| {
+        let _ = backend.terminal_mut().draw(|frame| {
             let area = frame.size();
             if area.height < 5 {
                 return;
@@ -131,7 +130,11 @@ fn poll_for_bookmark_key(context: &mut AppContext, backend: &mut AppBackend) ->
         if let Ok(event) = context.poll_event() {
             match event {
                 AppEvent::Termion(key) => return Some(key),
-                event => process_event::process_noninteractive(event, context),
+                event => {
+                    if process_event::process_in_modal(event, context, backend) {
+                        return None;
+                    }
+                }
             };
         }
     }
@@ -0,0 +1,229 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::AppEvent;
+use crate::util::checksum::{self, ChecksumAlgorithm, ChecksumEntry, ChecksumOutput};
+
+use super::file_ops;
+
+// suffix of the checksum lists `verify_checksums` looks for
+const CHECKSUM_LIST_EXT: &str = "sha256sum";
+// key of the progress message of checksum threads, see `WorkerContext::set_progress_msg`
+pub const PROGRESS_JOB: &str = "checksum";
+
+fn selected_paths(context: &AppContext) -> AppResult<Vec<PathBuf>> {
+    let paths = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .map(|list| list.get_selected_paths())
+        .unwrap_or_default();
+    let paths: Vec<PathBuf> = paths.into_iter().filter(|p| p.is_file()).collect();
+    if paths.is_empty() {
+        return Err(AppError::new(
+            AppErrorKind::Io(io::ErrorKind::InvalidData),
+            "no files selected".to_string(),
+        ));
+    }
+    Ok(paths)
+}
+
+/// Computes checksums of the selected files in the background
+pub fn checksum(context: &mut AppContext, algorithm: ChecksumAlgorithm, copy: bool) -> AppResult {
+    let files = selected_paths(context)?
+        .into_iter()
+        .map(|path| (path, None))
+        .collect();
+    let output = if copy {
+        ChecksumOutput::Clipboard
+    } else {
+        ChecksumOutput::Messages
+    };
+    spawn_checksum_thread(context, algorithm, files, output);
+    Ok(())
+}
+
+/// Checks the selected files against their checksum lists in the background.
+///
+/// A selected `*.sha256sum` file is used as list of files to check,
+/// for other files `<file>.sha256sum` next to it is used.
+pub fn verify_checksums(context: &mut AppContext) -> AppResult {
+    let mut files = Vec::new();
+    for path in selected_paths(context)? {
+        let is_list = path
+            .extension()
+            .map(|ext| ext == CHECKSUM_LIST_EXT)
+            .unwrap_or(false);
+        let list = if is_list {
+            path.clone()
+        } else {
+            checksum_list_path(&path)
+        };
+        if !list.exists() {
+            return Err(AppError::new(
+                AppErrorKind::Io(io::ErrorKind::NotFound),
+                format!("{}: no checksum file found", list.display()),
+            ));
+        }
+        let entries = checksum::read_checksum_list(&list)?;
+        if is_list {
+            files.extend(entries.into_iter().map(|(p, sum)| (p, Some(sum))));
+        } else {
+            // lists next to a file usually only contain that file
+            let file_name = path.file_name();
+            let expected = entries
+                .into_iter()
+                .find(|(p, _)| p.file_name() == file_name)
+                .map(|(_, sum)| sum);
+            match expected {
+                Some(sum) => files.push((path, Some(sum))),
+                None => {
+                    return Err(AppError::new(
+                        AppErrorKind::Io(io::ErrorKind::NotFound),
+                        format!("{}: not listed in {}", path.display(), list.display()),
+                    ))
+                }
+            }
+        }
+    }
+    spawn_checksum_thread(
+        context,
+        ChecksumAlgorithm::Sha256,
+        files,
+        ChecksumOutput::Messages,
+    );
+    Ok(())
+}
+
+fn checksum_list_path(path: &Path) -> PathBuf {
+    let mut list = path.as_os_str().to_owned();
+    list.push(".");
+    list.push(CHECKSUM_LIST_EXT);
+    PathBuf::from(list)
+}
+
+/// Computes the checksums of `files`, checking them against the expected ones if given,
+/// and sends the results as `AppEvent::ChecksumResult`
+pub fn spawn_checksum_thread(
+    context: &mut AppContext,
+    algorithm: ChecksumAlgorithm,
+    files: Vec<(PathBuf, Option<String>)>,
+    output: ChecksumOutput,
+) {
+    let event_tx: mpsc::Sender<AppEvent> = context.clone_event_tx();
+    let total = files.len();
+    let _ = event_tx.send(AppEvent::ChecksumProgress {
+        processed: 0,
+        total,
+    });
+    let _ = thread::spawn(move || {
+        let mut entries = Vec::with_capacity(total);
+        for (i, (path, expected)) in files.into_iter().enumerate() {
+            let result = checksum::checksum(&path, algorithm).map_err(|e| e.to_string());
+            entries.push(ChecksumEntry {
+                path,
+                expected,
+                result,
+            });
+            let _ = event_tx.send(AppEvent::ChecksumProgress {
+                processed: i + 1,
+                total,
+            });
+        }
+        let _ = event_tx.send(AppEvent::ChecksumResult {
+            algorithm,
+            entries,
+            output,
+        });
+    });
+}
+
+/// Reports the results of a checksum thread
+pub fn process_checksum_result(
+    context: &mut AppContext,
+    algorithm: ChecksumAlgorithm,
+    entries: Vec<ChecksumEntry>,
+    output: ChecksumOutput,
+) {
+    context
+        .worker_context_mut()
+        .clear_progress_msg(PROGRESS_JOB);
+
+    let verify = entries.iter().any(|entry| entry.expected.is_some());
+    let mut lines = Vec::with_capacity(entries.len());
+    let mut failed = 0;
+    // a single file is shown with its checksum or error, more are summed up in one message
+    let mut single_msg = None;
+    for entry in entries.iter() {
+        let name = entry
+            .path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match &entry.result {
+            Err(e) => {
+                failed += 1;
+                log::warn!("checksum of {}: {}", entry.path.display(), e);
+                single_msg = Some(Err(format!("{}: {}", name, e)));
+            }
+            Ok(_) if entry.mismatch() => {
+                failed += 1;
+                context
+                    .message_queue_mut()
+                    .push_error(format!("{}: checksum mismatch", name));
+            }
+            Ok(sum) => {
+                single_msg = Some(Ok(format!("{} {}  {}", algorithm.as_str(), sum, name)));
+                lines.push(checksum::format_checksum_line(sum, &name));
+            }
+        }
+    }
+
+    if verify {
+        let msg = format!(
+            "verified {} files, {} failed",
+            entries.len() - failed,
+            failed
+        );
+        if failed == 0 {
+            context.message_queue_mut().push_success(msg);
+        } else {
+            context.message_queue_mut().push_error(msg);
+        }
+        return;
+    }
+
+    let copied = output == ChecksumOutput::Clipboard && !lines.is_empty();
+    if copied {
+        if let Err(e) = file_ops::copy_string_to_buffer(lines.join("\n")) {
+            context.message_queue_mut().push_error(e.to_string());
+        }
+    }
+    let msg = match single_msg {
+        Some(msg) if entries.len() == 1 => msg,
+        _ => {
+            let mut msg = format!(
+                "computed {} checksums of {} files",
+                algorithm.as_str(),
+                lines.len()
+            );
+            if copied {
+                msg.push_str(" and copied them");
+            }
+            if failed > 0 {
+                msg.push_str(&format!(", {} failed (see the log)", failed));
+                Err(msg)
+            } else {
+                Ok(msg)
+            }
+        }
+    };
+    match msg {
+        Ok(msg) => context.message_queue_mut().push_success(msg),
+        Err(msg) => context.message_queue_mut().push_error(msg),
+    }
+}
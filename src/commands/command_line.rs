@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use crate::config::clean::keymap::AppKeyMapping;
-use crate::context::AppContext;
+use crate::context::{AppContext, PromptHistory};
 use crate::error::AppResult;
 use crate::key_command::{AppExecute, Command};
 use crate::ui::views::{DummyListener, TuiTextField};
@@ -20,17 +20,13 @@ pub fn read_and_execute(
         .prompt(":")
         .prefix(prefix)
         .suffix(suffix)
+        .history(PromptHistory::for_input(prefix))
         .get_input(backend, context, &mut listener);
 
     if let Some(s) = user_input {
-        let mut trimmed = s.trim_start();
-        let _ = context.commandline_context_mut().history_mut().add(trimmed);
+        let trimmed = s.trim_start();
 
-        if let Some(alias) = context.config_ref().cmd_aliases.get(trimmed) {
-            trimmed = alias;
-        }
-
-        let command = Command::from_str(trimmed)?;
+        let command = Command::from_str(&context.config_ref().expand_alias(trimmed))?;
         command.execute(context, backend, keymap_t)
     } else {
         Ok(())
@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use termion::event::{Event, Key};
+
+use crate::config::clean::keymap::AppKeyMapping;
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::key_command::{AppExecute, Command};
+use crate::ui::views::TuiView;
+use crate::ui::widgets::{self, TuiCommandPalette};
+use crate::ui::AppBackend;
+
+/// How well `query` matches `text`, lower is better. The characters of `query`
+/// have to appear in `text` in order, scattered matches score worse
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    for (i, c) in query.to_lowercase().chars().enumerate() {
+        let found = text[pos..].iter().position(|t| *t == c)?;
+        // the first character may start anywhere, but earlier is better
+        score += if i == 0 { found } else { found * 2 };
+        pos += found + 1;
+    }
+    Some(score)
+}
+
+/// Rows of the palette matching `query`, best matches first.
+/// Descriptions are searched too, but rank below command names
+fn filter_rows<'a>(rows: &'a [[String; 4]], query: &str) -> Vec<&'a [String; 4]> {
+    let mut matches: Vec<(usize, &[String; 4])> = rows
+        .iter()
+        .filter_map(|row| {
+            let by_command = fuzzy_score(query, &row[1]);
+            let by_comment = fuzzy_score(query, &row[2]).map(|score| score + 1000);
+            by_command.or(by_comment).map(|score| (score, row))
+        })
+        .collect();
+    matches.sort_by_key(|(score, _)| *score);
+    matches.into_iter().map(|(_, row)| row).collect()
+}
+
+/// Lists every command with its keybinding, filtered by a fuzzy search
+/// and runs the chosen one
+pub fn command_palette(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+) -> AppResult {
+    let mut rows = widgets::get_raw_keymap_table(&keymap_t.default_view, "", 1);
+    rows.extend(widgets::get_unbound_commands_table(
+        &keymap_t.default_view,
+        "",
+    ));
+    rows.sort_by(|a, b| a[1].cmp(&b[1]));
+
+    let mut query = String::new();
+    let mut index = 0;
+
+    context.flush_event();
+    let selected = loop {
+        let matches = filter_rows(&rows, &query);
+        index = index.min(matches.len().saturating_sub(1));
+
+        let _ = backend.terminal_mut().draw(|frame| {
+            let area = frame.size();
+            let mut view = TuiView::new(context);
+            view.show_bottom_status = false;
+            frame.render_widget(view, area);
+            frame.render_widget(TuiCommandPalette::new(&query, &matches, index), area);
+        });
+
+        match context.poll_event() {
+            Ok(AppEvent::Termion(Event::Key(key))) => match key {
+                Key::Esc => break None,
+                Key::Char('\n') => break matches.get(index).map(|row| (*row).clone()),
+                Key::Up | Key::Ctrl('p') => index = index.saturating_sub(1),
+                Key::Down | Key::Ctrl('n') => index += 1,
+                Key::Backspace => {
+                    query.pop();
+                    index = 0;
+                }
+                Key::Char(c) => {
+                    query.push(c);
+                    index = 0;
+                }
+                _ => {}
+            },
+            Ok(AppEvent::Termion(_)) => context.flush_event(),
+            Ok(event) => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+            Err(_) => return Ok(()),
+        }
+    };
+
+    let row = match selected {
+        Some(row) => row,
+        None => return Ok(()),
+    };
+    // the row shows the first command of a keybinding, all of them are run
+    if let Some(commands) = widgets::get_keybind_commands(&keymap_t.default_view, &row[0]) {
+        for command in commands {
+            command.execute(context, backend, keymap_t)?;
+        }
+        return Ok(());
+    }
+    // commands without a keybinding may need arguments, they are typed in the command line
+    let command = match Command::from_str(&row[1]) {
+        Ok(command) => command,
+        Err(_) if row[0].is_empty() => Command::CommandLine {
+            prefix: format!("{} ", row[1]),
+            suffix: String::new(),
+        },
+        Err(e) => return Err(e),
+    };
+    command.execute(context, backend, keymap_t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_test() {
+        assert_eq!(fuzzy_score("", "quit"), Some(0));
+        assert_eq!(fuzzy_score("qt", "quit"), Some(4));
+        assert_eq!(fuzzy_score("QUIT", "quit"), Some(0));
+        assert_eq!(fuzzy_score("tq", "quit"), None);
+        assert!(fuzzy_score("rename", "rename") < fuzzy_score("rename", "reload_dirlist_name"));
+        assert!(fuzzy_score("tab", "new_tab") < fuzzy_score("tab", "toggle_hidden_tab"));
+    }
+
+    #[test]
+    fn keybind_commands_test() {
+        use crate::config::clean::keymap::KeyMapping;
+        use crate::key_command::CommandKeybind;
+
+        let commands = vec![
+            Command::from_str("cd /").unwrap(),
+            Command::ToggleHiddenFiles,
+        ];
+        let mut sub_keymap = KeyMapping::new();
+        sub_keymap.insert(
+            Event::Key(Key::Char('h')),
+            CommandKeybind::SimpleKeybind {
+                commands,
+                description: None,
+            },
+        );
+        let mut keymap = KeyMapping::new();
+        keymap.insert(
+            Event::Key(Key::Char('g')),
+            CommandKeybind::CompositeKeybind(sub_keymap),
+        );
+
+        let rows = widgets::get_raw_keymap_table(&keymap, "", 1);
+        assert_eq!(rows[0][0], "gh");
+        let found = widgets::get_keybind_commands(&keymap, &rows[0][0]).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(widgets::get_keybind_commands(&keymap, "g").is_none());
+        assert!(widgets::get_keybind_commands(&keymap, "").is_none());
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use termion::event::{Event, Key};
+
+use crate::config::clean::keymap::AppKeyMapping;
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::key_command::{Command, CommandKeybind};
+use crate::ui::widgets::TuiDirCompare;
+use crate::ui::AppBackend;
+use crate::util::dir_compare::{self, DirDifference};
+
+// two selected directories, or the directories of the current and the next tab
+fn dirs_to_compare(context: &AppContext) -> AppResult<(PathBuf, PathBuf)> {
+    let selected: Vec<PathBuf> = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .map(|list| {
+            list.iter_selected()
+                .map(|entry| entry.file_path().to_path_buf())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    if selected.len() == 2 {
+        return Ok((selected[0].clone(), selected[1].clone()));
+    }
+
+    let tab_context = context.tab_context_ref();
+    if tab_context.len() < 2 {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            "Select two directories or open a second tab to compare".to_string(),
+        ));
+    }
+    let next_id = &tab_context.tab_order[(tab_context.index + 1) % tab_context.len()];
+    let left = tab_context.curr_tab_ref().cwd().to_path_buf();
+    let right = match tab_context.tab_ref(next_id) {
+        Some(tab) => tab.cwd().to_path_buf(),
+        None => {
+            return Err(AppError::new(
+                AppErrorKind::UnknownError,
+                "Failed to find next tab".to_string(),
+            ))
+        }
+    };
+    Ok((left, right))
+}
+
+fn compare_in_background(
+    context: &AppContext,
+    left: PathBuf,
+    right: PathBuf,
+    cancel: Arc<AtomicBool>,
+) {
+    let event_tx = context.clone_event_tx();
+    let _ = thread::spawn(move || {
+        let res = dir_compare::compare_dirs(&left, &right, &cancel);
+        let _ = event_tx.send(AppEvent::DirCompareResult { left, right, res });
+    });
+}
+
+/// Compares the directories in the background and shows the differences once they are known
+pub fn compare_tabs(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+) -> AppResult {
+    let (left, right) = dirs_to_compare(context)?;
+    let cancel = Arc::new(AtomicBool::new(false));
+    compare_in_background(context, left.clone(), right.clone(), cancel.clone());
+
+    let res = show_differences(context, backend, keymap_t, &left, &right);
+    // the comparison is of no use once the view is closed
+    cancel.store(true, Ordering::Relaxed);
+    res
+}
+
+fn show_differences(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+    left: &Path,
+    right: &Path,
+) -> AppResult {
+    let mut differences: Option<Vec<DirDifference>> = None;
+
+    context.flush_event();
+    let mut offset = 0;
+    loop {
+        context.remove_external_preview();
+        backend.render(TuiDirCompare::new(
+            left,
+            right,
+            differences.as_deref(),
+            &mut offset,
+        ));
+
+        let event = match context.poll_event() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+        match event {
+            AppEvent::DirCompareResult {
+                left: result_left,
+                right: result_right,
+                res,
+            } if result_left == left && result_right == right => differences = Some(res?),
+            AppEvent::Termion(Event::Key(Key::Esc)) => break,
+            AppEvent::Termion(event) => {
+                if let Some(CommandKeybind::SimpleKeybind { commands, .. }) =
+                    keymap_t.help_view.get(&event)
+                {
+                    for command in commands {
+                        match command {
+                            Command::CursorMoveUp { .. } => offset = offset.saturating_sub(1),
+                            Command::CursorMoveDown { .. } => offset = offset.saturating_add(1),
+                            Command::CursorMoveHome => offset = 0,
+                            Command::CursorMoveEnd => offset = usize::MAX,
+                            Command::CursorMovePageUp(_) => offset = offset.saturating_sub(10),
+                            Command::CursorMovePageDown(_) => offset = offset.saturating_add(10),
+                            Command::CloseTab | Command::CompareTabs => return Ok(()),
+                            _ => (),
+                        }
+                    }
+                }
+                context.flush_event();
+            }
+            event => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
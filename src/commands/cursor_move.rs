@@ -63,7 +63,7 @@ pub fn to_path(context: &mut AppContext, path: &path::Path) -> AppResult {
     let display_options = context.config_ref().display_options_ref().clone();
     if let Some(curr_list) = context.tab_context_mut().curr_tab_mut().curr_list_mut() {
         if let path::Component::Normal(name) = path.components().next().ok_or_else(err)? {
-            let index = curr_list.get_index_from_name(name.to_str().ok_or_else(err)?);
+            let index = curr_list.iter().position(|e| e.file_name_os() == name);
             curr_list.set_index(index, &ui_context, &display_options);
         }
     }
@@ -72,11 +72,17 @@ pub fn to_path(context: &mut AppContext, path: &path::Path) -> AppResult {
 }
 
 pub fn up(context: &mut AppContext, u: usize) -> AppResult {
+    let wrap = context.config_ref().display_options_ref().wrap_cursor();
     let movement = context
         .tab_context_ref()
         .curr_tab_ref()
         .curr_list_ref()
-        .and_then(|list| list.get_index().map(|idx| idx.saturating_sub(u)));
+        .and_then(|list| {
+            list.get_index().map(|idx| match idx {
+                0 if wrap && u > 0 => list.len() - 1,
+                idx => idx.saturating_sub(u),
+            })
+        });
 
     if let Some(s) = movement {
         cursor_move(context, s);
@@ -85,11 +91,20 @@ pub fn up(context: &mut AppContext, u: usize) -> AppResult {
 }
 
 pub fn down(context: &mut AppContext, u: usize) -> AppResult {
+    let wrap = context.config_ref().display_options_ref().wrap_cursor();
     let movement = context
         .tab_context_ref()
         .curr_tab_ref()
         .curr_list_ref()
-        .and_then(|list| list.get_index().map(|idx| idx.saturating_add(u)));
+        .and_then(|list| {
+            list.get_index().map(|idx| {
+                if wrap && u > 0 && idx + 1 == list.len() {
+                    0
+                } else {
+                    idx.saturating_add(u)
+                }
+            })
+        });
 
     if let Some(s) = movement {
         cursor_move(context, s);
@@ -97,6 +112,12 @@ pub fn down(context: &mut AppContext, u: usize) -> AppResult {
     Ok(())
 }
 
+/// Moves the cursor to the `n`th entry, counting from 1 like line numbers
+pub fn to_index(context: &mut AppContext, n: usize) -> AppResult {
+    cursor_move(context, n.saturating_sub(1));
+    Ok(())
+}
+
 pub fn home(context: &mut AppContext) -> AppResult {
     let movement = context
         .tab_context_ref()
@@ -187,6 +208,17 @@ pub fn page_down(context: &mut AppContext, backend: &mut AppBackend, proportion:
     Ok(())
 }
 
+/// Scrolls the current directory list, keeping the cursor where possible
+pub fn scroll_view(context: &mut AppContext, offset: isize) -> AppResult {
+    let ui_context = context.ui_context_ref().clone();
+    let display_options = context.config_ref().display_options_ref().clone();
+    if let Some(curr_list) = context.tab_context_mut().curr_tab_mut().curr_list_mut() {
+        curr_list.scroll_viewport(offset, &ui_context, &display_options);
+    }
+    lazy_load_directory_size(context);
+    Ok(())
+}
+
 pub fn page_home(context: &mut AppContext, _: &mut AppBackend) -> AppResult {
     let new_index = context
         .tab_context_ref()
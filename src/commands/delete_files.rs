@@ -10,9 +10,15 @@ use crate::io::{FileOperation, FileOperationOptions, IoWorkerThread};
 use crate::ui::widgets::TuiPrompt;
 use crate::ui::AppBackend;
 
-fn prompt(context: &mut AppContext, backend: &mut AppBackend, paths_len: usize) -> bool {
+fn prompt(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    paths_len: usize,
+    shred: bool,
+) -> bool {
     let ch = {
-        let prompt_str = format!("Delete {} files? (Y/n)", paths_len);
+        let action = if shred { "Shred" } else { "Delete" };
+        let prompt_str = format!("{} {} files? (Y/n)", action, paths_len);
         let mut prompt = TuiPrompt::new(&prompt_str);
         prompt.get_key(backend, context)
     };
@@ -40,12 +46,21 @@ fn delete_files(
     paths: Vec<path::PathBuf>,
     background: bool,
     permanently: bool,
+    shred: bool,
 ) -> AppResult<()> {
     let file_op = FileOperation::Delete;
+    // shredded files never go to the trash
+    let shred_passes = if shred {
+        context.config_ref().shred_passes.max(1)
+    } else {
+        0
+    };
     let options = FileOperationOptions {
         overwrite: false,
         skip_exist: false,
-        permanently: !context.config_ref().use_trash || permanently,
+        permanently: !context.config_ref().use_trash || permanently || shred,
+        shred_passes,
+        ..Default::default()
     };
 
     let dest = path::PathBuf::new();
@@ -54,7 +69,7 @@ fn delete_files(
         context.worker_context_mut().push_worker(worker_thread);
     } else {
         let (wtx, _) = mpsc::channel();
-        worker_thread.start(wtx)?;
+        worker_thread.start(wtx, context.config_ref().task_threads)?;
     }
 
     let history = context.tab_context_mut().curr_tab_mut().history_mut();
@@ -71,6 +86,7 @@ pub fn delete_selected_files(
     background: bool,
     permanently: bool,
     noconfirm: bool,
+    shred: bool,
 ) -> AppResult {
     let paths = context
         .tab_context_ref()
@@ -88,8 +104,8 @@ pub fn delete_selected_files(
         return Err(err);
     }
 
-    if noconfirm || prompt(context, backend, paths_len) {
-        delete_files(context, paths, background, permanently)?;
+    if noconfirm || prompt(context, backend, paths_len, shred) {
+        delete_files(context, paths, background, permanently, shred)?;
     }
 
     let curr_tab = context.tab_context_ref().curr_tab_ref();
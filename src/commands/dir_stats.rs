@@ -0,0 +1,144 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use termion::event::{Event, Key};
+
+use crate::commands::{change_directory, cursor_move};
+use crate::config::clean::keymap::AppKeyMapping;
+use crate::context::{AppContext, MessageLevel};
+use crate::error::AppResult;
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::key_command::{Command, CommandKeybind};
+use crate::ui::widgets::TuiDirStats;
+use crate::ui::AppBackend;
+use crate::util::dir_stats::{self, DirStats};
+
+// key of the progress message of the walk, see `WorkerContext::set_progress_msg`
+pub const PROGRESS_JOB: &str = "dir_stats";
+
+fn compute_in_background(context: &mut AppContext, root: PathBuf, cancel: Arc<AtomicBool>) {
+    let event_tx = context.clone_event_tx();
+    let _ = thread::spawn(move || {
+        let res = dir_stats::dir_stats(&root, &cancel, |scanned| {
+            let _ = event_tx.send(AppEvent::DirStatsProgress { scanned });
+        });
+        let _ = event_tx.send(AppEvent::DirStatsResult { root, res });
+    });
+}
+
+pub fn process_dir_stats_result(
+    context: &mut AppContext,
+    root: PathBuf,
+    res: io::Result<DirStats>,
+) {
+    context
+        .worker_context_mut()
+        .clear_progress_msg(PROGRESS_JOB);
+    match res {
+        Ok(stats) => context.set_dir_stats(root, stats),
+        // the view was closed before the walk finished
+        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+        Err(e) => context
+            .message_queue_mut()
+            .push_notification(format!("{}: {}", root.display(), e), MessageLevel::Error),
+    }
+}
+
+fn jump_to(context: &mut AppContext, path: &Path) -> AppResult {
+    if let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) {
+        change_directory::change_directory(context, parent)?;
+        cursor_move::to_path(context, Path::new(file_name))?;
+    }
+    Ok(())
+}
+
+/// Computes statistics of the current directory and its subdirectories in the background
+/// and shows them once they are ready
+pub fn dir_stats(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+) -> AppResult {
+    let root = context.tab_context_ref().curr_tab_ref().cwd().to_path_buf();
+    context.clear_dir_stats();
+    let cancel = Arc::new(AtomicBool::new(false));
+    compute_in_background(context, root.clone(), cancel.clone());
+
+    let res = show_dir_stats(context, backend, keymap_t, &root);
+    // the walk is of no use once the view is closed
+    cancel.store(true, Ordering::Relaxed);
+    res
+}
+
+fn show_dir_stats(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+    root: &Path,
+) -> AppResult {
+    context.flush_event();
+
+    let mut cursor: usize = 0;
+    loop {
+        // results of an earlier `stats` of another directory are not shown
+        let stats = context
+            .dir_stats_ref()
+            .filter(|(path, _)| *path == root)
+            .map(|(_, stats)| stats);
+        let file_count = stats.map_or(0, |s| s.listed_files().count());
+        cursor = cursor.min(file_count.saturating_sub(1));
+        let progress = context.worker_context_ref().get_msg();
+        backend.render(TuiDirStats::new(root, stats, progress, cursor));
+        let cursor_path = stats
+            .and_then(|s| s.listed_files().nth(cursor))
+            .map(Path::to_path_buf);
+
+        let event = match context.poll_event() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+        match event {
+            AppEvent::Termion(event) => {
+                match event {
+                    Event::Key(Key::Esc) => break,
+                    Event::Key(Key::Char('\n')) => {
+                        if let Some(path) = cursor_path {
+                            return jump_to(context, &path);
+                        }
+                    }
+                    event => {
+                        if let Some(CommandKeybind::SimpleKeybind { commands, .. }) =
+                            keymap_t.help_view.get(&event)
+                        {
+                            for command in commands {
+                                match command {
+                                    Command::CursorMoveUp { .. } => {
+                                        cursor = cursor.saturating_sub(1)
+                                    }
+                                    Command::CursorMoveDown { .. } => {
+                                        cursor = cursor.saturating_add(1)
+                                    }
+                                    Command::CursorMoveHome => cursor = 0,
+                                    Command::CursorMoveEnd => cursor = usize::MAX,
+                                    Command::CloseTab => return Ok(()),
+                                    _ => (),
+                                }
+                            }
+                        }
+                    }
+                }
+                context.flush_event();
+            }
+            event => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
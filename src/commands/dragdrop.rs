@@ -0,0 +1,43 @@
+use shell_words::split;
+
+use crate::config::clean::mimetype::ProgramEntry;
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::util::process::fork_execute;
+
+/// Passes the selected files to the `dragdrop_command` of `joshuto.toml`,
+/// e.g. `dragon-drop` or `ripdrag`
+pub fn dragdrop(context: &mut AppContext) -> AppResult {
+    let paths = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .map(|s| s.get_selected_paths())
+        .unwrap_or_default();
+    if paths.is_empty() {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            "No files selected".to_string(),
+        ));
+    }
+
+    let command = context.config_ref().dragdrop_command.clone();
+    let mut words = split(&command).map_err(|_| {
+        AppError::new(
+            AppErrorKind::InvalidParameters,
+            format!("Cannot split dragdrop command: {}", command),
+        )
+    })?;
+    if words.is_empty() {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            "dragdrop_command is empty".to_string(),
+        ));
+    }
+    let mut entry = ProgramEntry::new(words.remove(0));
+    entry.args(words.into_iter()).fork(true).silent(true);
+
+    let (child_id, handle) = fork_execute(&entry, &paths, context.clone_event_tx())?;
+    context.worker_context_mut().push_child(child_id, handle);
+    Ok(())
+}
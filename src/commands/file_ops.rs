@@ -37,9 +37,10 @@ pub fn symlink_relative(context: &mut AppContext) -> AppResult {
     Ok(())
 }
 
-pub fn paste(context: &mut AppContext, options: FileOperationOptions) -> AppResult {
+pub fn paste(context: &mut AppContext, mut options: FileOperationOptions) -> AppResult {
     match context.take_local_state() {
         Some(state) if !state.paths.is_empty() => {
+            options.rename_pattern = context.config_ref().paste_rename_pattern.clone();
             let dest = context.tab_context_ref().curr_tab_ref().cwd().to_path_buf();
             let worker_thread = IoWorkerThread::new(state.file_op, state.paths, dest, options);
             context.worker_context_mut().push_worker(worker_thread);
@@ -58,7 +59,7 @@ pub fn copy_filename(context: &mut AppContext) -> AppResult {
         .curr_tab_ref()
         .curr_list_ref()
         .and_then(|c| c.curr_entry_ref())
-        .map(|entry| entry.file_name().to_string());
+        .map(|entry| entry.file_name_os().to_string_lossy().to_string());
 
     if let Some(file_name) = entry_file_name {
         copy_string_to_buffer(file_name)?;
@@ -73,11 +74,11 @@ pub fn copy_filename_without_extension(context: &mut AppContext) -> AppResult {
         .curr_list_ref()
         .and_then(|c| c.curr_entry_ref())
         .map(|entry| {
-            entry
-                .file_name()
+            let file_name = entry.file_name_os().to_string_lossy();
+            file_name
                 .rsplit_once('.')
                 .map(|(name, _)| name.to_string())
-                .unwrap_or_else(|| entry.file_name().to_string())
+                .unwrap_or_else(|| file_name.to_string())
         });
 
     if let Some(file_name) = entry_file_name {
@@ -125,7 +126,7 @@ pub fn copy_dirpath(context: &mut AppContext) -> AppResult {
     Ok(())
 }
 
-fn copy_string_to_buffer(string: String) -> AppResult {
+pub fn copy_string_to_buffer(string: String) -> AppResult {
     let clipboards = [
         (
             "wl-copy",
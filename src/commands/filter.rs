@@ -1,9 +1,18 @@
 use crate::context::{AppContext, MatchContext};
 use crate::error::AppResult;
 
-use super::reload;
+use super::{folder_settings, reload};
 
 pub fn filter(context: &mut AppContext, filter_context: MatchContext) -> AppResult {
+    match &filter_context {
+        MatchContext::String { pattern, .. } => {
+            let pattern = pattern.clone();
+            folder_settings::update_folder_setting(context, |s| s.filter = Some(pattern))?;
+        }
+        MatchContext::None => folder_settings::update_folder_setting(context, |s| s.filter = None)?,
+        _ => {}
+    }
+
     let curr_tab = context.tab_context_mut().curr_tab_mut();
     let path = curr_tab.cwd().to_path_buf();
 
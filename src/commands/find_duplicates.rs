@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use termion::event::{Event, Key};
+
+use crate::config::clean::keymap::AppKeyMapping;
+use crate::context::{AppContext, MessageLevel};
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::io::{FileOperation, FileOperationOptions, IoWorkerThread};
+use crate::key_command::{Command, CommandKeybind};
+use crate::ui::widgets::{TuiDuplicates, TuiPrompt};
+use crate::ui::AppBackend;
+use crate::util::duplicates::{self, DuplicateGroup};
+
+// key of the progress message of the search, see `WorkerContext::set_progress_msg`
+pub const PROGRESS_JOB: &str = "duplicates";
+
+/// Searches the current directory and its subdirectories for duplicates in the background
+pub fn find_duplicates(context: &mut AppContext) -> AppResult {
+    let root = context.tab_context_ref().curr_tab_ref().cwd().to_path_buf();
+    let event_tx = context.clone_event_tx();
+    let _ = thread::spawn(move || {
+        let res = duplicates::find_duplicates(&root, |processed, total| {
+            let _ = event_tx.send(AppEvent::DuplicatesProgress { processed, total });
+        });
+        let _ = event_tx.send(AppEvent::DuplicatesResult { root, res });
+    });
+    context
+        .message_queue_mut()
+        .push_info("Searching for duplicates...".to_string());
+    Ok(())
+}
+
+pub fn process_duplicates_result(
+    context: &mut AppContext,
+    root: PathBuf,
+    res: io::Result<Vec<DuplicateGroup>>,
+) {
+    context
+        .worker_context_mut()
+        .clear_progress_msg(PROGRESS_JOB);
+    match res {
+        Ok(groups) => {
+            let msg = if groups.is_empty() {
+                format!("No duplicates found in {}", root.display())
+            } else {
+                format!(
+                    "Found {} groups of duplicates, use `show_duplicates` to review them",
+                    groups.len()
+                )
+            };
+            context
+                .message_queue_mut()
+                .push_notification(msg, MessageLevel::Success);
+            context.set_duplicates(root, groups);
+        }
+        Err(e) => context
+            .message_queue_mut()
+            .push_notification(format!("{}: {}", root.display(), e), MessageLevel::Error),
+    }
+}
+
+/// Shows the results of the last `find_duplicates`, where files can be marked and deleted
+pub fn show_duplicates(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+) -> AppResult {
+    if context.duplicates_ref().is_none() {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            "No results, run `find_duplicates` first".to_string(),
+        ));
+    }
+    context.flush_event();
+
+    let mut cursor: usize = 0;
+    let mut marked: HashSet<PathBuf> = HashSet::new();
+
+    while let Some((root, groups)) = context.duplicates_ref() {
+        let file_count: usize = groups.iter().map(|g| g.paths.len()).sum();
+        cursor = cursor.min(file_count.saturating_sub(1));
+        backend.render(TuiDuplicates::new(root, groups, cursor, &marked));
+        let cursor_path = groups
+            .iter()
+            .flat_map(|g| g.paths.iter())
+            .nth(cursor)
+            .cloned();
+
+        let event = match context.poll_event() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+        match event {
+            AppEvent::Termion(event) => {
+                match event {
+                    Event::Key(Key::Esc) => break,
+                    Event::Key(Key::Char(' ')) => {
+                        if let Some(path) = cursor_path {
+                            if !marked.remove(&path) {
+                                if keeps_a_copy(groups, &marked, &path) {
+                                    marked.insert(path);
+                                } else {
+                                    context.message_queue_mut().push_error(
+                                        "The last copy of a file cannot be marked".to_string(),
+                                    );
+                                }
+                            }
+                            cursor = cursor.saturating_add(1);
+                        }
+                    }
+                    Event::Key(Key::Char('a')) => {
+                        for group in groups {
+                            marked.extend(group.paths.iter().skip(1).cloned());
+                        }
+                    }
+                    Event::Key(Key::Char('u')) => marked.clear(),
+                    Event::Key(Key::Char('d')) if !marked.is_empty() => {
+                        delete_marked(context, backend, &mut marked);
+                    }
+                    event => {
+                        if let Some(CommandKeybind::SimpleKeybind { commands, .. }) =
+                            keymap_t.help_view.get(&event)
+                        {
+                            for command in commands {
+                                match command {
+                                    Command::CursorMoveUp { .. } => {
+                                        cursor = cursor.saturating_sub(1)
+                                    }
+                                    Command::CursorMoveDown { .. } => {
+                                        cursor = cursor.saturating_add(1)
+                                    }
+                                    Command::CursorMoveHome => cursor = 0,
+                                    Command::CursorMoveEnd => cursor = usize::MAX,
+                                    Command::CloseTab => return Ok(()),
+                                    _ => (),
+                                }
+                            }
+                        }
+                    }
+                }
+                context.flush_event();
+            }
+            event => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// whether a file of the group of `path` is left unmarked once `path` is marked
+fn keeps_a_copy(groups: &[DuplicateGroup], marked: &HashSet<PathBuf>, path: &Path) -> bool {
+    groups
+        .iter()
+        .find(|g| g.paths.iter().any(|p| p == path))
+        .map_or(true, |g| {
+            g.paths.iter().any(|p| p != path && !marked.contains(p))
+        })
+}
+
+fn delete_marked(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    marked: &mut HashSet<PathBuf>,
+) {
+    if context.config_ref().readonly {
+        context
+            .message_queue_mut()
+            .push_error("Cannot delete files in readonly mode".to_string());
+        return;
+    }
+    let prompt_str = format!("Delete {} files? (y/N)", marked.len());
+    let key = TuiPrompt::new(&prompt_str).get_key(backend, context);
+    if key != Key::Char('y') {
+        return;
+    }
+
+    let paths: Vec<PathBuf> = marked.drain().collect();
+    if let Some((_, groups)) = context.duplicates_mut() {
+        for group in groups.iter_mut() {
+            group.paths.retain(|p| !paths.contains(p));
+        }
+        groups.retain(|g| g.paths.len() > 1);
+    }
+
+    let options = FileOperationOptions {
+        overwrite: false,
+        skip_exist: false,
+        permanently: !context.config_ref().use_trash,
+        shred_passes: 0,
+        ..Default::default()
+    };
+    let worker_thread = IoWorkerThread::new(FileOperation::Delete, paths, PathBuf::new(), options);
+    context.worker_context_mut().push_worker(worker_thread);
+}
@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path;
+
+use crate::config::clean::folder_settings::FolderSetting;
+use crate::config::raw::folder_settings::FolderSettingsRaw;
+use crate::config::{search_directories, ConfigType};
+use crate::context::{AppContext, MatchContext};
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::history::{folder_sort_options, DirectoryHistory};
+
+use crate::{CONFIG_HIERARCHY, FOLDER_SETTINGS_T};
+
+use super::reload;
+
+fn find_folder_settings_file() -> Option<path::PathBuf> {
+    match search_directories(ConfigType::FolderSettings.as_filename(), &CONFIG_HIERARCHY) {
+        Some(file_path) => Some(file_path),
+        None => CONFIG_HIERARCHY
+            .iter()
+            .find(|p| p.exists())
+            .map(|p| p.join(ConfigType::FolderSettings.as_filename())),
+    }
+}
+
+fn write_folder_settings() -> AppResult {
+    let file_path = match find_folder_settings_file() {
+        Some(file_path) => file_path,
+        // `--no-config` clears the hierarchy
+        None if CONFIG_HIERARCHY.is_empty() => {
+            return Err(AppError::new(
+                AppErrorKind::InvalidParameters,
+                "Folder settings are not saved with --no-config".to_string(),
+            ))
+        }
+        None => {
+            return Err(AppError::new(
+                AppErrorKind::Io(io::ErrorKind::NotFound),
+                "No config directory to save folder settings in".to_string(),
+            ))
+        }
+    };
+    let raw = FolderSettingsRaw::from(&*FOLDER_SETTINGS_T.lock().unwrap());
+    let content = toml::to_string(&raw).map_err(|e| {
+        AppError::new(
+            AppErrorKind::ParseError,
+            format!("Failed to serialize folder settings: {}", e),
+        )
+    })?;
+    let mut file = File::create(file_path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+fn refresh_all_tabs(context: &mut AppContext) -> AppResult {
+    for (_, tab) in context.tab_context_mut().iter_mut() {
+        tab.history_mut().depreciate_all_entries();
+    }
+    reload::soft_reload_curr_tab(context)?;
+    Ok(())
+}
+
+/// Remembers the sort order, hidden files visibility and filter of the current directory
+pub fn save_folder_settings(context: &mut AppContext) -> AppResult {
+    let curr_tab = context.tab_context_ref().curr_tab_ref();
+    let cwd = curr_tab.cwd().to_path_buf();
+    let sort_options = folder_sort_options(&cwd, curr_tab.option_ref());
+    let filter = match curr_tab
+        .option_ref()
+        .dirlist_options_ref(&cwd)
+        .map(|o| o.filter_context_ref())
+    {
+        Some(MatchContext::String { pattern, .. }) => Some(pattern.clone()),
+        _ => None,
+    };
+
+    {
+        let mut folder_settings = FOLDER_SETTINGS_T.lock().unwrap();
+        let show_hidden = folder_settings
+            .get(&cwd)
+            .and_then(|s| s.show_hidden)
+            .unwrap_or_else(|| context.config_ref().display_options_ref().show_hidden());
+        let setting = FolderSetting {
+            sort_method: sort_options.sort_methods.list.front().copied(),
+            reverse: Some(sort_options.reverse),
+            show_hidden: Some(show_hidden),
+            filter,
+        };
+        folder_settings.insert(cwd, setting);
+    }
+    write_folder_settings()?;
+    context
+        .message_queue_mut()
+        .push_info("Saved folder settings".to_string());
+    Ok(())
+}
+
+pub fn clear_folder_settings(context: &mut AppContext) -> AppResult {
+    let cwd = context.tab_context_ref().curr_tab_ref().cwd().to_path_buf();
+    let removed = FOLDER_SETTINGS_T.lock().unwrap().remove(&cwd).is_some();
+    if removed {
+        write_folder_settings()?;
+        refresh_all_tabs(context)?;
+    }
+    Ok(())
+}
+
+/// Keeps the saved settings of the current directory, if there are any,
+/// in sync with view changes made while inside of it
+pub fn update_folder_setting<F>(context: &AppContext, f: F) -> AppResult
+where
+    F: FnOnce(&mut FolderSetting),
+{
+    let cwd = context.tab_context_ref().curr_tab_ref().cwd();
+    let updated = match FOLDER_SETTINGS_T.lock().unwrap().get_mut(cwd) {
+        Some(setting) => {
+            f(setting);
+            true
+        }
+        None => false,
+    };
+    if updated {
+        write_folder_settings()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::commands::change_directory::change_directory;
+use crate::commands::cursor_move;
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::fs::LinkType;
+
+/// Returns the absolute path the symlink under the cursor points to
+fn link_target(context: &AppContext) -> AppResult<PathBuf> {
+    let entry = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|list| list.curr_entry_ref())
+        .ok_or_else(|| {
+            AppError::new(
+                AppErrorKind::InvalidParameters,
+                "No file selected".to_string(),
+            )
+        })?;
+    if !matches!(entry.metadata.link_type(), LinkType::Symlink { .. }) {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            format!("{}: not a symbolic link", entry.file_name()),
+        ));
+    }
+
+    let path = entry.file_path();
+    let target = fs::read_link(path)?;
+    // relative targets are relative to the directory containing the link
+    Ok(match path.parent() {
+        Some(parent) if target.is_relative() => parent.join(target),
+        _ => target,
+    })
+}
+
+pub fn follow_symlink(context: &mut AppContext) -> AppResult {
+    let target = link_target(context)?;
+    let (parent, file_name) = match (target.parent(), target.file_name()) {
+        (Some(parent), Some(file_name)) => (fs::canonicalize(parent)?, file_name.to_os_string()),
+        // the target is `/` or ends in `..`
+        _ => {
+            let target = fs::canonicalize(&target)?;
+            return change_directory(context, &target);
+        }
+    };
+
+    change_directory(context, &parent)?;
+    let index = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|list| list.iter().position(|e| e.file_name_os() == file_name));
+    match index {
+        Some(index) => cursor_move::cursor_move(context, index),
+        None => context.message_queue_mut().push_warning(format!(
+            "{}: not found, the link may be broken or the target hidden",
+            parent.join(&file_name).display()
+        )),
+    }
+    Ok(())
+}
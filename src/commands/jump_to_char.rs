@@ -0,0 +1,107 @@
+use termion::event::Key;
+
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::fs::JoshutoDirList;
+use crate::ui::widgets::TuiPrompt;
+use crate::ui::AppBackend;
+
+use super::cursor_move;
+
+/// Like smart case: an uppercase letter in `prefix` makes the match case sensitive
+fn starts_with(file_name: &str, prefix: &str) -> bool {
+    if prefix.chars().any(|c| c.is_uppercase()) {
+        file_name.starts_with(prefix)
+    } else {
+        file_name.to_lowercase().starts_with(prefix)
+    }
+}
+
+/// Finds the next entry starting with `prefix`, beginning at `offset` and wrapping around
+fn find_prefix(
+    curr_list: &JoshutoDirList,
+    prefix: &str,
+    offset: usize,
+    forward: bool,
+) -> Option<usize> {
+    let len = curr_list.len();
+    (0..len)
+        .map(|i| {
+            if forward {
+                (offset + i) % len
+            } else {
+                (offset + len - i) % len
+            }
+        })
+        .find(|i| starts_with(curr_list.contents[*i].file_name(), prefix))
+}
+
+fn jump(context: &mut AppContext, prefix: &str, skip_current: bool, forward: bool) -> bool {
+    let index = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|list| {
+            let curr = list.get_index()?;
+            let offset = match (skip_current, forward) {
+                (false, _) => curr,
+                (true, true) => curr + 1,
+                (true, false) => curr + list.len() - 1,
+            };
+            find_prefix(list, prefix, offset, forward)
+        });
+    match index {
+        Some(index) => {
+            cursor_move::cursor_move(context, index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Jumps to entries beginning with the typed characters until ESC or Enter is pressed
+pub fn jump_to_char(context: &mut AppContext, backend: &mut AppBackend) -> AppResult {
+    let mut prefix = String::new();
+    let mut found = true;
+    loop {
+        let prompt_str = if found {
+            format!("jump: {}", prefix)
+        } else {
+            format!("jump: {} (no match)", prefix)
+        };
+        let key = TuiPrompt::new(&prompt_str).get_key(backend, context);
+        match key {
+            Key::Char('\n') | Key::Esc => break,
+            Key::Backspace => {
+                prefix.pop();
+                found = true;
+            }
+            Key::Char(c) => {
+                let first = prefix.is_empty();
+                prefix.push(c);
+                // the first character moves on like vim's `f`, later ones refine the match
+                found = jump(context, &prefix, first, true);
+            }
+            _ => break,
+        }
+    }
+    context.flush_event();
+    if !prefix.is_empty() {
+        context.set_jump_prefix(prefix);
+    }
+    Ok(())
+}
+
+pub fn jump_next(context: &mut AppContext) -> AppResult {
+    if let Some(prefix) = context.jump_prefix_ref().map(|s| s.to_string()) {
+        jump(context, &prefix, true, true);
+    }
+    Ok(())
+}
+
+pub fn jump_prev(context: &mut AppContext) -> AppResult {
+    if let Some(prefix) = context.jump_prefix_ref().map(|s| s.to_string()) {
+        jump(context, &prefix, true, false);
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use termion::event::Event;
+
+use crate::config::clean::keymap::KeymapEdit;
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::key_command::Command;
+
+// The keymap is only borrowed while commands run,
+// so changes are queued and applied by the main loop afterwards
+pub fn map(context: &mut AppContext, keys: &[Event], command: &Command) -> AppResult {
+    context.push_keymap_edit(KeymapEdit::Map {
+        events: keys.to_vec(),
+        commands: vec![command.clone()],
+    });
+    Ok(())
+}
+
+pub fn unmap(context: &mut AppContext, keys: &[Event]) -> AppResult {
+    context.push_keymap_edit(KeymapEdit::Unmap {
+        events: keys.to_vec(),
+    });
+    Ok(())
+}
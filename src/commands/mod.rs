@@ -2,28 +2,44 @@ pub mod bookmark;
 pub mod bulk_rename;
 pub mod case_sensitivity;
 pub mod change_directory;
+pub mod checksum;
 pub mod command_line;
+pub mod command_palette;
+pub mod compare_tabs;
 pub mod cursor_move;
 pub mod custom_search;
 pub mod delete_files;
+pub mod dir_stats;
+pub mod dragdrop;
 pub mod escape;
 pub mod file_ops;
 pub mod filter;
 pub mod filter_glob;
 pub mod filter_regex;
 pub mod filter_string;
+pub mod find_duplicates;
 pub mod flat;
+pub mod folder_settings;
+pub mod follow_symlink;
 pub mod fzf;
+pub mod jump_to_char;
 pub mod line_nums;
 pub mod linemode;
+pub mod map;
 pub mod new_directory;
 pub mod numbered_command;
 pub mod open_file;
+pub mod open_terminal;
 pub mod parent_cursor_move;
+pub mod preview_cache;
 pub mod preview_cursor_move;
 pub mod quit;
 pub mod reload;
+pub mod reload_config;
 pub mod rename_file;
+pub mod rename_regex;
+pub mod rename_transform;
+pub mod script;
 pub mod search;
 pub mod search_fzf;
 pub mod search_glob;
@@ -37,11 +53,18 @@ pub mod select_string;
 pub mod set_mode;
 pub mod show_help;
 pub mod show_hidden;
+pub mod show_messages;
+pub mod show_properties;
 pub mod show_tasks;
+pub mod size_format;
 pub mod sort;
 pub mod sub_process;
 pub mod subdir_fzf;
+pub mod suspend;
 pub mod tab_ops;
 pub mod touch_file;
+pub mod tree;
 pub mod uimodes;
+pub mod view_file;
+pub mod xattr;
 pub mod zoxide;
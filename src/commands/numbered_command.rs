@@ -69,7 +69,11 @@ pub fn numbered_command(
                 }
                 context.flush_event();
             }
-            event => process_event::process_noninteractive(event, context),
+            event => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
         }
     }
 }
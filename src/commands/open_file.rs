@@ -1,13 +1,16 @@
+use std::ffi::OsStr;
 use std::io;
 use std::path;
 
-use crate::commands::{quit, reload};
+use crate::commands::{quit, reload, tree};
 use crate::config::clean::mimetype::ProgramEntry;
 use crate::context::AppContext;
 use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::fs::JoshutoDirEntry;
 use crate::ui::views::DummyListener;
 use crate::ui::views::TuiTextField;
 use crate::ui::AppBackend;
+use crate::util::hooks::{self, Hook};
 use crate::util::mimetype::get_mimetype;
 use crate::util::process::{execute_and_wait, fork_execute};
 
@@ -15,28 +18,29 @@ use super::change_directory;
 
 use crate::MIMETYPE_T;
 
-fn _get_options<'a>(path: &path::Path) -> Vec<&'a ProgramEntry> {
-    let mut options: Vec<&ProgramEntry> = Vec::new();
+fn _get_options(path: &path::Path) -> Vec<ProgramEntry> {
+    let mimetype_t = MIMETYPE_T.read().unwrap();
+    let mut options: Vec<ProgramEntry> = Vec::new();
 
     if let Some(entries) = path
         .extension()
         .and_then(|ext| ext.to_str())
-        .and_then(|file_ext| MIMETYPE_T.app_list_for_ext(file_ext))
+        .and_then(|file_ext| mimetype_t.app_list_for_ext(file_ext))
     {
-        options.extend(entries);
+        options.extend(entries.iter().cloned());
         return options;
     }
 
     if let Ok(file_mimetype) = get_mimetype(path) {
-        if let Some(entry) = MIMETYPE_T.app_list_for_mimetype(file_mimetype.get_type()) {
+        if let Some(entry) = mimetype_t.app_list_for_mimetype(file_mimetype.get_type()) {
             match entry.subtypes().get(file_mimetype.get_subtype()) {
                 Some(entries) => {
-                    options.extend(entries);
+                    options.extend(entries.iter().cloned());
                     return options;
                 }
                 None => {
                     let entries = entry.app_list();
-                    options.extend(entries);
+                    options.extend(entries.iter().cloned());
                     return options;
                 }
             }
@@ -90,7 +94,7 @@ fn _open_with_xdg(
 fn _open_with_helper<S>(
     context: &mut AppContext,
     backend: &mut AppBackend,
-    options: Vec<&ProgramEntry>,
+    options: Vec<ProgramEntry>,
     files: &[S],
 ) -> std::io::Result<()>
 where
@@ -147,34 +151,56 @@ where
     Ok(())
 }
 
+/// Marked files `open` acts on instead of the file under the cursor
+fn selected_files(context: &AppContext) -> Vec<JoshutoDirEntry> {
+    context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .map_or_else(Vec::new, |s| s.iter_selected().cloned().collect())
+}
+
 pub fn open(context: &mut AppContext, backend: &mut AppBackend) -> AppResult {
     let curr_list = context.tab_context_ref().curr_tab_ref().curr_list_ref();
     let entry = curr_list.and_then(|s| s.curr_entry_ref().cloned());
+    let paths = selected_files(context);
+    // with `open_selected`, marked files take precedence over the directory under the cursor
+    let open_marked =
+        context.config_ref().open_selected && paths.iter().any(|e| !e.file_path().is_dir());
 
     match entry {
         None => (),
-        Some(entry) if entry.file_path().is_dir() => {
+        Some(entry) if entry.is_expandable() && !open_marked && tree::is_tree(context) => {
+            tree::toggle_expanded(context, entry.file_path())?;
+        }
+        Some(entry) if entry.file_path().is_dir() && !open_marked => {
             let path = entry.file_path().to_path_buf();
             change_directory::cd(path.as_path(), context)?;
             reload::soft_reload_curr_tab(context)?;
         }
         Some(entry) => {
-            if context.args.file_chooser {
+            if context.args.file_chooser || context.args.choosefile.is_some() {
                 return quit::quit_with_action(context, quit::QuitAction::OutputSelectedFiles);
             }
 
-            let paths = curr_list.map_or_else(Vec::new, |s| s.iter_selected().cloned().collect());
             let (path, files) = if paths.is_empty() {
-                (entry.file_path(), vec![entry.file_name()])
+                (entry.file_path(), vec![entry.file_name_os()])
             } else {
                 (
                     paths.first().unwrap().file_path(),
-                    paths.iter().map(|e| e.file_name()).collect(),
+                    paths.iter().map(|e| e.file_name_os()).collect(),
                 )
             };
             let options = _get_options(path);
             let option = options.iter().find(|option| option.program_exists());
 
+            let hook_files = if paths.is_empty() {
+                vec![entry.file_path().to_path_buf()]
+            } else {
+                paths.iter().map(|e| e.file_path().to_path_buf()).collect()
+            };
+            hooks::call_hook(context, Hook::FileOpen { files: hook_files });
+
             let config = context.config_ref();
 
             if let Some(option) = option {
@@ -194,11 +220,13 @@ pub fn open_with_index(
     backend: &mut AppBackend,
     index: usize,
 ) -> AppResult {
-    let paths = context
+    let paths: Vec<JoshutoDirEntry> = context
         .tab_context_ref()
         .curr_tab_ref()
         .curr_list_ref()
-        .map_or(vec![], |s| s.iter_selected().cloned().collect());
+        .map_or(vec![], |s| {
+            s.selected_or_current().into_iter().cloned().collect()
+        });
 
     if paths.is_empty() {
         return Err(AppError::new(
@@ -206,17 +234,23 @@ pub fn open_with_index(
             String::from("No files selected"),
         ));
     }
-    let files: Vec<&str> = paths.iter().map(|e| e.file_name()).collect();
+    let files: Vec<&OsStr> = paths.iter().map(|e| e.file_name_os()).collect();
     let options = _get_options(paths[0].file_path());
 
     if index >= options.len() {
         return Err(AppError::new(
             AppErrorKind::Io(std::io::ErrorKind::InvalidData),
-            "option does not exist".to_string(),
+            format!(
+                "option {} does not exist, there are {} for this file",
+                index,
+                options.len()
+            ),
         ));
     }
 
     let option = &options[index];
+    let hook_files = paths.iter().map(|e| e.file_path().to_path_buf()).collect();
+    hooks::call_hook(context, Hook::FileOpen { files: hook_files });
     _open_with_entry(context, backend, option, &files)?;
     Ok(())
 }
@@ -239,9 +273,53 @@ pub fn open_with_interactive(context: &mut AppContext, backend: &mut AppBackend)
                 .clone(),
         );
     }
-    let files: Vec<&str> = paths.iter().map(|e| e.file_name()).collect();
+    let files: Vec<&OsStr> = paths.iter().map(|e| e.file_name_os()).collect();
     let options = _get_options(paths[0].file_path());
 
+    let hook_files = paths.iter().map(|e| e.file_path().to_path_buf()).collect();
+    hooks::call_hook(context, Hook::FileOpen { files: hook_files });
     _open_with_helper(context, backend, options, &files)?;
     Ok(())
 }
+
+/// Opens all selected files with one invocation of `words`,
+/// or of the first program configured for the first file
+pub fn open_all(context: &mut AppContext, backend: &mut AppBackend, words: &[String]) -> AppResult {
+    let paths = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .map(|s| s.get_selected_paths())
+        .unwrap_or_default();
+    if paths.is_empty() {
+        return Err(AppError::new(
+            AppErrorKind::Io(io::ErrorKind::NotFound),
+            String::from("No files selected"),
+        ));
+    }
+
+    hooks::call_hook(
+        context,
+        Hook::FileOpen {
+            files: paths.clone(),
+        },
+    );
+    match words.split_first() {
+        Some((command, args)) => {
+            let mut option = ProgramEntry::new(command.clone());
+            option.args(args.iter().cloned());
+            backend.terminal_drop();
+            let res = execute_and_wait(&option, &paths);
+            backend.terminal_restore(context.config_ref().mouse_support)?;
+            res?;
+        }
+        None => {
+            let options = _get_options(&paths[0]);
+            match options.iter().find(|option| option.program_exists()) {
+                Some(option) => _open_with_entry(context, backend, option, &paths)?,
+                None => _open_with_helper(context, backend, options, &paths)?,
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::env;
+use std::process::Command;
+
+use shell_words::split;
+
+use crate::config::clean::mimetype::ProgramEntry;
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::ui::AppBackend;
+use crate::util::process::fork_execute;
+
+use super::reload;
+
+const ENV_SHELL: &str = "SHELL";
+
+/// Spawns the `terminal` of `joshuto.toml` in the current directory,
+/// or runs `$SHELL` in place of joshuto if no terminal is configured
+pub fn open_terminal(context: &mut AppContext, backend: &mut AppBackend) -> AppResult {
+    let cwd = context.tab_context_ref().curr_tab_ref().cwd().to_path_buf();
+
+    match context.config_ref().terminal.clone() {
+        Some(terminal) => {
+            let mut words = split(&terminal).map_err(|_| {
+                AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("Cannot split terminal command: {}", terminal),
+                )
+            })?;
+            if words.is_empty() {
+                return Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    "terminal command is empty".to_string(),
+                ));
+            }
+            let mut entry = ProgramEntry::new(words.remove(0));
+            entry
+                .args(words.into_iter())
+                .cwd(cwd)
+                .fork(true)
+                .silent(true);
+
+            let paths: [&str; 0] = [];
+            let (child_id, handle) = fork_execute(&entry, paths, context.clone_event_tx())?;
+            context.worker_context_mut().push_child(child_id, handle);
+            context
+                .message_queue_mut()
+                .push_info(format!("Spawned: {}", terminal));
+        }
+        None => {
+            let shell = env::var(ENV_SHELL).unwrap_or_else(|_| "sh".to_string());
+            context.remove_external_preview();
+            backend.terminal_drop();
+            let res = Command::new(&shell).current_dir(&cwd).status();
+            backend.terminal_restore(context.config_ref().mouse_support)?;
+            let _ = reload::soft_reload_curr_tab(context);
+            res?;
+        }
+    }
+    Ok(())
+}
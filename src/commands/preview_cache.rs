@@ -0,0 +1,25 @@
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::preview::preview_cache::PreviewCache;
+use crate::util::format;
+
+pub fn clear_preview_cache(context: &mut AppContext) -> AppResult {
+    let preview_context = context.preview_context_mut();
+    preview_context.previews_mut().clear();
+    preview_context.set_image_preview(None);
+
+    // entries left from a session with the cache enabled are removed as well
+    let cache = preview_context
+        .cache_ref()
+        .cloned()
+        .or_else(|| PreviewCache::new(u64::MAX, None));
+    let freed = match cache {
+        Some(cache) => cache.clear()?,
+        None => 0,
+    };
+    context.message_queue_mut().push_success(format!(
+        "Cleared preview cache ({})",
+        format::file_size_to_string(freed)
+    ));
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use std::io;
 
+use termion::event::Key;
+
 use crate::context::AppContext;
 use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::ui::widgets::TuiPrompt;
+use crate::ui::AppBackend;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum QuitAction {
@@ -10,6 +14,9 @@ pub enum QuitAction {
     Force,
     OutputCurrentDirectory,
     OutputSelectedFiles,
+    /// Terminated by this signal. Running file operations are finished first,
+    /// queued ones are dropped
+    Terminate(i32),
 }
 
 impl QuitAction {
@@ -20,18 +27,25 @@ impl QuitAction {
             Self::Force => 100,
             Self::OutputCurrentDirectory => 101,
             Self::OutputSelectedFiles => 102,
+            // the exit code of shells for processes killed by a signal
+            Self::Terminate(signal) => 128 + signal,
         }
     }
 }
 
+/// Number of file operations running or waiting in the background
+fn pending_tasks(context: &AppContext) -> usize {
+    let worker_context = context.worker_context_ref();
+    worker_context.iter().count() + worker_context.running_count()
+}
+
 pub fn quit_with_action(context: &mut AppContext, quit_action: QuitAction) -> AppResult {
     if quit_action == QuitAction::Force {
         context.quit = quit_action;
         return Ok(());
     }
 
-    let worker_context = context.worker_context_ref();
-    if worker_context.is_busy() || !worker_context.is_empty() {
+    if pending_tasks(context) > 0 {
         Err(AppError::new(
             AppErrorKind::Io(io::ErrorKind::Other),
             String::from("operations running in background, use `quit --force` to quit"),
@@ -41,3 +55,38 @@ pub fn quit_with_action(context: &mut AppContext, quit_action: QuitAction) -> Ap
         Ok(())
     }
 }
+
+/// Quits after asking the user if background tasks would be stopped,
+/// or other tabs would be closed unless `all_tabs` is set
+pub fn quit_with_confirm(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    quit_action: QuitAction,
+    all_tabs: bool,
+) -> AppResult {
+    if quit_action == QuitAction::Force {
+        context.quit = quit_action;
+        return Ok(());
+    }
+
+    let mut warnings = Vec::new();
+    let tasks = pending_tasks(context);
+    if tasks > 0 {
+        warnings.push(format!("{} background tasks will be stopped", tasks));
+    }
+    let tabs = context.tab_context_ref().len();
+    if tabs > 1 && !all_tabs {
+        warnings.push(format!("{} tabs are open", tabs));
+    }
+    if warnings.is_empty() {
+        context.quit = quit_action;
+        return Ok(());
+    }
+
+    let prompt_str = format!("{}, quit anyway? (y/N)", warnings.join(", "));
+    let key = TuiPrompt::new(&prompt_str).get_key(backend, context);
+    if key == Key::Char('y') {
+        context.quit = quit_action;
+    }
+    Ok(())
+}
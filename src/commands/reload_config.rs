@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use crate::config::clean::app::AppConfig;
+use crate::config::clean::keymap::{AppKeyMapping, KeymapEdit};
+use crate::config::clean::mimetype::AppProgramRegistry;
+use crate::config::clean::theme::AppTheme;
+use crate::config::problems;
+use crate::config::{search_config_directories, ConfigType, TomlConfigFile};
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::history::DirectoryHistory;
+use crate::ui::AppBackend;
+use crate::util::{format, logging, syntax};
+use crate::{MIMETYPE_T, THEME_T};
+
+/// Config files read again by `reload_config`
+pub const RELOADED_CONFIGS: [ConfigType; 4] = [
+    ConfigType::App,
+    ConfigType::Keymap,
+    ConfigType::Mimetype,
+    ConfigType::Theme,
+];
+
+/// Paths of the reloadable config files in use
+pub fn config_paths() -> Vec<PathBuf> {
+    RELOADED_CONFIGS
+        .iter()
+        .filter_map(|config_type| search_config_directories(config_type.as_filename()))
+        .collect()
+}
+
+/// Reads joshuto.toml, keymap.toml, mimetype.toml and theme.toml again
+/// and applies them. Nothing changes if one of them fails to parse
+pub fn reload_config(context: &mut AppContext) -> AppResult {
+    let res = _reload_config(context);
+    // skipped keybindings and the like
+    for problem in problems::take_problems() {
+        context
+            .message_queue_mut()
+            .push_warning(problem.to_string());
+    }
+    res
+}
+
+fn _reload_config(context: &mut AppContext) -> AppResult {
+    let mut config = AppConfig::reload_config()?;
+    let keymap = AppKeyMapping::reload_config_with_aliases(&config.cmd_aliases)?;
+    let mimetype = AppProgramRegistry::reload_config()?;
+    let theme = if config.display_options_ref().high_contrast() {
+        AppTheme::high_contrast()
+    } else {
+        AppTheme::reload_config()?
+    };
+
+    config.readonly |= context.args.readonly;
+    // checked at startup already
+    if let Some(level) = context.args.log_level.as_deref() {
+        config.log_level = level.parse().unwrap_or(config.log_level);
+    }
+    // the log file stays the one opened at startup
+    config.log_file = context.config_ref().log_file.clone();
+    // chosen at startup, depending on the terminal
+    config._preview_options_mut().preview_protocol = context
+        .config_ref()
+        .preview_options_ref()
+        .preview_protocol
+        .clone();
+    format::set_size_format(config.display_options_ref().size_format());
+    logging::set_level(config.log_level);
+    AppBackend::set_colors(config.display_options_ref().colors().enabled());
+    *context.config_mut() = config;
+    context.push_keymap_edit(KeymapEdit::Replace(Box::new(keymap)));
+    *MIMETYPE_T.write().unwrap() = mimetype;
+    *THEME_T.write().unwrap() = theme;
+    syntax::reload_theme();
+
+    // previews and listings are built again with the new settings
+    context.preview_context_mut().previews_mut().clear();
+    for (_, tab) in context.tab_context_mut().iter_mut() {
+        tab.history_mut().depreciate_all_entries();
+        if let Some(s) = tab.curr_list_mut() {
+            s.depreciate();
+        }
+    }
+    context
+        .message_queue_mut()
+        .push_success("Reloaded config".to_string());
+    Ok(())
+}
@@ -60,7 +60,10 @@ fn _get_current_file_name(context: &mut AppContext) -> Option<String> {
         .tab_context_ref()
         .curr_tab_ref()
         .curr_list_ref()
-        .and_then(|list| list.curr_entry_ref().map(|s| s.file_name().to_string()))
+        .and_then(|list| {
+            list.curr_entry_ref()
+                .map(|s| s.file_name_os().to_string_lossy().to_string())
+        })
 }
 
 pub fn rename_file_append(
@@ -0,0 +1,364 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::{Regex, RegexBuilder};
+use termion::event::{Event, Key};
+
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::ui::views::TuiView;
+use crate::ui::widgets::TuiProperties;
+use crate::ui::AppBackend;
+use crate::util::string::escape_os_str;
+
+use super::reload;
+
+const FOOTER: &str = "y: rename  <ESC>: cancel";
+const FOOTER_CONFLICT: &str = "conflicts found, nothing will be renamed  <ESC>: close";
+
+/// A parsed `s/pattern/replacement/flags` expression
+#[derive(Debug)]
+pub struct Substitution {
+    pub regex: Regex,
+    pub replacement: String,
+    pub global: bool,
+}
+
+impl Substitution {
+    pub fn apply(&self, name: &str) -> String {
+        if self.global {
+            self.regex
+                .replace_all(name, self.replacement.as_str())
+                .into_owned()
+        } else {
+            self.regex
+                .replace(name, self.replacement.as_str())
+                .into_owned()
+        }
+    }
+}
+
+fn invalid(msg: String) -> AppError {
+    AppError::new(AppErrorKind::InvalidParameters, msg)
+}
+
+/// Parses `s/pattern/replacement/flags`, any character following `s` can be
+/// used as the delimiter. Supported flags are `g` (replace every match) and
+/// `i` (case insensitive)
+pub fn parse_substitution(s: &str) -> AppResult<Substitution> {
+    let mut chars = s.chars();
+    if chars.next() != Some('s') {
+        return Err(invalid(format!(
+            "Expected s/pattern/replacement/, got '{}'",
+            s
+        )));
+    }
+    let delim = match chars.next() {
+        Some(c) if !c.is_alphanumeric() && !c.is_whitespace() && c != '\\' => c,
+        _ => return Err(invalid(format!("Invalid delimiter in '{}'", s))),
+    };
+
+    // split on unescaped delimiters, escaped delimiters lose their backslash
+    let mut parts = vec![String::new()];
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(next) if next == delim => parts.last_mut().unwrap().push(next),
+                Some(next) => {
+                    let part = parts.last_mut().unwrap();
+                    part.push(c);
+                    part.push(next);
+                }
+                None => parts.last_mut().unwrap().push(c),
+            }
+        } else if c == delim && parts.len() < 3 {
+            parts.push(String::new());
+        } else {
+            parts.last_mut().unwrap().push(c);
+        }
+    }
+    if parts.len() != 3 {
+        return Err(invalid(format!(
+            "Expected s{0}pattern{0}replacement{0}, got '{1}'",
+            delim, s
+        )));
+    }
+    let flags = parts.pop().unwrap();
+    let replacement = parts.pop().unwrap();
+    let pattern = parts.pop().unwrap();
+
+    let mut global = false;
+    let mut case_insensitive = false;
+    for flag in flags.chars() {
+        match flag {
+            'g' => global = true,
+            'i' => case_insensitive = true,
+            c => return Err(invalid(format!("Unknown flag '{}'", c))),
+        }
+    }
+    if pattern.is_empty() {
+        return Err(invalid("Pattern is empty".to_string()));
+    }
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| invalid(e.to_string()))?;
+    Ok(Substitution {
+        regex,
+        replacement,
+        global,
+    })
+}
+
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_file(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+/// Returns why renaming `src` to `dest` would fail or overwrite something,
+/// `targets` holds the destinations of all renames seen so far
+fn rename_conflict(
+    src: &Path,
+    dest: &Path,
+    targets: &mut HashSet<PathBuf>,
+) -> Option<&'static str> {
+    let name = dest
+        .file_name()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    if name.is_empty() || name == "." || name == ".." || dest.parent() != src.parent() {
+        Some("invalid name")
+    } else if !targets.insert(dest.to_path_buf()) {
+        Some("duplicate")
+    } else if dest.symlink_metadata().is_ok() && !same_file(src, dest) {
+        // case only renames on case insensitive filesystems point at the same file
+        Some("exists")
+    } else {
+        None
+    }
+}
+
+/// Shows the pending renames as `old → new` and applies them once the user
+/// confirms with `y`. Nothing is renamed if any of them conflict
+pub fn confirm_renames(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    title: &str,
+    renames: Vec<(PathBuf, PathBuf)>,
+) -> AppResult {
+    let renames: Vec<(PathBuf, PathBuf)> = renames
+        .into_iter()
+        .filter(|(src, dest)| src != dest)
+        .collect();
+    if renames.is_empty() {
+        context
+            .message_queue_mut()
+            .push_info("No file names changed".to_string());
+        return Ok(());
+    }
+
+    let mut targets = HashSet::with_capacity(renames.len());
+    let mut conflicts = 0;
+    let rows: Vec<(String, String)> = renames
+        .iter()
+        .map(|(src, dest)| {
+            let old_name = src.file_name().map(escape_os_str).unwrap_or_default();
+            let new_name = dest.file_name().map(escape_os_str).unwrap_or_default();
+            let value = match rename_conflict(src, dest, &mut targets) {
+                Some(reason) => {
+                    conflicts += 1;
+                    format!("→ {}  [{}]", new_name, reason)
+                }
+                None => format!("→ {}", new_name),
+            };
+            (old_name, value)
+        })
+        .collect();
+    let title = format!(" {}: {} file(s) ", title, renames.len());
+    let footer = if conflicts > 0 {
+        FOOTER_CONFLICT
+    } else {
+        FOOTER
+    };
+
+    context.flush_event();
+    let confirmed = loop {
+        let _ = backend.terminal_mut().draw(|frame| {
+            let area = frame.size();
+            let mut view = TuiView::new(context);
+            view.show_bottom_status = false;
+            frame.render_widget(view, area);
+            frame.render_widget(TuiProperties::new(&title, &rows).footer(footer), area);
+        });
+
+        match context.poll_event() {
+            Ok(AppEvent::Termion(Event::Key(Key::Char('y')))) => break conflicts == 0,
+            Ok(AppEvent::Termion(Event::Key(_))) => break false,
+            Ok(AppEvent::Termion(_)) => context.flush_event(),
+            Ok(event) => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+            Err(_) => return Ok(()),
+        }
+    };
+    context.flush_event();
+    if !confirmed {
+        if conflicts > 0 {
+            context
+                .message_queue_mut()
+                .push_warning(format!("{} conflict(s), nothing renamed", conflicts));
+        }
+        return Ok(());
+    }
+
+    let mut renamed = 0;
+    let mut res = Ok(());
+    for (src, dest) in renames.iter() {
+        if let Err(e) = fs::rename(src, dest) {
+            res = Err(AppError::from(e));
+            break;
+        }
+        renamed += 1;
+    }
+    reload::soft_reload_curr_tab(context)?;
+    res?;
+    context
+        .message_queue_mut()
+        .push_success(format!("Renamed {} file(s)", renamed));
+    Ok(())
+}
+
+/// Paths of the selected entries, or the current entry if nothing is selected
+pub fn rename_targets(context: &AppContext) -> AppResult<Vec<PathBuf>> {
+    let paths: Vec<PathBuf> = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .map_or(vec![], |s| s.selected_or_current())
+        .into_iter()
+        .map(|entry| entry.file_path().to_path_buf())
+        .collect();
+    if paths.is_empty() {
+        return Err(invalid("No files selected".to_string()));
+    }
+    Ok(paths)
+}
+
+pub fn rename_regex(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    expression: &str,
+) -> AppResult {
+    let substitution = parse_substitution(expression)?;
+    let renames = rename_targets(context)?
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let new_name = substitution.apply(&name);
+            let dest = path.with_file_name(new_name);
+            Some((path, dest))
+        })
+        .collect();
+    confirm_renames(context, backend, "rename_regex", renames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn substitute(expr: &str, name: &str) -> String {
+        parse_substitution(expr).unwrap().apply(name)
+    }
+
+    #[test]
+    fn substitutions_replace_the_first_match() {
+        assert_eq!(substitute("s/a/b/", "banana"), "bbnana");
+    }
+
+    #[test]
+    fn global_substitutions_replace_all_matches() {
+        assert_eq!(substitute("s/a/b/g", "banana"), "bbnbnb");
+    }
+
+    #[test]
+    fn substitutions_can_ignore_case() {
+        assert_eq!(substitute("s/A/_/gi", "banana"), "b_n_n_");
+    }
+
+    #[test]
+    fn substitutions_support_other_delimiters_and_groups() {
+        assert_eq!(
+            substitute(r"s#(\d+)\.jpeg#img_$1.jpg#", "01.jpeg"),
+            "img_01.jpg"
+        );
+    }
+
+    #[test]
+    fn escaped_delimiters_are_literal() {
+        assert_eq!(substitute(r"s/\//-/g", "a/b"), "a-b");
+    }
+
+    #[test]
+    fn malformed_substitutions_are_refused() {
+        assert!(parse_substitution("s/a/b").is_err());
+        assert!(parse_substitution("s//b/").is_err());
+        assert!(parse_substitution("s/a/b/x").is_err());
+        assert!(parse_substitution("y/a/b/").is_err());
+    }
+
+    #[test]
+    fn renames_onto_the_same_target_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut targets = HashSet::new();
+        let new = dir.path().join("new");
+        assert_eq!(
+            rename_conflict(&dir.path().join("a"), &new, &mut targets),
+            None
+        );
+        assert_eq!(
+            rename_conflict(&dir.path().join("b"), &new, &mut targets),
+            Some("duplicate")
+        );
+    }
+
+    #[test]
+    fn renames_onto_existing_files_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("existing"), "").unwrap();
+        assert_eq!(
+            rename_conflict(
+                &dir.path().join("src"),
+                &dir.path().join("existing"),
+                &mut HashSet::new()
+            ),
+            Some("exists")
+        );
+    }
+
+    #[test]
+    fn names_with_slashes_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            rename_conflict(
+                &dir.path().join("src"),
+                &dir.path().join("a").join("b"),
+                &mut HashSet::new()
+            ),
+            Some("invalid name")
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::ui::AppBackend;
+use crate::util::name_transform::NameTransform;
+
+use super::rename_regex::{confirm_renames, rename_targets};
+
+pub fn rename_transform(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    transforms: &[NameTransform],
+) -> AppResult {
+    let renames = rename_targets(context)?
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let new_name = transforms
+                .iter()
+                .fold(name, |name, transform| transform.apply(&name));
+            let dest = path.with_file_name(new_name);
+            Some((path, dest))
+        })
+        .collect();
+    confirm_renames(context, backend, "rename_transform", renames)
+}
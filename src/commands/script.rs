@@ -0,0 +1,120 @@
+use crate::config::clean::keymap::AppKeyMapping;
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::ui::AppBackend;
+
+/// Runs `scripts/<name>.rhai` from the config directories.
+///
+/// Scripts get a snapshot of joshuto's state as variables:
+/// `args`, `cwd`, `cursor`, `selection` and `tabs`.
+/// Built-in commands are queued with `run("...")` and executed in order
+/// once the script finishes, `message("...")` shows a message.
+#[cfg(feature = "scripting")]
+pub fn run_script(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+    words: &[String],
+) -> AppResult {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::str::FromStr;
+
+    use rhai::{Array, Dynamic, Engine, Scope};
+
+    use crate::config::search_config_directories;
+    use crate::key_command::{AppExecute, Command};
+
+    let (name, args) = match words.split_first() {
+        Some(s) => s,
+        None => {
+            return Err(AppError::new(
+                AppErrorKind::InvalidParameters,
+                "script: no script name given".to_string(),
+            ))
+        }
+    };
+    let script_path =
+        search_config_directories(&format!("scripts/{}.rhai", name)).ok_or_else(|| {
+            AppError::new(
+                AppErrorKind::InvalidParameters,
+                format!("script: cannot find scripts/{}.rhai", name),
+            )
+        })?;
+
+    let commands: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+    let messages: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let mut engine = Engine::new();
+    {
+        let commands = commands.clone();
+        engine.register_fn("run", move |command: &str| {
+            commands.borrow_mut().push(command.to_string());
+        });
+    }
+    {
+        let messages = messages.clone();
+        engine.register_fn("message", move |message: &str| {
+            messages.borrow_mut().push(message.to_string());
+        });
+    }
+
+    let to_array =
+        |strings: Vec<String>| -> Array { strings.into_iter().map(Dynamic::from).collect() };
+
+    let curr_tab = context.tab_context_ref().curr_tab_ref();
+    let curr_list = curr_tab.curr_list_ref();
+    let cursor = curr_list
+        .and_then(|list| list.curr_entry_ref())
+        .map(|entry| entry.file_path().to_string_lossy().to_string())
+        .unwrap_or_default();
+    let selection = curr_list
+        .map(|list| list.get_selected_paths())
+        .unwrap_or_default()
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    let tabs = context
+        .tab_context_ref()
+        .tab_refs_in_order()
+        .iter()
+        .map(|tab| tab.cwd().to_string_lossy().to_string())
+        .collect();
+
+    let mut scope = Scope::new();
+    scope.push_constant("args", to_array(args.to_vec()));
+    scope.push_constant("cwd", curr_tab.cwd().to_string_lossy().to_string());
+    scope.push_constant("cursor", cursor);
+    scope.push_constant("selection", to_array(selection));
+    scope.push_constant("tabs", to_array(tabs));
+
+    let res = engine.run_file_with_scope(&mut scope, script_path);
+
+    for message in messages.take() {
+        context.message_queue_mut().push_info(message);
+    }
+    if let Err(e) = res {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            format!("script {}: {}", name, e),
+        ));
+    }
+
+    for command in commands.take() {
+        Command::from_str(&command)?.execute(context, backend, keymap_t)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "scripting"))]
+pub fn run_script(
+    _context: &mut AppContext,
+    _backend: &mut AppBackend,
+    _keymap_t: &AppKeyMapping,
+    _words: &[String],
+) -> AppResult {
+    Err(AppError::new(
+        AppErrorKind::UnrecognizedCommand,
+        "script: joshuto was built without the `scripting` feature".to_string(),
+    ))
+}
@@ -1,5 +1,3 @@
-use std::cmp::Ordering;
-
 use termion::event::{Event, Key};
 
 use crate::config::clean::keymap::AppKeyMapping;
@@ -60,7 +58,7 @@ pub fn help_loop(
                                             move_offset(&mut offset, 1)
                                         }
                                         Command::CursorMoveHome => offset = 0,
-                                        Command::CursorMoveEnd => offset = 255,
+                                        Command::CursorMoveEnd => offset = usize::MAX,
                                         Command::CursorMovePageUp(_) => {
                                             move_offset(&mut offset, -10)
                                         }
@@ -86,27 +84,21 @@ pub fn help_loop(
                 }
                 context.flush_event();
             }
-            _ => process_event::process_noninteractive(event, context),
+            event => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
         }
     }
 
     Ok(())
 }
 
-// offset is a u8, so if we make it negative program will fail.
-// This function prevents this error
-fn move_offset(offset: &mut u8, moving_amount: i8) {
-    match moving_amount.cmp(&0) {
-        Ordering::Greater => {
-            *offset += moving_amount as u8;
-        }
-        Ordering::Less => {
-            if *offset > -moving_amount as u8 {
-                *offset -= -moving_amount as u8;
-            } else {
-                *offset = 0;
-            }
-        }
-        Ordering::Equal => (),
+fn move_offset(offset: &mut usize, moving_amount: isize) {
+    if moving_amount < 0 {
+        *offset = offset.saturating_sub(moving_amount.unsigned_abs());
+    } else {
+        *offset = offset.saturating_add(moving_amount as usize);
     }
 }
@@ -2,15 +2,11 @@ use crate::context::AppContext;
 use crate::error::AppResult;
 use crate::history::DirectoryHistory;
 
-use super::reload;
+use crate::FOLDER_SETTINGS_T;
 
-pub fn _toggle_hidden(context: &mut AppContext) {
-    let opposite = !context.config_ref().display_options_ref().show_hidden();
-    context
-        .config_mut()
-        .display_options_mut()
-        .set_show_hidden(opposite);
+use super::{folder_settings, reload};
 
+fn depreciate_all_lists(context: &mut AppContext) {
     for (_, tab) in context.tab_context_mut().iter_mut() {
         tab.history_mut().depreciate_all_entries();
         if let Some(s) = tab.curr_list_mut() {
@@ -19,8 +15,43 @@ pub fn _toggle_hidden(context: &mut AppContext) {
     }
 }
 
+/// Shows or hides the entries matched by `.gitignore` files and `ignore_patterns`
+pub fn toggle_ignored(context: &mut AppContext) -> AppResult {
+    let display_options = context.config_mut().display_options_mut();
+    let hide_ignored = !display_options.hide_ignored();
+    display_options.set_hide_ignored(hide_ignored);
+
+    depreciate_all_lists(context);
+    reload::soft_reload_curr_tab(context)?;
+    let msg = if hide_ignored {
+        "Hiding ignored files"
+    } else {
+        "Showing ignored files"
+    };
+    context.message_queue_mut().push_info(msg.to_string());
+    Ok(())
+}
+
+/// Toggles hidden files in the current directory if it has its own setting
+/// for them (see `save_folder_settings`), otherwise everywhere
 pub fn toggle_hidden(context: &mut AppContext) -> AppResult {
-    _toggle_hidden(context);
+    let cwd = context.tab_context_ref().curr_tab_ref().cwd();
+    let folder_shown = FOLDER_SETTINGS_T
+        .lock()
+        .unwrap()
+        .get(cwd)
+        .and_then(|s| s.show_hidden);
+    match folder_shown {
+        Some(shown) => {
+            folder_settings::update_folder_setting(context, |s| s.show_hidden = Some(!shown))?
+        }
+        None => {
+            let display_options = context.config_mut().display_options_mut();
+            let shown = display_options.show_hidden();
+            display_options.set_show_hidden(!shown);
+        }
+    }
+    depreciate_all_lists(context);
     reload::soft_reload_curr_tab(context)?;
     Ok(())
 }
@@ -0,0 +1,64 @@
+use termion::event::{Event, Key};
+
+use crate::config::clean::keymap::AppKeyMapping;
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::key_command::{Command, CommandKeybind};
+use crate::ui::widgets::TuiMessageLog;
+use crate::ui::AppBackend;
+
+pub fn show_messages(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+) -> AppResult {
+    context.flush_event();
+
+    // start at the most recent messages
+    let mut offset = usize::MAX;
+
+    loop {
+        context.remove_external_preview();
+        backend.render(TuiMessageLog::new(
+            context.message_queue_ref().log_ref(),
+            &mut offset,
+        ));
+
+        let event = match context.poll_event() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+
+        match event {
+            AppEvent::Termion(Event::Key(Key::Esc)) => break,
+            AppEvent::Termion(event) => {
+                if let Some(CommandKeybind::SimpleKeybind { commands, .. }) =
+                    keymap_t.help_view.get(&event)
+                {
+                    for command in commands {
+                        match command {
+                            Command::CursorMoveUp { .. } => offset = offset.saturating_sub(1),
+                            Command::CursorMoveDown { .. } => offset = offset.saturating_add(1),
+                            Command::CursorMoveHome => offset = 0,
+                            Command::CursorMoveEnd => offset = usize::MAX,
+                            Command::CursorMovePageUp(_) => offset = offset.saturating_sub(10),
+                            Command::CursorMovePageDown(_) => offset = offset.saturating_add(10),
+                            Command::CloseTab | Command::Messages => return Ok(()),
+                            _ => (),
+                        }
+                    }
+                }
+                context.flush_event();
+            }
+            event => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
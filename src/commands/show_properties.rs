@@ -0,0 +1,185 @@
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::time;
+
+use termion::event::{Event, Key};
+
+use crate::commands::checksum::{self, spawn_checksum_thread};
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::ui::views::TuiView;
+use crate::ui::widgets::TuiProperties;
+use crate::ui::AppBackend;
+use crate::util::checksum::{ChecksumAlgorithm, ChecksumOutput};
+use crate::util::{format, mimetype, unix};
+
+const TIME_FORMATTING: &str = "%Y-%m-%d %H:%M:%S %z";
+// shown until a checksum is computed in the background
+const PENDING: &str = "computing...";
+
+pub fn show_properties(context: &mut AppContext, backend: &mut AppBackend) -> AppResult {
+    let path = match context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|list| list.curr_entry_ref())
+    {
+        Some(entry) => entry.file_path().to_path_buf(),
+        None => {
+            return Err(AppError::new(
+                AppErrorKind::InvalidParameters,
+                "No file selected".to_string(),
+            ))
+        }
+    };
+    let mut rows = properties(&path)?;
+    let title = format!(
+        " {} ",
+        path.file_name()
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_else(|| path.to_string_lossy())
+    );
+
+    context.flush_event();
+    loop {
+        let _ = backend.terminal_mut().draw(|frame| {
+            let area = frame.size();
+            let mut view = TuiView::new(context);
+            view.show_bottom_status = false;
+            frame.render_widget(view, area);
+            frame.render_widget(TuiProperties::new(&title, &rows), area);
+        });
+
+        let event = match context.poll_event() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+        match event {
+            AppEvent::Termion(event) => {
+                let algorithm = match event {
+                    Event::Key(Key::Esc) => break,
+                    Event::Key(Key::Char('1')) => Some(ChecksumAlgorithm::Md5),
+                    Event::Key(Key::Char('2')) => Some(ChecksumAlgorithm::Sha1),
+                    Event::Key(Key::Char('3')) => Some(ChecksumAlgorithm::Sha256),
+                    _ => None,
+                };
+                if let Some(algorithm) = algorithm {
+                    let name = algorithm.as_str().to_string();
+                    if path.is_file() && !rows.iter().any(|(n, _)| *n == name) {
+                        rows.push((name, PENDING.to_string()));
+                        let files = vec![(path.clone(), None)];
+                        spawn_checksum_thread(
+                            context,
+                            algorithm,
+                            files,
+                            ChecksumOutput::Properties,
+                        );
+                    }
+                }
+                context.flush_event();
+            }
+            // results of an earlier popup for another file are dropped
+            AppEvent::ChecksumResult {
+                algorithm,
+                entries,
+                output: ChecksumOutput::Properties,
+            } => {
+                context
+                    .worker_context_mut()
+                    .clear_progress_msg(checksum::PROGRESS_JOB);
+                let row = rows.iter_mut().find(|(n, _)| n == algorithm.as_str());
+                let entry = entries.into_iter().find(|e| e.path == path);
+                if let (Some(row), Some(entry)) = (row, entry) {
+                    row.1 = entry.result.unwrap_or_else(|e| e);
+                }
+            }
+            event => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    context.flush_event();
+    Ok(())
+}
+
+fn properties(path: &Path) -> AppResult<Vec<(String, String)>> {
+    let metadata = fs::symlink_metadata(path)?;
+    let mut rows = Vec::new();
+    let mut push = |name: &str, value: String| rows.push((name.to_string(), value));
+
+    push("Path", path.to_string_lossy().to_string());
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let target = if path.exists() {
+            target
+        } else {
+            format!("{} (broken)", target)
+        };
+        push("Link target", target);
+    }
+    push(
+        "Size",
+        format!(
+            "{} ({} bytes)",
+            format::file_size_to_string(metadata.len()).trim(),
+            metadata.len()
+        ),
+    );
+    push(
+        "Permissions",
+        format!(
+            "{} ({:04o})",
+            unix::mode_to_string(metadata.mode()),
+            metadata.mode() & 0o7777
+        ),
+    );
+    push(
+        "Owner",
+        format!(
+            "{} ({})",
+            unix::uid_to_string(metadata.uid()).unwrap_or_default(),
+            metadata.uid()
+        ),
+    );
+    push(
+        "Group",
+        format!(
+            "{} ({})",
+            unix::gid_to_string(metadata.gid()).unwrap_or_default(),
+            metadata.gid()
+        ),
+    );
+    if let Ok(modified) = metadata.modified() {
+        push("Modified", time_to_string(modified));
+    }
+    if let Ok(accessed) = metadata.accessed() {
+        push("Accessed", time_to_string(accessed));
+    }
+    if let Ok(created) = metadata.created() {
+        push("Created", time_to_string(created));
+    }
+    let changed = time::UNIX_EPOCH + time::Duration::from_secs(metadata.ctime().max(0) as u64);
+    push("Changed", time_to_string(changed));
+    push("Inode", metadata.ino().to_string());
+    push("Device", metadata.dev().to_string());
+    push("Hard links", metadata.nlink().to_string());
+    if let Ok(mimetype) = mimetype::get_mimetype(path) {
+        push(
+            "Mimetype",
+            format!("{}/{}", mimetype.get_type(), mimetype.get_subtype()),
+        );
+    }
+    Ok(rows)
+}
+
+fn time_to_string(time: time::SystemTime) -> String {
+    let datetime: chrono::DateTime<chrono::offset::Local> = time.into();
+    datetime.format(TIME_FORMATTING).to_string()
+}
@@ -51,7 +51,11 @@ pub fn show_tasks(
                     }
                     context.flush_event();
                 }
-                event => process_event::process_noninteractive(event, context),
+                event => {
+                    if process_event::process_in_modal(event, context, backend) {
+                        return Ok(());
+                    }
+                }
             };
         }
     }
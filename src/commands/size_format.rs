@@ -0,0 +1,16 @@
+use crate::config::clean::app::display::size_format::SizeFormat;
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::util::format;
+
+/// Sets how file sizes are shown, `None` switches to the next format
+pub fn set_size_format(context: &mut AppContext, size_format: Option<SizeFormat>) -> AppResult {
+    let display_options = context.config_mut().display_options_mut();
+    let size_format = size_format.unwrap_or_else(|| display_options.size_format().next());
+    display_options.set_size_format(size_format);
+    format::set_size_format(size_format);
+    context
+        .message_queue_mut()
+        .push_info(format!("Size format: {}", size_format.as_str()));
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use crate::config::clean::app::display::sort_type::SortType;
 use crate::context::AppContext;
 use crate::error::AppResult;
-use crate::history::DirectoryHistory;
+use crate::history::{folder_sort_options, DirectoryHistory};
 
-use super::reload;
+use super::{folder_settings, reload};
 
 pub fn set_sort(context: &mut AppContext, method: SortType) -> AppResult {
     let curr_tab = context.tab_context_mut().curr_tab_mut();
@@ -12,14 +12,16 @@ pub fn set_sort(context: &mut AppContext, method: SortType) -> AppResult {
         .sort_options_mut()
         .set_sort_method(method);
     curr_tab.history_mut().depreciate_all_entries();
+    folder_settings::update_folder_setting(context, |s| s.sort_method = Some(method))?;
     refresh(context)
 }
 
 pub fn toggle_reverse(context: &mut AppContext) -> AppResult {
     let curr_tab = context.tab_context_mut().curr_tab_mut();
-    let reversed = !curr_tab.option_mut().sort_options_ref().reverse;
+    let reversed = !folder_sort_options(curr_tab.cwd(), curr_tab.option_ref()).reverse;
     curr_tab.option_mut().sort_options_mut().reverse = reversed;
     curr_tab.history_mut().depreciate_all_entries();
+    folder_settings::update_folder_setting(context, |s| s.reverse = Some(reversed))?;
     refresh(context)
 }
 
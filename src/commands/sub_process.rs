@@ -1,31 +1,30 @@
 use crate::context::AppContext;
 use crate::error::AppResult;
+use crate::fs::JoshutoDirEntry;
 use crate::ui::AppBackend;
 use std::process::{Command, Stdio};
 
 use super::reload;
 
-pub fn current_filenames(context: &AppContext) -> Vec<&str> {
+/// The selected entries, or the current one if none are selected
+fn current_entries(context: &AppContext) -> Vec<&JoshutoDirEntry> {
     let mut result = Vec::new();
     if let Some(curr_list) = context.tab_context_ref().curr_tab_ref().curr_list_ref() {
-        let mut i = 0;
-        curr_list
-            .iter_selected()
-            .map(|e| e.file_name())
-            .for_each(|file_name| {
-                result.push(file_name);
-                i += 1;
-            });
-        if i == 0 {
-            if let Some(entry) = curr_list.curr_entry_ref() {
-                result.push(entry.file_name());
-            }
+        result.extend(curr_list.iter_selected());
+        if result.is_empty() {
+            result.extend(curr_list.curr_entry_ref());
         }
     }
-
     result
 }
 
+pub fn current_filenames(context: &AppContext) -> Vec<&str> {
+    current_entries(context)
+        .into_iter()
+        .map(|e| e.file_name())
+        .collect()
+}
+
 fn execute_sub_process(
     context: &mut AppContext,
     words: &[String],
@@ -35,8 +34,8 @@ fn execute_sub_process(
     for word in words.iter().skip(1) {
         match (*word).as_str() {
             "%s" => {
-                current_filenames(context).into_iter().for_each(|x| {
-                    command.arg(x);
+                current_entries(context).into_iter().for_each(|x| {
+                    command.arg(x.file_name_os());
                 });
             }
             "%p" => {
@@ -61,6 +60,7 @@ fn execute_sub_process(
             }
         };
     }
+    log::debug!("running {:?}", command);
     if spawn {
         command
             .stdout(Stdio::piped())
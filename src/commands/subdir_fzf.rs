@@ -28,7 +28,7 @@ pub fn fzf_change_dir(context: &mut AppContext, path: &Path) -> AppResult {
             Some(curr_list) => curr_list
                 .iter()
                 .enumerate()
-                .find(|(_, e)| e.file_name() == file_name)
+                .find(|(_, e)| e.file_name_os() == file_name)
                 .map(|(i, _)| i),
             None => None,
         };
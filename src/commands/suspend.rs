@@ -0,0 +1,15 @@
+use signal_hook::consts::signal;
+use signal_hook::low_level;
+
+use crate::context::AppContext;
+use crate::error::AppResult;
+use crate::ui::AppBackend;
+
+/// Restores the terminal and stops the process until it receives `SIGCONT`.
+pub fn suspend(context: &mut AppContext, backend: &mut AppBackend) -> AppResult {
+    backend.terminal_drop();
+    // SIGSTOP can't be caught, so this returns once we are continued
+    low_level::raise(signal::SIGSTOP)?;
+    backend.terminal_restore(context.config_ref().mouse_support)?;
+    Ok(())
+}
@@ -7,7 +7,7 @@ use crate::context::AppContext;
 use crate::error::{AppError, AppErrorKind, AppResult};
 use crate::history::DirectoryHistory;
 use crate::tab::{JoshutoTab, TabHomePage};
-use crate::util::{cwd, unix};
+use crate::util::{cwd, expand};
 
 use crate::HOME_DIR;
 
@@ -127,7 +127,7 @@ pub fn new_tab(context: &mut AppContext, mode: &NewTabMode) -> AppResult {
                 "No directory at cursor.".to_string(),
             )),
         NewTabMode::Directory(directory) => {
-            let directory_path = unix::expand_shell_string(directory);
+            let directory_path = expand::expand_path(directory)?;
             Ok(if directory_path.is_absolute() {
                 directory_path
             } else {
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use crate::context::AppContext;
+use crate::error::AppResult;
+
+use super::reload;
+
+/// Whether the current directory is listed as a tree
+pub fn is_tree(context: &AppContext) -> bool {
+    let curr_tab = context.tab_context_ref().curr_tab_ref();
+    curr_tab
+        .option_ref()
+        .dirlist_options_ref(&curr_tab.cwd().to_path_buf())
+        .map_or(false, |opts| opts.tree())
+}
+
+fn refresh(context: &mut AppContext) -> AppResult {
+    if let Some(list) = context.tab_context_mut().curr_tab_mut().curr_list_mut() {
+        list.depreciate();
+    }
+    reload::soft_reload_curr_tab(context)?;
+    Ok(())
+}
+
+/// Switches between listing the current directory as a tree and the usual listing
+pub fn toggle_tree(context: &mut AppContext) -> AppResult {
+    let tree = !is_tree(context);
+    let curr_tab = context.tab_context_mut().curr_tab_mut();
+    let path = curr_tab.cwd().to_path_buf();
+    let opts = curr_tab.option_mut().dirlist_options_mut(&path);
+    opts.set_tree(tree);
+    // flat listings don't mix with trees
+    opts.set_depth(0);
+    refresh(context)
+}
+
+fn set_expanded(context: &mut AppContext, path: &Path, expanded: bool) -> AppResult {
+    let curr_tab = context.tab_context_mut().curr_tab_mut();
+    let cwd = curr_tab.cwd().to_path_buf();
+    curr_tab
+        .option_mut()
+        .dirlist_options_mut(&cwd)
+        .set_expanded(path, expanded);
+    refresh(context)
+}
+
+/// Expands or collapses the directory `path` of the tree
+pub fn toggle_expanded(context: &mut AppContext, path: &Path) -> AppResult {
+    let curr_tab = context.tab_context_ref().curr_tab_ref();
+    let expanded = curr_tab
+        .option_ref()
+        .dirlist_options_ref(&curr_tab.cwd().to_path_buf())
+        .map_or(false, |opts| opts.is_expanded(path));
+    set_expanded(context, path, !expanded)
+}
+
+/// Collapses the directory under the cursor, or the one containing it
+/// and moves the cursor there
+pub fn tree_collapse(context: &mut AppContext) -> AppResult {
+    if !is_tree(context) {
+        return Ok(());
+    }
+    let curr_tab = context.tab_context_ref().curr_tab_ref();
+    let cwd = curr_tab.cwd().to_path_buf();
+    let entry = match curr_tab.curr_list_ref().and_then(|l| l.curr_entry_ref()) {
+        Some(entry) => entry,
+        None => return Ok(()),
+    };
+    let expanded = curr_tab
+        .option_ref()
+        .dirlist_options_ref(&cwd)
+        .map_or(false, |opts| opts.is_expanded(entry.file_path()));
+    if expanded {
+        let path = entry.file_path_buf();
+        return set_expanded(context, &path, false);
+    }
+
+    let parent = match entry.file_path().parent() {
+        Some(parent) if parent != cwd => parent.to_path_buf(),
+        _ => return Ok(()),
+    };
+    set_expanded(context, &parent, false)?;
+    let ui_context = context.ui_context_ref().clone();
+    let display_options = context.config_ref().display_options_ref().clone();
+    if let Some(curr_list) = context.tab_context_mut().curr_tab_mut().curr_list_mut() {
+        let index = curr_list.iter().position(|e| e.file_path() == parent);
+        curr_list.set_index(index, &ui_context, &display_options);
+    }
+    Ok(())
+}
@@ -0,0 +1,335 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use ratatui::text::Line;
+use termion::event::{Event, Key};
+
+use crate::config::clean::keymap::AppKeyMapping;
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::key_command::{Command, CommandKeybind};
+use crate::preview::preview_file;
+use crate::ui::widgets::TuiPager;
+use crate::ui::AppBackend;
+use crate::util::{format, string, syntax};
+
+// larger files are cut off
+const MAX_VIEW_SIZE: u64 = 16 * 1024 * 1024;
+// smaller text files are read at once, which is needed for syntax highlighting
+const MAX_HIGHLIGHT_SIZE: u64 = 1024 * 1024;
+const HEX_LINE_SIZE: u64 = 16;
+const TAB_WIDTH: usize = 4;
+const FOOTER: &str = "Press <ESC> to return, / to search, n/N for next/previous match";
+
+pub fn view_file(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+    hex: bool,
+) -> AppResult {
+    let path = match context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|list| list.curr_entry_ref())
+    {
+        Some(entry) => entry.file_path().to_path_buf(),
+        None => {
+            return Err(AppError::new(
+                AppErrorKind::InvalidParameters,
+                "No file selected".to_string(),
+            ))
+        }
+    };
+    if path.is_dir() {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            format!("{}: is a directory", path.display()),
+        ));
+    }
+    let mut file = PagedFile::open(&path, hex)?;
+    let title = path.to_string_lossy().to_string();
+
+    pager_loop(context, backend, keymap_t, &title, &mut file);
+    Ok(())
+}
+
+/// The lines of a file, read in pages as they are scrolled to or searched
+struct PagedFile {
+    reader: io::Take<BufReader<fs::File>>,
+    hex: bool,
+    lines: Vec<String>,
+    // syntax highlighting of `lines`, only for files read at once
+    styled_lines: Option<Vec<Line<'static>>>,
+    // bytes read so far
+    position: usize,
+    done: bool,
+}
+
+impl PagedFile {
+    fn open(path: &Path, hex: bool) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        let size = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+        let hex = hex || preview_file::is_binary(reader.fill_buf()?);
+        let mut paged = Self {
+            reader: reader.take(MAX_VIEW_SIZE),
+            hex,
+            lines: Vec::new(),
+            styled_lines: None,
+            position: 0,
+            done: false,
+        };
+        if !hex && size <= MAX_HIGHLIGHT_SIZE {
+            paged.load_to(usize::MAX)?;
+            paged.styled_lines = syntax::highlight_lines(path, &paged.lines);
+        }
+        Ok(paged)
+    }
+
+    /// Reads lines until there are `count` of them or the file ends
+    fn load_to(&mut self, count: usize) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        while self.lines.len() < count && !self.done {
+            bytes.clear();
+            let line = if self.hex {
+                (&mut self.reader)
+                    .take(HEX_LINE_SIZE)
+                    .read_to_end(&mut bytes)?;
+                format::hexdump(&bytes, self.position).pop()
+            } else {
+                self.reader.read_until(b'\n', &mut bytes)?;
+                let text = bytes.strip_suffix(b"\n").unwrap_or(&bytes);
+                let text = text.strip_suffix(b"\r").unwrap_or(text);
+                let text = text
+                    .split(|&b| b == b'\t')
+                    .map(string::escape_bytes)
+                    .collect::<Vec<_>>()
+                    .join(&" ".repeat(TAB_WIDTH));
+                (!bytes.is_empty()).then(|| text)
+            };
+            self.position += bytes.len();
+            match line {
+                Some(line) => self.lines.push(line),
+                None => self.done = true,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads what is needed to show `page_size` lines from `offset`
+    fn load_page(&mut self, offset: usize, page_size: usize) {
+        // a failing read ends the file, the lines so far can still be shown
+        if self.load_to(offset.saturating_add(page_size)).is_err() {
+            self.done = true;
+        }
+    }
+
+    fn line(&mut self, index: usize) -> Option<&str> {
+        self.load_page(index, 1);
+        self.lines.get(index).map(String::as_str)
+    }
+
+    fn find_next(&mut self, pattern: &str, start: usize) -> Option<usize> {
+        if pattern.is_empty() {
+            return None;
+        }
+        let mut i = start;
+        while let Some(line) = self.line(i) {
+            if line.contains(pattern) {
+                return Some(i);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    fn find_prev(&self, pattern: &str, end: usize) -> Option<usize> {
+        if pattern.is_empty() {
+            return None;
+        }
+        self.lines
+            .iter()
+            .enumerate()
+            .take(end)
+            .rev()
+            .find(|(_, line)| line.contains(pattern))
+            .map(|(i, _)| i)
+    }
+}
+
+fn pager_loop(
+    context: &mut AppContext,
+    backend: &mut AppBackend,
+    keymap_t: &AppKeyMapping,
+    title: &str,
+    file: &mut PagedFile,
+) {
+    context.flush_event();
+
+    let mut offset = 0;
+    // the search being typed, starts with '/'
+    let mut search_query = String::new();
+    let mut pattern = String::new();
+
+    loop {
+        let page_size = backend
+            .terminal_ref()
+            .size()
+            .map(|area| area.height.saturating_sub(2) as usize)
+            .unwrap_or(1);
+
+        if offset == usize::MAX {
+            file.load_page(0, usize::MAX);
+        } else {
+            file.load_page(offset, page_size);
+        }
+
+        context.remove_external_preview();
+        let footer = if search_query.is_empty() {
+            FOOTER
+        } else {
+            search_query.as_str()
+        };
+        backend.render(
+            TuiPager::new(title, &file.lines, &mut offset, footer, &pattern)
+                .styled_lines(file.styled_lines.as_deref()),
+        );
+
+        let event = match context.poll_event() {
+            Ok(event) => event,
+            Err(_) => return,
+        };
+
+        match event {
+            AppEvent::Termion(event) => {
+                if search_query.is_empty() {
+                    match event {
+                        Event::Key(Key::Esc) => break,
+                        Event::Key(Key::Char('/')) => search_query.push('/'),
+                        Event::Key(Key::Char('n')) => {
+                            if let Some(i) = file.find_next(&pattern, offset + 1) {
+                                offset = i;
+                            }
+                        }
+                        Event::Key(Key::Char('N')) => {
+                            if let Some(i) = file.find_prev(&pattern, offset) {
+                                offset = i;
+                            }
+                        }
+                        Event::Key(Key::PageUp) => offset = offset.saturating_sub(page_size),
+                        Event::Key(Key::PageDown) | Event::Key(Key::Char(' ')) => {
+                            offset = offset.saturating_add(page_size)
+                        }
+                        event => {
+                            if let Some(CommandKeybind::SimpleKeybind { commands, .. }) =
+                                keymap_t.help_view.get(&event)
+                            {
+                                for command in commands {
+                                    match command {
+                                        Command::CursorMoveUp { .. } => {
+                                            offset = offset.saturating_sub(1)
+                                        }
+                                        Command::CursorMoveDown { .. } => {
+                                            offset = offset.saturating_add(1)
+                                        }
+                                        Command::CursorMoveHome => offset = 0,
+                                        Command::CursorMoveEnd => offset = usize::MAX,
+                                        Command::CursorMovePageUp(_) => {
+                                            offset = offset.saturating_sub(page_size)
+                                        }
+                                        Command::CursorMovePageDown(_) => {
+                                            offset = offset.saturating_add(page_size)
+                                        }
+                                        Command::CloseTab => return,
+                                        _ => (),
+                                    }
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    match event {
+                        Event::Key(Key::Esc) => search_query.clear(),
+                        Event::Key(Key::Backspace) => {
+                            search_query.pop();
+                        }
+                        Event::Key(Key::Char('\n')) => {
+                            pattern = search_query.split_off(1);
+                            search_query.clear();
+                            if let Some(i) = file.find_next(&pattern, offset) {
+                                offset = i;
+                            }
+                        }
+                        Event::Key(Key::Char(chr)) => search_query.push(chr),
+                        _ => (),
+                    }
+                }
+                context.flush_event();
+            }
+            _ => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a file past `MAX_HIGHLIGHT_SIZE`, ending in "needle"
+    fn big_file() -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.txt");
+        let mut text = String::from("a\tb\x1b[31m\r\n");
+        while text.len() as u64 <= MAX_HIGHLIGHT_SIZE {
+            text.push_str("filler line\n");
+        }
+        text.push_str("needle");
+        fs::write(&path, &text).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn lines_are_loaded_by_page() {
+        let (_dir, path) = big_file();
+        let mut file = PagedFile::open(&path, false).unwrap();
+        assert!(file.lines.is_empty());
+        file.load_page(0, 10);
+        assert_eq!(file.lines.len(), 10);
+    }
+
+    #[test]
+    fn tabs_and_control_characters_are_made_visible() {
+        let (_dir, path) = big_file();
+        let mut file = PagedFile::open(&path, false).unwrap();
+        file.load_page(0, 1);
+        assert_eq!(file.lines[0], "a    b\\u{1b}[31m");
+    }
+
+    #[test]
+    fn searching_forward_loads_up_to_the_match() {
+        let (_dir, path) = big_file();
+        let mut file = PagedFile::open(&path, false).unwrap();
+        let last = file.find_next("needle", 0).unwrap();
+        assert_eq!(file.lines.len(), last + 1);
+        assert_eq!(file.line(last + 1), None);
+        assert!(file.done);
+        assert_eq!(file.find_prev("needle", last), None);
+    }
+
+    #[test]
+    fn hex_lines_show_16_bytes_each() {
+        let (_dir, path) = big_file();
+        let mut hex = PagedFile::open(&path, true).unwrap();
+        hex.load_page(1, 1);
+        assert_eq!(hex.lines.len(), 2);
+        assert!(hex.lines[1].starts_with("00000010  "));
+    }
+}
@@ -0,0 +1,199 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use rand::Rng;
+
+use termion::event::Event;
+
+use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::process_event;
+use crate::event::AppEvent;
+use crate::ui::views::TuiView;
+use crate::ui::widgets::TuiProperties;
+use crate::ui::AppBackend;
+use crate::util::xattr;
+
+use super::reload;
+
+const ENV_EDITOR: &str = "EDITOR";
+const FILE_PREFIX: &str = "joshuto-acl-";
+const RAND_STR_LEN: usize = 10;
+const FOOTER: &str = "<ESC>: close";
+// ACLs are stored in these attributes, they are shown through `getfacl` instead
+const ACL_XATTR_PREFIX: &str = "system.posix_acl_";
+
+fn curr_path(context: &AppContext) -> AppResult<PathBuf> {
+    match context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|list| list.curr_entry_ref())
+    {
+        Some(entry) => Ok(entry.file_path().to_path_buf()),
+        None => Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            "No file selected".to_string(),
+        )),
+    }
+}
+
+/// Runs `getfacl` on `path`, returning the ACL entries
+fn get_acl(path: &Path) -> AppResult<Vec<String>> {
+    let output = Command::new("getfacl")
+        .arg("--omit-header")
+        .arg("--absolute-names")
+        .arg("--")
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::new(
+            AppErrorKind::Io(io::ErrorKind::Other),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect())
+}
+
+pub fn xattr_list(context: &mut AppContext, backend: &mut AppBackend) -> AppResult {
+    let path = curr_path(context)?;
+
+    let mut rows: Vec<(String, String)> = xattr::list(&path)?
+        .into_iter()
+        .filter(|(name, _)| !name.starts_with(ACL_XATTR_PREFIX))
+        .map(|(name, value)| (name, xattr::display_value(&value)))
+        .collect();
+    // getfacl might not be installed, the attributes are still worth showing
+    if let Ok(acl) = get_acl(&path) {
+        rows.extend(acl.into_iter().map(|entry| ("acl".to_string(), entry)));
+    }
+    if rows.is_empty() {
+        context
+            .message_queue_mut()
+            .push_info(format!("{}: no extended attributes", path.display()));
+        return Ok(());
+    }
+    let title = format!(
+        " {} ",
+        path.file_name()
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_else(|| path.to_string_lossy())
+    );
+
+    context.flush_event();
+    loop {
+        let _ = backend.terminal_mut().draw(|frame| {
+            let area = frame.size();
+            let mut view = TuiView::new(context);
+            view.show_bottom_status = false;
+            frame.render_widget(view, area);
+            frame.render_widget(TuiProperties::new(&title, &rows).footer(FOOTER), area);
+        });
+
+        match context.poll_event() {
+            Ok(AppEvent::Termion(Event::Key(_))) => break,
+            Ok(AppEvent::Termion(_)) => context.flush_event(),
+            Ok(event) => {
+                if process_event::process_in_modal(event, context, backend) {
+                    return Ok(());
+                }
+            }
+            Err(_) => return Ok(()),
+        }
+    }
+    context.flush_event();
+    Ok(())
+}
+
+pub fn xattr_set(context: &mut AppContext, name: &str, value: &str) -> AppResult {
+    let paths = context
+        .tab_context_ref()
+        .curr_tab_ref()
+        .curr_list_ref()
+        .map(|s| s.get_selected_paths())
+        .unwrap_or_default();
+    if paths.is_empty() {
+        return Err(AppError::new(
+            AppErrorKind::InvalidParameters,
+            "No files selected".to_string(),
+        ));
+    }
+    for path in paths.iter() {
+        xattr::set(path, name, value.as_bytes())?;
+    }
+    context
+        .message_queue_mut()
+        .push_success(format!("Set {} on {} file(s)", name, paths.len()));
+    Ok(())
+}
+
+/// Returns whether the ACL was changed
+fn _acl_edit(path: &Path) -> AppResult<bool> {
+    let editor = env::var(ENV_EDITOR)?;
+    let acl = get_acl(path)?;
+
+    // the temporary directory is shared, so the file gets an unpredictable name
+    // and is never opened if something already exists there
+    let mut file_name = String::from(FILE_PREFIX);
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(RAND_STR_LEN)
+        .for_each(|ch| file_name.push(ch as char));
+    let file_path = env::temp_dir().join(file_name);
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&file_path)?
+        .write_all((acl.join("\n") + "\n").as_bytes())?;
+
+    let initial_modified = fs::metadata(&file_path)?.modified()?;
+    let status = Command::new(editor).arg(&file_path).status();
+    let last_modified = fs::metadata(&file_path)?.modified()?;
+    if status.is_err() || last_modified <= initial_modified {
+        fs::remove_file(&file_path)?;
+        status?;
+        return Ok(false);
+    }
+
+    let output = Command::new("setfacl")
+        .arg(format!("--set-file={}", file_path.display()))
+        .arg("--")
+        .arg(path)
+        .stdin(Stdio::null())
+        .output();
+    fs::remove_file(&file_path)?;
+    let output = output?;
+    if !output.status.success() {
+        return Err(AppError::new(
+            AppErrorKind::Io(io::ErrorKind::Other),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(true)
+}
+
+pub fn acl_edit(context: &mut AppContext, backend: &mut AppBackend) -> AppResult {
+    let path = curr_path(context)?;
+
+    context.remove_external_preview();
+    backend.terminal_drop();
+    let res = _acl_edit(&path);
+    backend.terminal_restore(context.config_ref().mouse_support)?;
+    reload::soft_reload_curr_tab(context)?;
+    if res? {
+        context
+            .message_queue_mut()
+            .push_success(format!("Updated ACL of {}", path.display()));
+    }
+    Ok(())
+}
@@ -1,32 +1,71 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::{
     config::{
+        problems::{self, ConfigProblem},
         raw::app::{AppConfigRaw, CustomCommand},
         ConfigType, TomlConfigFile,
     },
     error::AppResult,
+    util::{
+        logging,
+        name_resolution::{self, DEFAULT_RENAME_PATTERN},
+        unix,
+    },
 };
 
 use super::{
-    display::DisplayOption, preview::PreviewOption, search::SearchOption, tab::TabOption,
-    DEFAULT_CONFIG_FILE_PATH,
+    display::DisplayOption, hooks::HookOption, preview::PreviewOption, search::SearchOption,
+    tab::TabOption, DEFAULT_CONFIG_FILE_PATH,
 };
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub use_trash: bool,
+    pub shred_passes: usize,
     pub xdg_open: bool,
     pub xdg_open_fork: bool,
+    /// `open` acts on the marked files even when the cursor is on a directory
+    pub open_selected: bool,
+    /// commands changing files or running shell commands are refused
+    pub readonly: bool,
     pub watch_files: bool,
+    /// config files are reloaded when they change, see `reload_config`
+    pub watch_config: bool,
+    /// file operations running at the same time, the others wait in the queue
+    pub max_concurrent_tasks: usize,
+    /// threads each file operation copies or deletes the entries of directories with
+    pub task_threads: usize,
+    /// number of directory entries kept in the directory listings of each tab
+    pub dir_cache_size: usize,
+    /// names given to pasted files which already exist, see `unique_file_name`
+    pub paste_rename_pattern: String,
     pub custom_commands: Vec<CustomCommand>,
     pub focus_on_create: bool,
     pub mouse_support: bool,
+    pub update_title: bool,
+    /// how long messages are shown, `None` keeps them until the next key press
+    pub message_timeout: Option<Duration>,
+    /// entries kept in the history of each prompt
+    pub prompt_history_size: usize,
+    pub desktop_notifications: bool,
+    pub crash_log: Option<PathBuf>,
+    /// records of the `log` macros written to `log_file`, `Off` disables logging
+    pub log_level: log::LevelFilter,
+    /// `None` if there is no state directory to put the default log file in
+    pub log_file: Option<PathBuf>,
+    /// terminal emulator started by `open_terminal`, `None` runs `$SHELL` instead
+    pub terminal: Option<String>,
+    pub dragdrop_command: String,
     pub cmd_aliases: HashMap<String, String>,
     pub _display_options: DisplayOption,
     pub _preview_options: PreviewOption,
     pub _search_options: SearchOption,
     pub _tab_options: TabOption,
+    pub _hook_options: HookOption,
 }
 
 impl AppConfig {
@@ -35,6 +74,10 @@ impl AppConfig {
         Ok(Self::from(raw))
     }
 
+    pub fn expand_alias<'a>(&'a self, line: &'a str) -> Cow<'a, str> {
+        expand_alias(&self.cmd_aliases, line)
+    }
+
     pub fn display_options_ref(&self) -> &DisplayOption {
         &self._display_options
     }
@@ -60,6 +103,10 @@ impl AppConfig {
     pub fn tab_options_ref(&self) -> &TabOption {
         &self._tab_options
     }
+
+    pub fn hook_options_ref(&self) -> &HookOption {
+        &self._hook_options
+    }
 }
 
 impl std::default::Default for AppConfig {
@@ -82,17 +129,94 @@ impl From<AppConfigRaw> for AppConfig {
     fn from(raw: AppConfigRaw) -> Self {
         Self {
             use_trash: raw.use_trash,
+            shred_passes: raw.shred_passes,
             xdg_open: raw.xdg_open,
             xdg_open_fork: raw.xdg_open_fork,
+            open_selected: raw.open_selected,
+            readonly: raw.readonly,
             watch_files: raw.watch_files,
+            watch_config: raw.watch_config,
+            max_concurrent_tasks: raw.max_concurrent_tasks.max(1),
+            task_threads: match raw.task_threads {
+                0 => rayon::current_num_threads(),
+                n => n,
+            },
+            dir_cache_size: raw.dir_cache_size,
+            paste_rename_pattern: if name_resolution::is_valid_rename_pattern(
+                &raw.paste_rename_pattern,
+            ) {
+                raw.paste_rename_pattern
+            } else {
+                problems::report(ConfigProblem::new(
+                    AppConfig::get_type().as_filename(),
+                    None,
+                    format!(
+                        "paste_rename_pattern `{}` needs `{{name}}` and no `/`, using `{}`",
+                        raw.paste_rename_pattern, DEFAULT_RENAME_PATTERN
+                    ),
+                ));
+                DEFAULT_RENAME_PATTERN.to_string()
+            },
             cmd_aliases: raw.cmd_aliases,
             focus_on_create: raw.focus_on_create,
             mouse_support: raw.mouse_support,
+            update_title: raw.update_title,
+            message_timeout: match raw.message_timeout {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
+            prompt_history_size: raw.prompt_history_size,
+            desktop_notifications: raw.desktop_notifications,
+            crash_log: raw.crash_log.as_deref().map(unix::expand_shell_string),
+            log_level: raw.log_level.parse().unwrap_or(log::LevelFilter::Warn),
+            log_file: match raw.log_file.as_deref() {
+                Some(path) => Some(unix::expand_shell_string(path)),
+                None => logging::default_log_file(),
+            },
+            terminal: raw.terminal,
+            dragdrop_command: raw.dragdrop_command,
             _display_options: DisplayOption::from(raw.display_options),
             _preview_options: PreviewOption::from(raw.preview_options),
             _search_options: SearchOption::from(raw.search_options),
             _tab_options: TabOption::from(raw.tab_options),
+            _hook_options: HookOption::from(raw.hook_options),
             custom_commands: raw.custom_commands,
         }
     }
 }
+
+/// Replaces a command alias at the start of `line` with the command it stands for.
+///
+/// Arguments following the alias are appended, so with `e = "open_with vim"`
+/// the line `e 1` becomes `open_with vim 1`.
+pub fn expand_alias<'a>(aliases: &'a HashMap<String, String>, line: &'a str) -> Cow<'a, str> {
+    let line = line.trim_start();
+    if let Some(alias) = aliases.get(line) {
+        return Cow::Borrowed(alias);
+    }
+    match line.split_once(char::is_whitespace) {
+        Some((name, args)) => match aliases.get(name) {
+            Some(alias) => Cow::Owned(format!("{} {}", alias, args.trim_start())),
+            None => Cow::Borrowed(line),
+        },
+        None => Cow::Borrowed(line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_alias() {
+        let aliases: HashMap<String, String> = [("q", "quit"), ("e", "open_with vim")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(expand_alias(&aliases, "q"), "quit");
+        assert_eq!(expand_alias(&aliases, "e"), "open_with vim");
+        assert_eq!(expand_alias(&aliases, "e  1"), "open_with vim 1");
+        assert_eq!(expand_alias(&aliases, "quit --force"), "quit --force");
+        assert_eq!(expand_alias(&aliases, "qq"), "qq");
+    }
+}
@@ -0,0 +1,61 @@
+use std::ffi::OsString;
+
+/// Whether the user interface is drawn with colors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorMode {
+    Never,
+    Auto,
+    Always,
+}
+
+impl ColorMode {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "never" => Some(Self::Never),
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            _ => None,
+        }
+    }
+
+    /// Whether colors are drawn. `Auto` draws them unless `NO_COLOR` is set
+    /// (see <https://no-color.org>) or the terminal is a dumb one
+    pub fn enabled(&self) -> bool {
+        self.enabled_with(std::env::var_os("NO_COLOR"), std::env::var_os("TERM"))
+    }
+
+    fn enabled_with(&self, no_color: Option<OsString>, term: Option<OsString>) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Always => true,
+            Self::Auto => {
+                no_color.map_or(true, |s| s.is_empty()) && term.map_or(true, |t| t != "dumb")
+            }
+        }
+    }
+}
+
+impl std::default::Default for ColorMode {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_mode_test() {
+        let var = |s: &str| Some(OsString::from(s));
+        let auto = ColorMode::Auto;
+        assert!(auto.enabled_with(None, var("xterm")));
+        assert!(auto.enabled_with(var(""), var("xterm")));
+        assert!(!auto.enabled_with(var("1"), var("xterm")));
+        assert!(!auto.enabled_with(None, var("dumb")));
+        assert!(ColorMode::Always.enabled_with(var("1"), var("dumb")));
+        assert!(!ColorMode::Never.enabled_with(None, var("xterm")));
+        assert_eq!(ColorMode::from_str("never"), Some(ColorMode::Never));
+        assert_eq!(ColorMode::from_str("sometimes"), None);
+    }
+}
@@ -1,10 +1,15 @@
 use std::convert::From;
 
+use ignore::gitignore::Gitignore;
 use ratatui::layout::Constraint;
 
 use crate::config::raw::app::display::DisplayOptionRaw;
+use crate::fs::gitignore::build_ignore_patterns;
 
-use super::{dirlist::DirListDisplayOptions, line_number::LineNumberStyle, tab::TabDisplayOption};
+use super::{
+    color_mode::ColorMode, dirlist::DirListDisplayOptions, line_number::LineNumberStyle,
+    size_format::SizeFormat, tab::TabDisplayOption,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub enum DisplayMode {
@@ -23,10 +28,23 @@ pub struct DisplayOption {
     pub _automatically_count_files: bool,
     pub _collapse_preview: bool,
     pub _scroll_offset: usize,
+    pub _wrap_cursor: bool,
     pub _show_borders: bool,
     pub _show_hidden: bool,
+    /// hide entries matched by `.gitignore` files or `ignore_patterns`
+    pub _hide_ignored: bool,
+    pub _ignore_patterns: Gitignore,
     pub _show_icons: bool,
+    pub _file_markers: bool,
+    pub _tilde_in_titlebar: bool,
     pub _line_nums: LineNumberStyle,
+    pub _size_format: SizeFormat,
+    pub _date_format: String,
+    pub _middle_ellipsis: bool,
+    pub _lazy_metadata: bool,
+    pub _colors: ColorMode,
+    /// the bundled high contrast theme is used instead of theme.toml
+    pub _high_contrast: bool,
     pub column_ratio: (usize, usize, usize),
     pub default_layout: [Constraint; 3],
     pub no_preview_layout: [Constraint; 3],
@@ -62,15 +80,28 @@ impl From<DisplayOptionRaw> for DisplayOption {
         let _line_nums = LineNumberStyle::from_str(raw.line_number_style.as_str())
             .unwrap_or(LineNumberStyle::None);
 
+        let _size_format = SizeFormat::from_str(raw.size_format.as_str()).unwrap_or_default();
+
         Self {
             _mode: mode,
             _automatically_count_files: raw.automatically_count_files,
             _collapse_preview: raw.collapse_preview,
             _scroll_offset: raw.scroll_offset,
+            _wrap_cursor: raw.wrap_cursor,
             _show_borders: raw.show_borders,
             _show_hidden: raw.show_hidden,
+            _hide_ignored: raw.hide_ignored,
+            _ignore_patterns: build_ignore_patterns(&raw.ignore_patterns),
             _show_icons: raw.show_icons,
+            _file_markers: raw.file_markers,
+            _tilde_in_titlebar: raw.tilde_in_titlebar,
             _line_nums,
+            _size_format,
+            _date_format: raw.date_format,
+            _middle_ellipsis: raw.middle_ellipsis,
+            _lazy_metadata: raw.lazy_metadata,
+            _colors: ColorMode::from_str(raw.colors.as_str()).unwrap_or_default(),
+            _high_contrast: raw.high_contrast,
 
             column_ratio,
             default_layout,
@@ -102,6 +133,10 @@ impl DisplayOption {
         self._scroll_offset
     }
 
+    pub fn wrap_cursor(&self) -> bool {
+        self._wrap_cursor
+    }
+
     pub fn show_borders(&self) -> bool {
         self._show_borders
     }
@@ -114,10 +149,30 @@ impl DisplayOption {
         self._show_icons
     }
 
+    pub fn file_markers(&self) -> bool {
+        self._file_markers
+    }
+
+    pub fn tilde_in_titlebar(&self) -> bool {
+        self._tilde_in_titlebar
+    }
+
     pub fn set_show_hidden(&mut self, show_hidden: bool) {
         self._show_hidden = show_hidden;
     }
 
+    pub fn hide_ignored(&self) -> bool {
+        self._hide_ignored
+    }
+
+    pub fn set_hide_ignored(&mut self, hide_ignored: bool) {
+        self._hide_ignored = hide_ignored;
+    }
+
+    pub fn ignore_patterns(&self) -> &Gitignore {
+        &self._ignore_patterns
+    }
+
     pub fn line_nums(&self) -> LineNumberStyle {
         self._line_nums
     }
@@ -126,6 +181,34 @@ impl DisplayOption {
         self._line_nums = style;
     }
 
+    pub fn colors(&self) -> ColorMode {
+        self._colors
+    }
+
+    pub fn high_contrast(&self) -> bool {
+        self._high_contrast
+    }
+
+    pub fn lazy_metadata(&self) -> bool {
+        self._lazy_metadata
+    }
+
+    pub fn middle_ellipsis(&self) -> bool {
+        self._middle_ellipsis
+    }
+
+    pub fn date_format(&self) -> &str {
+        self._date_format.as_str()
+    }
+
+    pub fn size_format(&self) -> SizeFormat {
+        self._size_format
+    }
+
+    pub fn set_size_format(&mut self, size_format: SizeFormat) {
+        self._size_format = size_format;
+    }
+
     pub fn filter_func(
         &self,
     ) -> fn(&walkdir::DirEntry, &DisplayOption, &DirListDisplayOptions) -> bool {
@@ -155,10 +238,21 @@ impl std::default::Default for DisplayOption {
             _collapse_preview: true,
             column_ratio,
             _scroll_offset: 4,
+            _wrap_cursor: false,
             _show_borders: true,
             _show_hidden: false,
+            _hide_ignored: false,
+            _ignore_patterns: Gitignore::empty(),
             _show_icons: false,
+            _file_markers: false,
+            _tilde_in_titlebar: true,
             _line_nums: LineNumberStyle::None,
+            _size_format: SizeFormat::default(),
+            _date_format: "%Y-%m-%d %H:%M".to_string(),
+            _middle_ellipsis: false,
+            _lazy_metadata: false,
+            _colors: ColorMode::default(),
+            _high_contrast: false,
             default_layout,
             no_preview_layout,
             default_tab_display_option: TabDisplayOption::default(),
@@ -167,11 +261,7 @@ impl std::default::Default for DisplayOption {
 }
 
 fn is_hidden(entry: &walkdir::DirEntry) -> bool {
-    entry
-        .file_name()
-        .to_str()
-        .map(|s| s.starts_with('.'))
-        .unwrap_or(false)
+    entry.file_name().to_string_lossy().starts_with('.')
 }
 
 fn filter(
@@ -179,16 +269,15 @@ fn filter(
     opt: &DisplayOption,
     dirlist_opts: &DirListDisplayOptions,
 ) -> bool {
-    if !opt.show_hidden() && is_hidden(entry) {
+    let show_hidden = dirlist_opts
+        .show_hidden()
+        .unwrap_or_else(|| opt.show_hidden());
+    if !show_hidden && is_hidden(entry) {
         return false;
     }
 
-    let file_name = match entry.file_name().to_str() {
-        Some(s) => s,
-        None => return false,
-    };
-
-    if !dirlist_opts.filter_context_ref().is_match(file_name) {
+    let file_name = entry.file_name().to_string_lossy();
+    if !dirlist_opts.filter_context_ref().is_match(&file_name) {
         return false;
     }
 
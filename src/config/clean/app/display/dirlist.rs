@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
 use crate::context::MatchContext;
 
 /// Display options valid pre JoshutoDirList in a JoshutoTab
@@ -5,6 +8,11 @@ use crate::context::MatchContext;
 pub struct DirListDisplayOptions {
     filter_context: MatchContext,
     depth: u8,
+    show_hidden: Option<bool>,
+    // listed as a tree, see `toggle_tree`
+    tree: bool,
+    // directories of the tree whose contents are shown
+    expanded: HashSet<PathBuf>,
 }
 
 impl DirListDisplayOptions {
@@ -23,4 +31,36 @@ impl DirListDisplayOptions {
     pub fn depth(&self) -> u8 {
         self.depth
     }
+
+    /// Overrides the global `show_hidden` option for this directory
+    pub fn set_show_hidden(&mut self, show_hidden: Option<bool>) {
+        self.show_hidden = show_hidden;
+    }
+
+    pub fn show_hidden(&self) -> Option<bool> {
+        self.show_hidden
+    }
+
+    /// Collapses all directories when switching
+    pub fn set_tree(&mut self, tree: bool) {
+        self.tree = tree;
+        self.expanded.clear();
+    }
+
+    pub fn tree(&self) -> bool {
+        self.tree
+    }
+
+    pub fn is_expanded(&self, path: &Path) -> bool {
+        self.expanded.contains(path)
+    }
+
+    pub fn set_expanded(&mut self, path: &Path, expanded: bool) {
+        if expanded {
+            self.expanded.insert(path.to_path_buf());
+        } else {
+            // keep nothing expanded below a collapsed directory
+            self.expanded.retain(|p| !p.starts_with(path));
+        }
+    }
 }
@@ -1,8 +1,10 @@
+pub mod color_mode;
 pub mod config;
 pub mod dirlist;
 pub mod line_mode;
 pub mod line_number;
 pub mod new_tab;
+pub mod size_format;
 pub mod sort;
 pub mod sort_type;
 pub mod tab;
@@ -0,0 +1,44 @@
+/// How file sizes are shown
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeFormat {
+    /// powers of 1024 (K, M, G, ...)
+    Binary,
+    /// powers of 1000 (kB, MB, GB, ...)
+    Si,
+    /// exact number of bytes
+    Bytes,
+}
+
+impl SizeFormat {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "binary" => Some(Self::Binary),
+            "si" => Some(Self::Si),
+            "bytes" => Some(Self::Bytes),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Binary => "binary",
+            Self::Si => "si",
+            Self::Bytes => "bytes",
+        }
+    }
+
+    /// The format after this one, for cycling through all formats
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Binary => Self::Si,
+            Self::Si => Self::Bytes,
+            Self::Bytes => Self::Binary,
+        }
+    }
+}
+
+impl std::default::Default for SizeFormat {
+    fn default() -> Self {
+        Self::Binary
+    }
+}
@@ -13,6 +13,8 @@ use super::sort::SortOption;
 pub enum SortType {
     Lexical,
     Mtime,
+    Atime,
+    Ctime,
     Natural,
     Size,
     Ext,
@@ -23,6 +25,8 @@ impl SortType {
         match s {
             "lexical" => Some(SortType::Lexical),
             "mtime" => Some(SortType::Mtime),
+            "atime" => Some(SortType::Atime),
+            "ctime" => Some(SortType::Ctime),
             "natural" => Some(SortType::Natural),
             "size" => Some(SortType::Size),
             "ext" => Some(SortType::Ext),
@@ -33,6 +37,8 @@ impl SortType {
         match *self {
             SortType::Lexical => "lexical",
             SortType::Mtime => "mtime",
+            SortType::Atime => "atime",
+            SortType::Ctime => "ctime",
             SortType::Natural => "natural",
             SortType::Size => "size",
             SortType::Ext => "ext",
@@ -49,6 +55,8 @@ impl SortType {
             SortType::Lexical => lexical_sort(f1, f2, sort_option),
             SortType::Size => size_sort(f1, f2),
             SortType::Mtime => mtime_sort(f1, f2),
+            SortType::Atime => f1.metadata.accessed().cmp(&f2.metadata.accessed()),
+            SortType::Ctime => f1.metadata.changed().cmp(&f2.metadata.changed()),
             SortType::Ext => ext_sort(f1, f2),
         }
     }
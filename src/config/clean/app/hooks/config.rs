@@ -0,0 +1,25 @@
+use std::path;
+
+use crate::{config::raw::app::display::hooks::HookOptionRaw, util::unix};
+
+/// Scripts called at certain points of joshuto's lifecycle
+#[derive(Clone, Debug, Default)]
+pub struct HookOption {
+    pub on_startup: Option<path::PathBuf>,
+    pub on_exit: Option<path::PathBuf>,
+    pub on_dir_change: Option<path::PathBuf>,
+    pub on_file_open: Option<path::PathBuf>,
+}
+
+impl From<HookOptionRaw> for HookOption {
+    fn from(raw: HookOptionRaw) -> Self {
+        let expand = |s: Option<String>| s.map(|s| unix::expand_shell_string(&s));
+
+        Self {
+            on_startup: expand(raw.on_startup),
+            on_exit: expand(raw.on_exit),
+            on_dir_change: expand(raw.on_dir_change),
+            on_file_open: expand(raw.on_file_open),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod config;
 pub mod display;
+pub mod hooks;
 pub mod preview;
 pub mod search;
 pub mod tab;
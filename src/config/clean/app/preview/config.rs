@@ -1,8 +1,12 @@
 use std::path;
+use std::time::Duration;
 
 use crate::{
     config::{
-        raw::app::display::preview::{default_max_preview_size, PreviewOptionRaw, PreviewProtocol},
+        raw::app::display::preview::{
+            default_max_preview_size, default_preview_cache_size, default_preview_cache_ttl,
+            PreviewOptionRaw, PreviewProtocol,
+        },
         search_directories,
     },
     util::unix,
@@ -14,6 +18,17 @@ pub struct PreviewOption {
     pub max_preview_size: u64,
     pub preview_protocol: PreviewProtocol,
     pub preview_script: Option<path::PathBuf>,
+    pub hex_preview: bool,
+    /// preview recognized source code with the builtin highlighter when the script shows nothing
+    pub syntax_highlight: bool,
+    /// reload the preview of the file under the cursor when it changes
+    pub watch_preview: bool,
+    /// keep previews on disk, off by default as previews of private files would be kept
+    pub preview_cache: bool,
+    /// size of the on-disk preview cache in bytes, 0 disables it
+    pub preview_cache_size: u64,
+    /// cached previews older than this are generated again, `None` keeps them
+    pub preview_cache_ttl: Option<Duration>,
     pub preview_shown_hook_script: Option<path::PathBuf>,
     pub preview_removed_hook_script: Option<path::PathBuf>,
 }
@@ -24,6 +39,12 @@ impl std::default::Default for PreviewOption {
             max_preview_size: default_max_preview_size(),
             preview_protocol: PreviewProtocol::Auto,
             preview_script: None,
+            hex_preview: true,
+            syntax_highlight: true,
+            watch_preview: false,
+            preview_cache: false,
+            preview_cache_size: default_preview_cache_size(),
+            preview_cache_ttl: Some(Duration::from_secs(default_preview_cache_ttl())),
             preview_shown_hook_script: None,
             preview_removed_hook_script: None,
         }
@@ -49,6 +70,15 @@ impl From<PreviewOptionRaw> for PreviewOption {
             max_preview_size: raw.max_preview_size,
             preview_protocol: raw.preview_protocol,
             preview_script,
+            hex_preview: raw.hex_preview,
+            syntax_highlight: raw.syntax_highlight,
+            watch_preview: raw.watch_preview,
+            preview_cache: raw.preview_cache,
+            preview_cache_size: raw.preview_cache_size,
+            preview_cache_ttl: match raw.preview_cache_ttl {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
             preview_shown_hook_script,
             preview_removed_hook_script,
         }
@@ -23,8 +23,8 @@ impl From<BookmarksRaw> for Bookmarks {
             .bookmark
             .drain(..)
             .filter_map(|bookmark| match keyparse::str_to_event(&bookmark.key) {
-                Some(event) => Some((event, bookmark.path)),
-                None => None,
+                Ok(event) => Some((event, bookmark.path)),
+                Err(_) => None,
             })
             .collect();
         map
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::config::clean::app::display::sort::SortOption;
+use crate::config::clean::app::display::sort_type::SortType;
+use crate::config::raw::folder_settings::{FolderSettingRaw, FolderSettingsRaw};
+use crate::config::{ConfigType, TomlConfigFile};
+use crate::util::unix;
+
+/// View settings remembered for a single directory,
+/// `None` falls back to the settings of the tab
+#[derive(Clone, Debug, Default)]
+pub struct FolderSetting {
+    pub sort_method: Option<SortType>,
+    pub reverse: Option<bool>,
+    pub show_hidden: Option<bool>,
+    pub filter: Option<String>,
+}
+
+impl FolderSetting {
+    pub fn sort_options(&self, sort_options: &SortOption) -> SortOption {
+        let mut sort_options = sort_options.clone();
+        if let Some(method) = self.sort_method {
+            sort_options.set_sort_method(method);
+        }
+        if let Some(reverse) = self.reverse {
+            sort_options.reverse = reverse;
+        }
+        sort_options
+    }
+}
+
+pub type FolderSettings = HashMap<PathBuf, FolderSetting>;
+
+impl TomlConfigFile for FolderSettings {
+    type Raw = FolderSettingsRaw;
+
+    fn get_type() -> ConfigType {
+        ConfigType::FolderSettings
+    }
+}
+
+impl From<FolderSettingsRaw> for FolderSettings {
+    fn from(raw: FolderSettingsRaw) -> Self {
+        raw.folder
+            .into_iter()
+            .map(|folder| {
+                let setting = FolderSetting {
+                    sort_method: folder.sort_method.and_then(|s| SortType::from_str(&s)),
+                    reverse: folder.reverse,
+                    show_hidden: folder.show_hidden,
+                    filter: folder.filter,
+                };
+                (unix::expand_shell_string(&folder.path), setting)
+            })
+            .collect()
+    }
+}
+
+impl From<&FolderSettings> for FolderSettingsRaw {
+    fn from(settings: &FolderSettings) -> Self {
+        let mut folder: Vec<FolderSettingRaw> = settings
+            .iter()
+            .map(|(path, setting)| FolderSettingRaw {
+                path: path.to_string_lossy().to_string(),
+                sort_method: setting.sort_method.map(|s| s.as_str().to_string()),
+                reverse: setting.reverse,
+                show_hidden: setting.show_hidden,
+                filter: setting.filter.clone(),
+            })
+            .collect();
+        folder.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { folder }
+    }
+}
@@ -14,6 +14,7 @@ pub struct Icons {
     pub ext: HashMap<String, String>,
     pub default_file: String,
     pub default_dir: String,
+    pub default_git_dir: Option<String>,
 }
 
 impl Icons {
@@ -44,6 +45,7 @@ impl From<IconsRaw> for Icons {
             file_exact: raw.file_exact,
             ext: raw.ext,
             default_file: raw.defaults.file,
+            default_git_dir: raw.defaults.git_directory,
             default_dir: raw.defaults.directory,
         }
     }
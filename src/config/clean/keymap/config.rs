@@ -3,23 +3,57 @@ use std::convert::From;
 use std::str::FromStr;
 
 use termion::event::Event;
+use toml::Spanned;
 
+use crate::config::clean::app::expand_alias;
+use crate::config::problems::{self, ConfigProblem};
 use crate::config::raw::keymap::{AppKeyMappingRaw, CommandKeymap};
-use crate::config::{ConfigType, TomlConfigFile};
-use crate::error::AppResult;
+use crate::config::{parse_file_to_config, search_config_directories, ConfigType, TomlConfigFile};
+use crate::error::{AppError, AppResult};
 use crate::key_command::{Command, CommandKeybind};
 use crate::traits::ToString;
 use crate::util::keyparse::str_to_event;
 
 use super::DEFAULT_CONFIG_FILE_PATH;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum KeymapError {
+    /// the key sequence is already bound to a command
     Conflict,
+    /// the key sequence is a prefix of another binding or the other way around,
+    /// so one of them can never be triggered
+    Shadowed,
+    /// the key sequence is not bound to anything
+    Unmapped,
+}
+
+impl std::fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Conflict => write!(f, "key sequence is already mapped"),
+            Self::Shadowed => write!(f, "key sequence overlaps with another key sequence"),
+            Self::Unmapped => write!(f, "key sequence is not mapped"),
+        }
+    }
+}
+
+/// Changes to the keymap made at runtime with `map` and `unmap`
+#[derive(Clone, Debug)]
+pub enum KeymapEdit {
+    Map {
+        events: Vec<Event>,
+        commands: Vec<Command>,
+    },
+    Unmap {
+        events: Vec<Event>,
+    },
+    /// the whole keymap is replaced after `reload_config`
+    Replace(Box<AppKeyMapping>),
 }
 
 pub type KeyMapping = HashMap<Event, CommandKeybind>;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct AppKeyMapping {
     pub default_view: KeyMapping,
     pub task_view: KeyMapping,
@@ -40,14 +74,90 @@ impl AppKeyMapping {
         let keymapping: Self = Self::from(crude);
         Ok(keymapping)
     }
+
+    pub fn from_raw(raw: AppKeyMappingRaw, aliases: &HashMap<String, String>) -> Self {
+        let mut keymaps = Self::new();
+        keymaps.default_view = command_keymaps_vec_to_map(&raw.default_view.keymap, aliases);
+        keymaps.task_view = command_keymaps_vec_to_map(&raw.task_view.keymap, aliases);
+        keymaps.help_view = command_keymaps_vec_to_map(&raw.help_view.keymap, aliases);
+        keymaps
+    }
+
+    /// Loads keymap.toml like `get_config`, with command aliases usable in keybindings
+    pub fn get_config_with_aliases(aliases: &HashMap<String, String>) -> Self {
+        let file_name = Self::get_type().as_filename();
+        match search_config_directories(file_name) {
+            Some(file_path) => match parse_file_to_config::<AppKeyMappingRaw, _>(&file_path) {
+                Ok(raw) => Self::from_raw(raw, aliases),
+                Err(e) => {
+                    problems::report(ConfigProblem::from_error(file_name, &e));
+                    Self::default()
+                }
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Reads keymap.toml again like `get_config_with_aliases`, but fails on errors
+    pub fn reload_config_with_aliases(aliases: &HashMap<String, String>) -> AppResult<Self> {
+        let file_name = Self::get_type().as_filename();
+        match search_config_directories(file_name) {
+            Some(file_path) => parse_file_to_config::<AppKeyMappingRaw, _>(&file_path)
+                .map(|raw| Self::from_raw(raw, aliases))
+                .map_err(|e| {
+                    AppError::new(
+                        e.kind().clone(),
+                        format!("Failed to parse {}: {}", file_name, e),
+                    )
+                }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Applies a runtime change to the keymap of the default view.
+    ///
+    /// Mapping an already bound key sequence replaces the old binding.
+    /// Returns the number of bindings removed by `unmap`, which is more than one
+    /// when the key sequence is the prefix of several bindings.
+    pub fn apply_edit(&mut self, edit: KeymapEdit) -> Result<usize, KeymapError> {
+        match edit {
+            KeymapEdit::Map { events, commands } => {
+                if let Some(CommandKeybind::SimpleKeybind { .. }) =
+                    get_keycommand(&self.default_view, &events)
+                {
+                    remove_keycommand(&mut self.default_view, &events);
+                }
+                insert_keycommand(&mut self.default_view, commands, None, &events).map(|_| 0)
+            }
+            KeymapEdit::Unmap { events } => remove_keycommand(&mut self.default_view, &events)
+                .map(|removed| count_keybinds(&removed))
+                .ok_or(KeymapError::Unmapped),
+            KeymapEdit::Replace(keymap) => {
+                *self = *keymap;
+                Ok(0)
+            }
+        }
+    }
 }
 
-fn command_keymaps_vec_to_map(keymaps: &[CommandKeymap]) -> HashMap<Event, CommandKeybind> {
+fn command_keymaps_vec_to_map(
+    keymaps: &[Spanned<CommandKeymap>],
+    aliases: &HashMap<String, String>,
+) -> HashMap<Event, CommandKeybind> {
     let mut hashmap = HashMap::new();
 
-    for keymap in keymaps {
+    for spanned in keymaps {
+        let keymap = spanned.get_ref();
+        let report = |message: String| {
+            problems::report(ConfigProblem::new(
+                AppKeyMapping::get_type().as_filename(),
+                Some(spanned.span()),
+                message,
+            ))
+        };
+
         if keymap.commands.is_empty() && keymap.command.is_none() {
-            eprintln!("Keymap `commands` cannot be empty");
+            report("keymap `commands` cannot be empty".to_string());
             continue;
         }
         let commands: Vec<Command> = match &keymap.command {
@@ -55,13 +165,15 @@ fn command_keymaps_vec_to_map(keymaps: &[CommandKeymap]) -> HashMap<Event, Comma
             None => keymap.commands.clone(),
         }
         .iter()
-        .filter_map(|cmd_str| match Command::from_str(cmd_str) {
-            Ok(s) => Some(s),
-            Err(err) => {
-                eprintln!("Keymap error: {}", err);
-                None
-            }
-        })
+        .filter_map(
+            |cmd_str| match Command::from_str(&expand_alias(aliases, cmd_str)) {
+                Ok(s) => Some(s),
+                Err(err) => {
+                    report(format!("`{}`: {}", cmd_str, err));
+                    None
+                }
+            },
+        )
         .collect();
 
         let expected_len = if keymap.command.is_none() {
@@ -71,18 +183,26 @@ fn command_keymaps_vec_to_map(keymaps: &[CommandKeymap]) -> HashMap<Event, Comma
         };
 
         if commands.len() != expected_len {
-            eprintln!("Failed to parse commands: {:?}", keymap.commands);
             continue;
         }
 
-        let key_events: Vec<Event> = keymap
+        let key_events: Vec<Event> = match keymap
             .keys
             .iter()
-            .filter_map(|s| str_to_event(s.as_str()))
-            .collect();
-
-        if key_events.len() != keymap.keys.len() {
-            eprintln!("Failed to parse keys: {:?}", keymap.keys);
+            .map(|s| str_to_event(s.as_str()))
+            .collect()
+        {
+            Ok(events) => events,
+            Err(err) => {
+                report(format!("keys {:?}: {}", keymap.keys, err));
+                continue;
+            }
+        };
+        if key_events.is_empty() {
+            report(format!(
+                "keymap `keys` cannot be empty: {:?}",
+                keymap.commands
+            ));
             continue;
         }
 
@@ -90,11 +210,19 @@ fn command_keymaps_vec_to_map(keymaps: &[CommandKeymap]) -> HashMap<Event, Comma
         if let Err(err) =
             insert_keycommand(&mut hashmap, commands, command_description, &key_events)
         {
+            let events_str: Vec<String> = key_events.iter().map(|e| e.to_string()).collect();
             match err {
                 KeymapError::Conflict => {
-                    let events_str: Vec<String> =
-                        key_events.iter().map(|e| e.to_string()).collect();
-                    eprintln!("Error: Ambiguous Keymapping: Multiple commands mapped to key sequence {:?} {:?}", events_str, keymap.commands);
+                    report(format!(
+                        "ambiguous keymapping: multiple commands mapped to key sequence {:?}",
+                        events_str
+                    ));
+                }
+                KeymapError::Unmapped => {
+                    report(format!("key sequence {:?} is not mapped", events_str));
+                }
+                KeymapError::Shadowed => {
+                    report(format!("shadowed keymapping: key sequence {:?} overlaps with a longer or shorter key sequence", events_str));
                 }
             }
         }
@@ -104,11 +232,7 @@ fn command_keymaps_vec_to_map(keymaps: &[CommandKeymap]) -> HashMap<Event, Comma
 
 impl From<AppKeyMappingRaw> for AppKeyMapping {
     fn from(raw: AppKeyMappingRaw) -> Self {
-        let mut keymaps = Self::new();
-        keymaps.default_view = command_keymaps_vec_to_map(&raw.default_view.keymap);
-        keymaps.task_view = command_keymaps_vec_to_map(&raw.task_view.keymap);
-        keymaps.help_view = command_keymaps_vec_to_map(&raw.help_view.keymap);
-        keymaps
+        Self::from_raw(raw, &HashMap::new())
     }
 }
 
@@ -142,7 +266,12 @@ fn insert_keycommand(
     let event = events[0].clone();
     if num_events == 1 {
         match keymap.entry(event) {
-            Entry::Occupied(_) => return Err(KeymapError::Conflict),
+            Entry::Occupied(entry) => {
+                return match entry.get() {
+                    CommandKeybind::SimpleKeybind { .. } => Err(KeymapError::Conflict),
+                    CommandKeybind::CompositeKeybind(_) => Err(KeymapError::Shadowed),
+                }
+            }
             Entry::Vacant(entry) => entry.insert(CommandKeybind::SimpleKeybind {
                 commands,
                 description,
@@ -156,7 +285,7 @@ fn insert_keycommand(
             CommandKeybind::CompositeKeybind(ref mut m) => {
                 insert_keycommand(m, commands, description, &events[1..])
             }
-            _ => Err(KeymapError::Conflict),
+            _ => Err(KeymapError::Shadowed),
         },
         Entry::Vacant(entry) => {
             let mut new_map = KeyMapping::new();
@@ -169,3 +298,119 @@ fn insert_keycommand(
         }
     }
 }
+
+fn get_keycommand<'a>(keymap: &'a KeyMapping, events: &[Event]) -> Option<&'a CommandKeybind> {
+    let (event, rest) = events.split_first()?;
+    match keymap.get(event)? {
+        CommandKeybind::CompositeKeybind(m) if !rest.is_empty() => get_keycommand(m, rest),
+        bind if rest.is_empty() => Some(bind),
+        _ => None,
+    }
+}
+
+fn count_keybinds(keybind: &CommandKeybind) -> usize {
+    match keybind {
+        CommandKeybind::SimpleKeybind { .. } => 1,
+        CommandKeybind::CompositeKeybind(m) => m.values().map(count_keybinds).sum(),
+    }
+}
+
+// removes the binding and any composite keybinds left empty by it
+fn remove_keycommand(keymap: &mut KeyMapping, events: &[Event]) -> Option<CommandKeybind> {
+    let (event, rest) = events.split_first()?;
+    if rest.is_empty() {
+        return keymap.remove(event);
+    }
+    let removed = match keymap.get_mut(event)? {
+        CommandKeybind::CompositeKeybind(m) => {
+            let removed = remove_keycommand(m, rest)?;
+            if m.is_empty() {
+                keymap.remove(event);
+            }
+            removed
+        }
+        CommandKeybind::SimpleKeybind { .. } => return None,
+    };
+    Some(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use termion::event::Key;
+
+    use super::*;
+
+    fn keys(chars: &str) -> Vec<Event> {
+        chars.chars().map(|c| Event::Key(Key::Char(c))).collect()
+    }
+
+    #[test]
+    fn test_insert_conflict_and_shadow() {
+        let mut keymap = KeyMapping::new();
+        assert!(insert_keycommand(&mut keymap, vec![Command::Help], None, &keys("gh")).is_ok());
+        assert_eq!(
+            insert_keycommand(&mut keymap, vec![Command::Help], None, &keys("gh")),
+            Err(KeymapError::Conflict)
+        );
+        assert_eq!(
+            insert_keycommand(&mut keymap, vec![Command::Help], None, &keys("g")),
+            Err(KeymapError::Shadowed)
+        );
+        assert_eq!(
+            insert_keycommand(&mut keymap, vec![Command::Help], None, &keys("ghx")),
+            Err(KeymapError::Shadowed)
+        );
+    }
+
+    #[test]
+    fn test_map_and_unmap() {
+        let mut keymap = AppKeyMapping::new();
+        let map = |events: Vec<Event>, command: Command| KeymapEdit::Map {
+            events,
+            commands: vec![command],
+        };
+        assert!(keymap.apply_edit(map(keys("gx"), Command::Help)).is_ok());
+        // remapping replaces the old binding
+        assert!(keymap
+            .apply_edit(map(keys("gx"), Command::CloseTab))
+            .is_ok());
+        assert!(matches!(
+            get_keycommand(&keymap.default_view, &keys("gx")),
+            Some(CommandKeybind::SimpleKeybind { commands, .. })
+                if matches!(commands[0], Command::CloseTab)
+        ));
+        assert_eq!(
+            keymap.apply_edit(map(keys("g"), Command::Help)),
+            Err(KeymapError::Shadowed)
+        );
+
+        assert_eq!(
+            keymap.apply_edit(KeymapEdit::Unmap { events: keys("gx") }),
+            Ok(1)
+        );
+        assert!(keymap.default_view.is_empty());
+        assert_eq!(
+            keymap.apply_edit(KeymapEdit::Unmap { events: keys("gx") }),
+            Err(KeymapError::Unmapped)
+        );
+    }
+
+    #[test]
+    fn test_unmap_prefix_counts_dropped_bindings() {
+        let mut keymap = AppKeyMapping::new();
+        for events in [keys("gx"), keys("gyy"), keys("gyz")] {
+            assert!(insert_keycommand(
+                &mut keymap.default_view,
+                vec![Command::Help],
+                None,
+                &events
+            )
+            .is_ok());
+        }
+        assert_eq!(
+            keymap.apply_edit(KeymapEdit::Unmap { events: keys("g") }),
+            Ok(3)
+        );
+        assert!(keymap.default_view.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::config::problems::{self, ConfigProblem};
 use crate::config::{raw::mimetype::AppProgramRegistryRaw, ConfigType, TomlConfigFile};
 
 use super::{ExtensionAppList, MimetypeAppList};
@@ -24,16 +25,37 @@ impl AppProgramRegistry {
     }
 }
 
+/// The programs of `class`, reporting classes missing from the `[class]` table
+fn class_app_list(
+    classes: &HashMap<String, ExtensionAppList>,
+    class: &str,
+    inherited_by: &str,
+) -> ExtensionAppList {
+    if class.is_empty() {
+        return ExtensionAppList::new();
+    }
+    match classes.get(class) {
+        Some(app_list) => app_list.clone(),
+        None => {
+            problems::report(ConfigProblem::new(
+                AppProgramRegistry::get_type().as_filename(),
+                None,
+                format!("{}: unknown class `{}` in `inherit`", inherited_by, class),
+            ));
+            ExtensionAppList::new()
+        }
+    }
+}
+
 impl From<AppProgramRegistryRaw> for AppProgramRegistry {
     fn from(raw: AppProgramRegistryRaw) -> Self {
         let mut extension = ExtensionRegistry::new();
         for (ext, app_list) in raw._extension {
-            let class = app_list.parent();
-            let mut combined_app_list: ExtensionAppList = raw
-                ._class
-                .get(class)
-                .map(|v| (*v).clone())
-                .unwrap_or_default();
+            let mut combined_app_list = class_app_list(
+                &raw._class,
+                app_list.parent(),
+                &format!("extension.{}", ext),
+            );
             combined_app_list.extend_from_slice(app_list.app_list());
 
             extension.insert(ext, combined_app_list);
@@ -41,12 +63,8 @@ impl From<AppProgramRegistryRaw> for AppProgramRegistry {
 
         let mut mimetype = MimetypeRegistry::new();
         for (ttype, data) in raw._mimetype {
-            let class = data.parent();
-            let mut combined_app_list: ExtensionAppList = raw
-                ._class
-                .get(class)
-                .map(|v| (*v).clone())
-                .unwrap_or_default();
+            let mut combined_app_list =
+                class_app_list(&raw._class, data.parent(), &format!("mimetype.{}", ttype));
             combined_app_list.extend_from_slice(data.app_list());
 
             let subtypes = data
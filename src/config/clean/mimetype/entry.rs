@@ -1,6 +1,17 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use std::env;
 use std::fmt;
+use std::path::PathBuf;
+
+use crate::util::unix;
+
+fn deserialize_cwd<'de, D>(deserializer: D) -> Result<Option<PathBuf>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let cwd: Option<String> = Option::deserialize(deserializer)?;
+    Ok(cwd.map(|s| unix::expand_shell_string(&s)))
+}
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct ProgramEntry {
@@ -14,6 +25,10 @@ pub struct ProgramEntry {
     _silent: bool,
     #[serde(default, rename = "pager")]
     _pager: bool,
+    #[serde(default, rename = "confirm_exit")]
+    _confirm_exit: bool,
+    #[serde(default, rename = "cwd", deserialize_with = "deserialize_cwd")]
+    _cwd: Option<PathBuf>,
 }
 
 impl ProgramEntry {
@@ -24,6 +39,8 @@ impl ProgramEntry {
             _fork: false,
             _silent: false,
             _pager: false,
+            _confirm_exit: false,
+            _cwd: None,
         }
     }
 
@@ -54,6 +71,11 @@ impl ProgramEntry {
         self
     }
 
+    pub fn cwd(&mut self, cwd: PathBuf) -> &mut Self {
+        self._cwd = Some(cwd);
+        self
+    }
+
     pub fn get_command(&self) -> &str {
         self._command.as_str()
     }
@@ -74,6 +96,15 @@ impl ProgramEntry {
         self._pager
     }
 
+    pub fn get_confirm_exit(&self) -> bool {
+        self._confirm_exit
+    }
+
+    /// Working directory of the program, joshuto's current directory if `None`
+    pub fn get_cwd(&self) -> Option<&PathBuf> {
+        self._cwd.as_ref()
+    }
+
     // TODO: Windows support
     pub fn program_exists(&self) -> bool {
         let program = self.get_command();
@@ -91,6 +122,8 @@ impl std::default::Default for ProgramEntry {
             _fork: false,
             _silent: false,
             _pager: false,
+            _confirm_exit: false,
+            _cwd: None,
         }
     }
 }
@@ -112,6 +145,9 @@ impl std::fmt::Display for ProgramEntry {
         if self.get_pager() {
             f.write_str("[pager]").unwrap();
         }
+        if self.get_confirm_exit() {
+            f.write_str("[confirm_exit]").unwrap();
+        }
         f.write_str("")
     }
 }
@@ -7,7 +7,7 @@ use crate::error::AppResult;
 
 use super::style::AppStyle;
 use super::tab::TabTheme;
-use super::DEFAULT_CONFIG_FILE_PATH;
+use super::{DEFAULT_CONFIG_FILE_PATH, HIGH_CONTRAST_CONFIG_FILE_PATH};
 
 #[derive(Clone, Debug)]
 pub struct AppTheme {
@@ -22,6 +22,10 @@ pub struct AppTheme {
     pub socket: AppStyle,
     pub ext: HashMap<String, AppStyle>,
     pub lscolors: Option<LsColors>,
+    /// theme used to highlight source code in previews, `None` disables highlighting
+    pub syntax_theme: Option<String>,
+    /// drawn in front of selected entries, empty to rely on `selection` alone
+    pub selection_prefix: String,
 }
 
 impl AppTheme {
@@ -29,6 +33,13 @@ impl AppTheme {
         let raw: AppThemeRaw = toml::from_str(DEFAULT_CONFIG_FILE_PATH)?;
         Ok(Self::from(raw))
     }
+
+    /// The bundled theme for `high_contrast`, in place of theme.toml
+    pub fn high_contrast() -> Self {
+        // This should not fail, like the default theme
+        let raw: AppThemeRaw = toml::from_str(HIGH_CONTRAST_CONFIG_FILE_PATH).unwrap();
+        Self::from(raw)
+    }
 }
 
 impl TomlConfigFile for AppTheme {
@@ -86,6 +97,8 @@ impl From<AppThemeRaw> for AppTheme {
             ext,
             tabs: TabTheme::from(tabs),
             lscolors,
+            syntax_theme: Some(raw.syntax_theme).filter(|s| !s.is_empty()),
+            selection_prefix: raw.selection_prefix,
         }
     }
 }
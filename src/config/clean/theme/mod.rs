@@ -9,3 +9,11 @@ pub const DEFAULT_CONFIG_FILE_PATH: &str = include_str!("../../../../config/them
 
 #[cfg(target_os = "windows")]
 pub const DEFAULT_CONFIG_FILE_PATH: &str = include_str!("..\\..\\..\\..\\config\\theme.toml");
+
+#[cfg(not(target_os = "windows"))]
+pub const HIGH_CONTRAST_CONFIG_FILE_PATH: &str =
+    include_str!("../../../../config/theme_high_contrast.toml");
+
+#[cfg(target_os = "windows")]
+pub const HIGH_CONTRAST_CONFIG_FILE_PATH: &str =
+    include_str!("..\\..\\..\\..\\config\\theme_high_contrast.toml");
@@ -6,6 +6,7 @@ pub enum ConfigType {
     Theme,
     Preview,
     Bookmarks,
+    FolderSettings,
     Icons,
 }
 
@@ -34,6 +35,7 @@ impl ConfigType {
             Self::Theme,
             Self::Preview,
             Self::Bookmarks,
+            Self::FolderSettings,
             Self::Icons,
         ]
     }
@@ -46,6 +48,7 @@ impl ConfigType {
             Self::Theme => "theme",
             Self::Preview => "preview",
             Self::Bookmarks => "bookmarks",
+            Self::FolderSettings => "folder_settings",
             Self::Icons => "icons",
         }
     }
@@ -58,6 +61,7 @@ impl ConfigType {
             Self::Theme => "theme.toml",
             Self::Preview => "preview.toml",
             Self::Bookmarks => "bookmarks.toml",
+            Self::FolderSettings => "folder_settings.toml",
             Self::Icons => "icons.toml",
         }
     }
@@ -69,7 +73,7 @@ impl ConfigType {
             Self::Keymap => Some(clean::keymap::DEFAULT_CONFIG_FILE_PATH),
             Self::Theme => Some(clean::theme::DEFAULT_CONFIG_FILE_PATH),
             Self::Icons => Some(clean::icon::DEFAULT_CONFIG_FILE_PATH),
-            Self::Mimetype | Self::Preview | Self::Bookmarks => None,
+            Self::Mimetype | Self::Preview | Self::Bookmarks | Self::FolderSettings => None,
         }
     }
 }
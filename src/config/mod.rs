@@ -2,13 +2,14 @@ pub mod clean;
 pub mod raw;
 
 pub mod config_type;
+pub mod problems;
 pub use config_type::ConfigType;
 
 use serde::de::DeserializeOwned;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
 use crate::CONFIG_HIERARCHY;
 
 pub trait TomlConfigFile: Sized + Default {
@@ -19,6 +20,12 @@ pub trait TomlConfigFile: Sized + Default {
     fn get_config() -> Self {
         parse_config_or_default::<Self::Raw, Self>(Self::get_type().as_filename())
     }
+
+    /// Reads the config file again like `get_config`, but fails on errors
+    /// instead of falling back to the default config
+    fn reload_config() -> AppResult<Self> {
+        parse_config_or_error::<Self::Raw, Self>(Self::get_type().as_filename())
+    }
 }
 
 // searches a list of folders for a given file in order of preference
@@ -40,11 +47,29 @@ fn parse_file_to_config<T, S>(file_path: &Path) -> AppResult<S>
 where
     T: DeserializeOwned + Into<S>,
 {
+    log::debug!("reading {}", file_path.display());
     let file_contents = fs::read_to_string(file_path)?;
     let config = toml::from_str::<T>(&file_contents)?;
     Ok(config.into())
 }
 
+pub fn parse_config_or_error<T, S>(file_name: &str) -> AppResult<S>
+where
+    T: DeserializeOwned + Into<S>,
+    S: std::default::Default,
+{
+    match search_config_directories(file_name) {
+        Some(file_path) => parse_file_to_config::<T, S>(&file_path).map_err(|e| {
+            log::warn!("failed to parse {}: {}", file_path.display(), e);
+            AppError::new(
+                e.kind().clone(),
+                format!("Failed to parse {}: {}", file_name, e),
+            )
+        }),
+        None => Ok(S::default()),
+    }
+}
+
 pub fn parse_config_or_default<T, S>(file_name: &str) -> S
 where
     T: DeserializeOwned + Into<S>,
@@ -54,7 +79,7 @@ where
         Some(file_path) => match parse_file_to_config::<T, S>(&file_path) {
             Ok(s) => s,
             Err(e) => {
-                eprintln!("Failed to parse {}: {}", file_name, e);
+                problems::report(problems::ConfigProblem::from_error(file_name, &e));
                 S::default()
             }
         },
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use super::search_config_directories;
+use crate::error::{AppError, AppErrorKind};
+
+lazy_static! {
+    // configs are loaded before there is a user interface to show problems in
+    static ref CONFIG_PROBLEMS: Mutex<Vec<ConfigProblem>> = Mutex::new(Vec::new());
+}
+
+/// A mistake found in a config file, which is skipped instead of failing to start
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub file_name: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    /// `span` is the byte range in the config file `file_name` the problem is about
+    pub fn new(file_name: &str, span: Option<Range<usize>>, message: String) -> Self {
+        let line = span.and_then(|span| {
+            let path = search_config_directories(file_name)?;
+            let contents = fs::read_to_string(path).ok()?;
+            Some(line_of(&contents, span.start))
+        });
+        Self {
+            file_name: file_name.to_string(),
+            line,
+            message,
+        }
+    }
+
+    /// Problem for a config file failing to parse, with the line of the syntax error if known
+    pub fn from_error(file_name: &str, err: &AppError) -> Self {
+        match err.kind() {
+            AppErrorKind::TomlDeError(e) => {
+                let message = e.message().trim_end().replace('\n', ", ");
+                Self::new(file_name, e.span(), message)
+            }
+            _ => Self::new(file_name, None, err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.file_name, line, self.message),
+            None => write!(f, "{}: {}", self.file_name, self.message),
+        }
+    }
+}
+
+/// 1-based line number of the byte `offset` in `contents`
+fn line_of(contents: &str, offset: usize) -> usize {
+    let offset = offset.min(contents.len());
+    contents.as_bytes()[..offset]
+        .iter()
+        .filter(|b| **b == b'\n')
+        .count()
+        + 1
+}
+
+pub fn report(problem: ConfigProblem) {
+    log::warn!("{}", problem);
+    if let Ok(mut problems) = CONFIG_PROBLEMS.lock() {
+        problems.push(problem);
+    }
+}
+
+/// The problems reported since the last call
+pub fn take_problems() -> Vec<ConfigProblem> {
+    CONFIG_PROBLEMS
+        .lock()
+        .map(|mut problems| std::mem::take(&mut *problems))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_of_test() {
+        let contents = "a = 1\nb = 2\n\nc = 3\n";
+        assert_eq!(line_of(contents, 0), 1);
+        assert_eq!(line_of(contents, 6), 2);
+        assert_eq!(line_of(contents, 13), 4);
+        assert_eq!(line_of(contents, 100), 5);
+    }
+
+    #[test]
+    fn from_error_test() {
+        let err: AppError = toml::from_str::<toml::Table>("a = 1\nb = \"\n")
+            .unwrap_err()
+            .into();
+        let problem = ConfigProblem::from_error("no_such_config.toml", &err);
+        assert_eq!(problem.line, None);
+        assert!(!problem.message.contains('\n'));
+        assert!(problem.to_string().starts_with("no_such_config.toml: "));
+    }
+}
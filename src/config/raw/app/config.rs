@@ -2,17 +2,41 @@ use std::collections::HashMap;
 
 use serde::Deserialize;
 
+use super::display::hooks::HookOptionRaw;
 use super::display::preview::PreviewOptionRaw;
 use super::display::search::SearchOptionRaw;
 use super::display::tab::TabOptionRaw;
 use super::display::DisplayOptionRaw;
 
+use crate::util::name_resolution::DEFAULT_RENAME_PATTERN;
+
 const fn default_true() -> bool {
     true
 }
 const fn default_scroll_offset() -> usize {
     6
 }
+const fn default_shred_passes() -> usize {
+    3
+}
+const fn default_dir_cache_size() -> usize {
+    100_000
+}
+const fn default_max_concurrent_tasks() -> usize {
+    1
+}
+const fn default_prompt_history_size() -> usize {
+    100
+}
+fn default_paste_rename_pattern() -> String {
+    DEFAULT_RENAME_PATTERN.to_string()
+}
+fn default_log_level() -> String {
+    "warn".to_string()
+}
+fn default_dragdrop_command() -> String {
+    "dragon-drop --and-exit".to_string()
+}
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct CustomCommand {
@@ -30,13 +54,47 @@ pub struct AppConfigRaw {
     pub xdg_open: bool,
     #[serde(default)]
     pub xdg_open_fork: bool,
+    #[serde(default)]
+    pub open_selected: bool,
+    #[serde(default)]
+    pub readonly: bool,
+    #[serde(default = "default_shred_passes")]
+    pub shred_passes: usize,
     #[serde(default = "default_true")]
     pub watch_files: bool,
+    #[serde(default)]
+    pub watch_config: bool,
+    #[serde(default = "default_max_concurrent_tasks")]
+    pub max_concurrent_tasks: usize,
+    #[serde(default)]
+    pub task_threads: usize,
+    #[serde(default = "default_dir_cache_size")]
+    pub dir_cache_size: usize,
+    #[serde(default = "default_paste_rename_pattern")]
+    pub paste_rename_pattern: String,
     #[serde(default = "default_true")]
     pub focus_on_create: bool,
     #[serde(default = "default_true")]
     pub mouse_support: bool,
     #[serde(default)]
+    pub update_title: bool,
+    #[serde(default)]
+    pub message_timeout: u64,
+    #[serde(default = "default_prompt_history_size")]
+    pub prompt_history_size: usize,
+    #[serde(default)]
+    pub desktop_notifications: bool,
+    #[serde(default)]
+    pub crash_log: Option<String>,
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    #[serde(default)]
+    pub log_file: Option<String>,
+    #[serde(default)]
+    pub terminal: Option<String>,
+    #[serde(default = "default_dragdrop_command")]
+    pub dragdrop_command: String,
+    #[serde(default, alias = "alias")]
     pub cmd_aliases: HashMap<String, String>,
     #[serde(default, rename = "display")]
     pub display_options: DisplayOptionRaw,
@@ -46,6 +104,8 @@ pub struct AppConfigRaw {
     pub search_options: SearchOptionRaw,
     #[serde(default, rename = "tab")]
     pub tab_options: TabOptionRaw,
+    #[serde(default, rename = "hooks")]
+    pub hook_options: HookOptionRaw,
     #[serde(default)]
     pub custom_commands: Vec<CustomCommand>,
 }
@@ -8,6 +8,14 @@ fn default_mode() -> String {
     "default".to_string()
 }
 
+fn default_colors() -> String {
+    "auto".to_string()
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d %H:%M".to_string()
+}
+
 const fn default_true() -> bool {
     true
 }
@@ -33,6 +41,12 @@ pub struct DisplayOptionRaw {
     #[serde(default = "default_scroll_offset")]
     pub scroll_offset: usize,
 
+    #[serde(default)]
+    pub wrap_cursor: bool,
+
+    #[serde(default)]
+    pub size_format: String,
+
     #[serde(default = "default_true")]
     pub show_borders: bool,
 
@@ -40,8 +54,17 @@ pub struct DisplayOptionRaw {
     pub show_hidden: bool,
 
     #[serde(default)]
+    pub hide_ignored: bool,
+
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+
+    #[serde(default, alias = "icons")]
     pub show_icons: bool,
 
+    #[serde(default)]
+    pub file_markers: bool,
+
     #[serde(default = "default_true")]
     pub tilde_in_titlebar: bool,
 
@@ -53,6 +76,21 @@ pub struct DisplayOptionRaw {
 
     #[serde(default)]
     pub linemode: LineMode,
+
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+
+    #[serde(default)]
+    pub middle_ellipsis: bool,
+
+    #[serde(default)]
+    pub lazy_metadata: bool,
+
+    #[serde(default = "default_colors")]
+    pub colors: String,
+
+    #[serde(default)]
+    pub high_contrast: bool,
 }
 
 impl std::default::Default for DisplayOptionRaw {
@@ -63,13 +101,23 @@ impl std::default::Default for DisplayOptionRaw {
             collapse_preview: true,
             column_ratio: None,
             scroll_offset: 4,
+            wrap_cursor: false,
+            size_format: "binary".to_string(),
             show_borders: true,
             show_hidden: false,
+            hide_ignored: false,
+            ignore_patterns: Vec::new(),
             show_icons: false,
+            file_markers: false,
             sort_options: SortOptionRaw::default(),
             tilde_in_titlebar: true,
             line_number_style: "none".to_string(),
             linemode: LineMode::default(),
+            date_format: default_date_format(),
+            middle_ellipsis: false,
+            lazy_metadata: false,
+            colors: default_colors(),
+            high_contrast: false,
         }
     }
 }
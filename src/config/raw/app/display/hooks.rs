@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, Default)]
+pub struct HookOptionRaw {
+    #[serde(default)]
+    pub on_startup: Option<String>,
+    #[serde(default)]
+    pub on_exit: Option<String>,
+    #[serde(default)]
+    pub on_dir_change: Option<String>,
+    #[serde(default)]
+    pub on_file_open: Option<String>,
+}
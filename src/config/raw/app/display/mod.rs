@@ -1,4 +1,5 @@
 pub mod config;
+pub mod hooks;
 pub mod preview;
 pub mod search;
 pub mod sort;
@@ -5,6 +5,18 @@ pub const fn default_max_preview_size() -> u64 {
     2 * 1024 * 1024 // 2 MB
 }
 
+pub const fn default_preview_cache_size() -> u64 {
+    100 * 1024 * 1024 // 100 MB
+}
+
+pub const fn default_preview_cache_ttl() -> u64 {
+    7 * 24 * 60 * 60 // a week
+}
+
+const fn default_true() -> bool {
+    true
+}
+
 #[derive(Clone, Debug, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum PreviewProtocol {
@@ -23,6 +35,18 @@ pub struct PreviewOptionRaw {
     pub preview_protocol: PreviewProtocol,
     #[serde(default)]
     pub preview_script: Option<String>,
+    #[serde(default = "default_true")]
+    pub hex_preview: bool,
+    #[serde(default = "default_true")]
+    pub syntax_highlight: bool,
+    #[serde(default)]
+    pub watch_preview: bool,
+    #[serde(default)]
+    pub preview_cache: bool,
+    #[serde(default = "default_preview_cache_size")]
+    pub preview_cache_size: u64,
+    #[serde(default = "default_preview_cache_ttl")]
+    pub preview_cache_ttl: u64,
     #[serde(default)]
     pub preview_shown_hook_script: Option<String>,
     #[serde(default)]
@@ -35,6 +59,12 @@ impl std::default::Default for PreviewOptionRaw {
             max_preview_size: default_max_preview_size(),
             preview_protocol: PreviewProtocol::Auto,
             preview_script: None,
+            hex_preview: true,
+            syntax_highlight: true,
+            watch_preview: false,
+            preview_cache: false,
+            preview_cache_size: default_preview_cache_size(),
+            preview_cache_ttl: default_preview_cache_ttl(),
             preview_shown_hook_script: None,
             preview_removed_hook_script: None,
         }
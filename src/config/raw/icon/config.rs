@@ -7,6 +7,9 @@ pub struct DefaultIcons {
     pub file: String,
     #[serde(default)]
     pub directory: String,
+    /// Directories containing a `.git` folder
+    #[serde(default)]
+    pub git_directory: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -26,6 +29,7 @@ impl std::default::Default for DefaultIcons {
         Self {
             file: "".to_string(),
             directory: "".to_string(),
+            git_directory: None,
         }
     }
 }
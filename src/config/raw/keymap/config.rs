@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use toml::Spanned;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct CommandKeymap {
@@ -15,7 +16,7 @@ pub struct CommandKeymap {
 #[derive(Clone, Debug, Deserialize)]
 pub struct AppModeKeyMapping {
     #[serde(default)]
-    pub keymap: Vec<CommandKeymap>,
+    pub keymap: Vec<Spanned<CommandKeymap>>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
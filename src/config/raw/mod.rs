@@ -1,5 +1,6 @@
 pub mod app;
 pub mod bookmarks;
+pub mod folder_settings;
 pub mod icon;
 pub mod keymap;
 pub mod mimetype;
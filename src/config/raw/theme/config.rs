@@ -4,6 +4,10 @@ use std::collections::HashMap;
 use super::style::AppStyleRaw;
 use super::tab::TabThemeRaw;
 
+fn default_syntax_theme() -> String {
+    "base16-ocean.dark".to_string()
+}
+
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct AppThemeRaw {
     #[serde(default)]
@@ -28,4 +32,8 @@ pub struct AppThemeRaw {
     pub ext: HashMap<String, AppStyleRaw>,
     #[serde(default)]
     pub lscolors_enabled: bool,
+    #[serde(default = "default_syntax_theme")]
+    pub syntax_theme: String,
+    #[serde(default)]
+    pub selection_prefix: String,
 }
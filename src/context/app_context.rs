@@ -2,17 +2,23 @@ use std::collections::HashSet;
 use std::process;
 use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
 use crate::commands::quit::QuitAction;
+use crate::commands::reload_config;
 use crate::config::clean::app::AppConfig;
+use crate::config::clean::keymap::KeymapEdit;
 use crate::config::raw::app::display::preview::PreviewProtocol;
 use crate::context::{
     CommandLineContext, LocalStateContext, MatchContext, MessageQueue, PreviewContext, TabContext,
     UiContext, WorkerContext,
 };
 use crate::event::{AppEvent, Events};
+use crate::preview::preview_cache::PreviewCache;
 use crate::preview::preview_file::PreviewFileState;
 use crate::ui::{views, AppBackend, PreviewArea};
+use crate::util::dir_stats::DirStats;
+use crate::util::duplicates::DuplicateGroup;
 use crate::Args;
 use notify::{RecursiveMode, Watcher};
 use ratatui_image::picker::Picker;
@@ -32,6 +38,8 @@ pub struct AppContext {
     local_state: Option<LocalStateContext>,
     // context related to searching
     search_context: Option<MatchContext>,
+    // prefix typed in the last `jump_to_char`
+    jump_prefix: Option<String>,
     // message queue for displaying messages
     message_queue: MessageQueue,
     // context related to io workers
@@ -49,6 +57,12 @@ pub struct AppContext {
     // the last preview area (or None if now preview shown) to check if a preview hook script needs
     // to be called
     preview_area: Option<PreviewArea>,
+    // keymap changes made by `map` and `unmap`, waiting to be applied by the main loop
+    keymap_edits: Vec<KeymapEdit>,
+    // results of the last `find_duplicates` and the directory it searched
+    duplicates: Option<(path::PathBuf, Vec<DuplicateGroup>)>,
+    // results of the last `stats` and the directory it looked at
+    dir_stats: Option<(path::PathBuf, DirStats)>,
 }
 
 impl AppContext {
@@ -78,8 +92,7 @@ impl AppContext {
         let events = Events::new();
         let event_tx = events.event_tx.clone();
 
-        let mut commandline_context = CommandLineContext::new();
-        let _ = commandline_context.history_mut().set_max_len(20);
+        let commandline_context = CommandLineContext::new(config.prompt_history_size);
 
         let event_tx_for_fs_notification = event_tx.clone();
         let watcher = notify::recommended_watcher(move |res| {
@@ -91,6 +104,18 @@ impl AppContext {
         let watched_paths = HashSet::with_capacity(3);
 
         let preview_script = config.preview_options_ref().preview_script.clone();
+        let hex_preview = config.preview_options_ref().hex_preview;
+        // highlighting reads the whole file, up to the preview size limit
+        let syntax_highlight = Some(config.preview_options_ref().max_preview_size)
+            .filter(|_| config.preview_options_ref().syntax_highlight);
+        let preview_cache = if config.preview_options_ref().preview_cache {
+            PreviewCache::new(
+                config.preview_options_ref().preview_cache_size,
+                config.preview_options_ref().preview_cache_ttl,
+            )
+        } else {
+            None
+        };
 
         Self {
             quit: QuitAction::DoNot,
@@ -99,15 +124,26 @@ impl AppContext {
             tab_context: TabContext::new(),
             local_state: None,
             search_context: None,
+            jump_prefix: None,
             message_queue: MessageQueue::new(),
             worker_context: WorkerContext::new(event_tx.clone()),
-            preview_context: PreviewContext::new(picker, preview_script, event_tx),
+            preview_context: PreviewContext::new(
+                picker,
+                preview_script,
+                hex_preview,
+                syntax_highlight,
+                preview_cache,
+                event_tx,
+            ),
             ui_context: UiContext { layout: vec![] },
             commandline_context,
             config,
             watcher,
             watched_paths,
             preview_area: None,
+            keymap_edits: Vec::new(),
+            duplicates: None,
+            dir_stats: None,
         }
     }
 
@@ -196,21 +232,36 @@ impl AppContext {
         }
     }
 
-    /// Updates the file system supervision with the currently shown directories.
+    /// Updates the file system supervision with the currently shown directories
+    /// and, with `watch_preview`, the file under the cursor.
     pub fn update_watcher(&mut self) {
         // collect the paths that shall be watched...
-        let mut new_paths_to_watch: HashSet<path::PathBuf> = HashSet::with_capacity(3);
+        let mut new_paths_to_watch: HashSet<path::PathBuf> = HashSet::with_capacity(4);
 
         let curr_tab_ref = self.tab_context_ref().curr_tab_ref();
 
-        let watched_lists = [
-            curr_tab_ref.parent_list_ref(),
-            curr_tab_ref.curr_list_ref(),
-            curr_tab_ref.child_list_ref(),
-        ];
+        if self.config_ref().watch_files {
+            let watched_lists = [
+                curr_tab_ref.parent_list_ref(),
+                curr_tab_ref.curr_list_ref(),
+                curr_tab_ref.child_list_ref(),
+            ];
 
-        for list in watched_lists.iter().flatten() {
-            new_paths_to_watch.insert(list.file_path().to_path_buf());
+            for list in watched_lists.iter().flatten() {
+                new_paths_to_watch.insert(list.file_path().to_path_buf());
+            }
+        }
+        if self.config_ref().watch_config {
+            // editors often replace the file, so watch the directory containing it
+            let config_dirs = reload_config::config_paths()
+                .into_iter()
+                .filter_map(|p| p.parent().map(path::Path::to_path_buf));
+            new_paths_to_watch.extend(config_dirs);
+        }
+        if self.config_ref().preview_options_ref().watch_preview {
+            if let Some(path) = self.previewed_file() {
+                new_paths_to_watch.insert(path.to_path_buf());
+            }
         }
 
         // remove paths from watcher which don't need to be watched anymore...
@@ -235,6 +286,12 @@ impl AppContext {
     pub fn poll_event(&self) -> Result<AppEvent, mpsc::RecvError> {
         self.events.next()
     }
+    pub fn poll_event_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<AppEvent, mpsc::RecvTimeoutError> {
+        self.events.next_timeout(timeout)
+    }
     pub fn flush_event(&self) {
         self.events.flush();
     }
@@ -271,6 +328,33 @@ impl AppContext {
         self.local_state.take()
     }
 
+    pub fn push_keymap_edit(&mut self, edit: KeymapEdit) {
+        self.keymap_edits.push(edit);
+    }
+    pub fn take_keymap_edits(&mut self) -> Vec<KeymapEdit> {
+        std::mem::take(&mut self.keymap_edits)
+    }
+
+    pub fn duplicates_ref(&self) -> Option<&(path::PathBuf, Vec<DuplicateGroup>)> {
+        self.duplicates.as_ref()
+    }
+    pub fn duplicates_mut(&mut self) -> Option<&mut (path::PathBuf, Vec<DuplicateGroup>)> {
+        self.duplicates.as_mut()
+    }
+    pub fn set_duplicates(&mut self, root: path::PathBuf, groups: Vec<DuplicateGroup>) {
+        self.duplicates = Some((root, groups));
+    }
+
+    pub fn dir_stats_ref(&self) -> Option<&(path::PathBuf, DirStats)> {
+        self.dir_stats.as_ref()
+    }
+    pub fn set_dir_stats(&mut self, root: path::PathBuf, stats: DirStats) {
+        self.dir_stats = Some((root, stats));
+    }
+    pub fn clear_dir_stats(&mut self) {
+        self.dir_stats = None;
+    }
+
     pub fn get_search_context(&self) -> Option<&MatchContext> {
         self.search_context.as_ref()
     }
@@ -278,6 +362,13 @@ impl AppContext {
         self.search_context = Some(context);
     }
 
+    pub fn jump_prefix_ref(&self) -> Option<&str> {
+        self.jump_prefix.as_deref()
+    }
+    pub fn set_jump_prefix(&mut self, prefix: String) {
+        self.jump_prefix = Some(prefix);
+    }
+
     pub fn preview_context_ref(&self) -> &PreviewContext {
         &self.preview_context
     }
@@ -305,6 +396,31 @@ impl AppContext {
     pub fn commandline_context_mut(&mut self) -> &mut CommandLineContext {
         &mut self.commandline_context
     }
+    /// The file under the cursor, if it is not a directory
+    pub fn previewed_file(&self) -> Option<&path::Path> {
+        self.tab_context_ref()
+            .curr_tab_ref()
+            .curr_list_ref()
+            .and_then(|list| list.curr_entry_ref())
+            .filter(|entry| !entry.metadata.is_dir())
+            .map(|entry| entry.file_path())
+    }
+
+    /// Loads the preview of `path` again, the current preview stays visible meanwhile
+    pub fn reload_preview(&mut self, backend: &AppBackend, path: path::PathBuf) {
+        self.preview_context
+            .load_preview_image(self, backend, path.clone());
+        if let Some(PreviewFileState::Success(_)) =
+            self.preview_context_ref().previews_ref().get(&path)
+        {
+            self.preview_context
+                .load_preview_script(self, backend, path);
+        } else {
+            self.preview_context_mut().previews_mut().remove(&path);
+            self.load_preview(backend, path);
+        }
+    }
+
     pub fn load_preview(&mut self, backend: &AppBackend, path: path::PathBuf) {
         // always load image without cache
         self.preview_context_mut().set_image_preview(None);
@@ -1,26 +1,157 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
 use rustyline::history::{History, MemHistory};
 
+/// Prompts keeping a history of their own
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PromptHistory {
+    Command,
+    Search,
+    /// shared by the other prompts, e.g. `open_with`
+    Input,
+}
+
+impl PromptHistory {
+    pub const ALL: [Self; 3] = [Self::Command, Self::Search, Self::Input];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Command => "command",
+            Self::Search => "search",
+            Self::Input => "input",
+        }
+    }
+
+    /// The history for a command line starting with `prefix`
+    pub fn for_input(prefix: &str) -> Self {
+        if prefix.trim_start().starts_with("search") {
+            Self::Search
+        } else {
+            Self::Command
+        }
+    }
+}
+
+impl std::default::Default for PromptHistory {
+    fn default() -> Self {
+        Self::Input
+    }
+}
+
+/// Prompt histories, saved to `$XDG_DATA_HOME/joshuto/history` so they
+/// are available in the next session
 pub struct CommandLineContext {
-    history: MemHistory,
+    histories: HashMap<PromptHistory, MemHistory>,
+    dir: Option<PathBuf>,
 }
 
 impl std::default::Default for CommandLineContext {
     fn default() -> Self {
-        Self {
-            history: MemHistory::new(),
-        }
+        Self::with_dir(None, 100)
     }
 }
 
 impl CommandLineContext {
-    pub fn new() -> Self {
-        Self::default()
+    /// Loads the histories of the previous sessions, keeping at most `max_len` entries each
+    pub fn new(max_len: usize) -> Self {
+        let dir = dirs_next::data_dir().map(|mut dir| {
+            dir.push(env!("CARGO_PKG_NAME"));
+            dir.push("history");
+            dir
+        });
+        Self::with_dir(dir, max_len)
+    }
+
+    pub fn with_dir(dir: Option<PathBuf>, max_len: usize) -> Self {
+        let mut histories = HashMap::new();
+        for kind in PromptHistory::ALL {
+            let mut history = MemHistory::new();
+            let _ = history.set_max_len(max_len);
+            if let Some(content) = history_file(&dir, kind).and_then(|p| fs::read_to_string(p).ok())
+            {
+                for line in content.lines() {
+                    let _ = history.add(line);
+                }
+            }
+            histories.insert(kind, history);
+        }
+        Self { histories, dir }
     }
 
-    pub fn history_ref(&self) -> &dyn History {
-        &self.history
+    pub fn history_ref(&self, kind: PromptHistory) -> &dyn History {
+        &self.histories[&kind]
     }
-    pub fn history_mut(&mut self) -> &mut dyn History {
-        &mut self.history
+    pub fn history_mut(&mut self, kind: PromptHistory) -> &mut dyn History {
+        self.histories.get_mut(&kind).unwrap()
+    }
+
+    /// Adds `line` to the history of `kind` and saves it
+    pub fn add(&mut self, kind: PromptHistory, line: &str) -> io::Result<()> {
+        let _ = self.history_mut(kind).add(line);
+        match history_file(&self.dir, kind) {
+            Some(path) => self.save(kind, &path),
+            None => Ok(()),
+        }
+    }
+
+    fn save(&self, kind: PromptHistory, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let history = &self.histories[&kind];
+        let mut content = String::new();
+        for i in 0..history.len() {
+            content.push_str(&history[i]);
+            content.push('\n');
+        }
+        fs::write(path, content)
+    }
+}
+
+fn history_file(dir: &Option<PathBuf>, kind: PromptHistory) -> Option<PathBuf> {
+    dir.as_ref().map(|dir| dir.join(kind.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // histories of at most 2 entries saved in a temporary directory, then read again
+    fn saved_history(entries: &[(PromptHistory, &str)]) -> (tempfile::TempDir, CommandLineContext) {
+        let dir = tempfile::tempdir().unwrap();
+        let history_dir = dir.path().join("history");
+        let mut context = CommandLineContext::with_dir(Some(history_dir.clone()), 2);
+        for (kind, entry) in entries {
+            context.add(*kind, entry).unwrap();
+        }
+        (dir, CommandLineContext::with_dir(Some(history_dir), 2))
+    }
+
+    #[test]
+    fn history_is_restored_up_to_its_size() {
+        let (_dir, context) = saved_history(&[
+            (PromptHistory::Command, "cd /tmp"),
+            (PromptHistory::Command, "mkdir a"),
+            (PromptHistory::Command, "mkdir b"),
+        ]);
+        let history = context.history_ref(PromptHistory::Command);
+        assert_eq!(history.len(), 2);
+        let entry = history.get(1, rustyline::history::SearchDirection::Forward);
+        assert_eq!(entry.unwrap().unwrap().entry, "mkdir b");
+    }
+
+    #[test]
+    fn each_prompt_has_its_own_history() {
+        let (_dir, context) = saved_history(&[
+            (PromptHistory::Command, "cd /tmp"),
+            (PromptHistory::Search, "search foo"),
+            (PromptHistory::default(), "1"),
+        ]);
+        assert_eq!(context.history_ref(PromptHistory::Command).len(), 1);
+        assert_eq!(context.history_ref(PromptHistory::Search).len(), 1);
+        assert_eq!(context.history_ref(PromptHistory::Input).len(), 1);
     }
 }
@@ -1,21 +1,57 @@
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
-use ratatui::style::{Color, Style};
+use chrono::{DateTime, Local};
+use ratatui::style::{Color, Modifier, Style};
 
+// number of messages kept for the `messages` view
+const MESSAGE_LOG_SIZE: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl MessageLevel {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct Message {
     pub content: String,
     pub style: Style,
+    pub level: MessageLevel,
+    pub timestamp: DateTime<Local>,
 }
 
 impl Message {
-    pub fn new(content: String, style: Style) -> Self {
-        Self { content, style }
+    pub fn new(content: String, style: Style, level: MessageLevel) -> Self {
+        Self {
+            content,
+            style,
+            level,
+            timestamp: Local::now(),
+        }
     }
 }
 
 #[derive(Default)]
 pub struct MessageQueue {
     contents: VecDeque<Message>,
+    // every message pushed so far, oldest first
+    log: VecDeque<Message>,
+    // when the current message was first shown
+    shown_since: Option<Instant>,
 }
 
 impl MessageQueue {
@@ -24,26 +60,75 @@ impl MessageQueue {
     }
 
     pub fn push_info(&mut self, msg: String) {
-        let message = Message::new(msg, Style::default().fg(Color::Yellow));
+        let message = Message::new(msg, Style::default().fg(Color::Yellow), MessageLevel::Info);
         self.push_msg(message);
     }
     pub fn push_success(&mut self, msg: String) {
-        let message = Message::new(msg, Style::default().fg(Color::Green));
+        let message = Message::new(
+            msg,
+            Style::default().fg(Color::Green),
+            MessageLevel::Success,
+        );
+        self.push_msg(message);
+    }
+    pub fn push_warning(&mut self, msg: String) {
+        let message = Message::new(
+            msg,
+            Style::default().fg(Color::Magenta),
+            MessageLevel::Warning,
+        );
         self.push_msg(message);
     }
     pub fn push_error(&mut self, msg: String) {
-        let message = Message::new(msg, Style::default().fg(Color::Red));
+        let message = Message::new(msg, Style::default().fg(Color::Red), MessageLevel::Error);
         self.push_msg(message);
     }
 
+    /// Pushes a highlighted message, for events the user did not wait for
+    pub fn push_notification(&mut self, msg: String, level: MessageLevel) {
+        let color = match level {
+            MessageLevel::Info => Color::Yellow,
+            MessageLevel::Success => Color::Green,
+            MessageLevel::Warning => Color::Magenta,
+            MessageLevel::Error => Color::Red,
+        };
+        let style = Style::default()
+            .fg(color)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        self.push_msg(Message::new(msg, style, level));
+    }
+
     pub fn pop_front(&mut self) -> Option<Message> {
-        self.contents.pop_front()
+        let message = self.contents.pop_front();
+        self.shown_since = if self.contents.is_empty() {
+            None
+        } else {
+            Some(Instant::now())
+        };
+        message
     }
     pub fn current_message(&self) -> Option<&Message> {
         self.contents.front()
     }
 
+    /// How long the current message stays visible, given messages are shown for `timeout`
+    pub fn time_left(&self, timeout: Duration) -> Option<Duration> {
+        self.shown_since
+            .map(|since| timeout.checked_sub(since.elapsed()).unwrap_or_default())
+    }
+
+    pub fn log_ref(&self) -> &VecDeque<Message> {
+        &self.log
+    }
+
     fn push_msg(&mut self, msg: Message) {
+        if self.log.len() >= MESSAGE_LOG_SIZE {
+            self.log.pop_front();
+        }
+        self.log.push_back(msg.clone());
+        if self.contents.is_empty() {
+            self.shown_since = Some(Instant::now());
+        }
         self.contents.push_back(msg);
     }
 }
@@ -4,8 +4,10 @@ use std::path::{self, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::mpsc::{self, Sender};
 use std::sync::Mutex;
+use std::time::Duration;
 use std::{io, thread};
 
+use image::{DynamicImage, ImageFormat};
 use ratatui::layout::Rect;
 use ratatui_image::picker::Picker;
 use ratatui_image::protocol::Protocol;
@@ -14,6 +16,7 @@ use ratatui_image::Resize;
 use crate::config::clean::app::AppConfig;
 use crate::event::{AppEvent, PreviewData};
 use crate::lazy_static;
+use crate::preview::preview_cache::PreviewCache;
 use crate::preview::preview_file::{FilePreview, PreviewFileState};
 use crate::ui::{views, AppBackend};
 use crate::AppContext;
@@ -22,6 +25,12 @@ lazy_static! {
     static ref GUARD: Mutex<()> = Mutex::new(());
 }
 
+// images are scaled down to fit into this many pixels before they are cached
+const THUMBNAIL_SIZE: u32 = 1024;
+
+// changes to a watched file within this interval cause a single preview reload
+const PREVIEW_REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
 type FilePreviewMetadata = HashMap<path::PathBuf, PreviewFileState>;
 
 pub struct PreviewContext {
@@ -30,25 +39,60 @@ pub struct PreviewContext {
     sender_script: Sender<(PathBuf, Rect)>,
     sender_image: Option<Sender<(PathBuf, Rect)>>,
     event_ts: Sender<AppEvent>,
+    cache: Option<PreviewCache>,
+    // a `PreviewChanged` event is on its way
+    refresh_pending: bool,
 }
 
 impl PreviewContext {
     pub fn new(
         picker: Option<Picker>,
         script: Option<PathBuf>,
+        hex_preview: bool,
+        syntax_highlight: Option<u64>,
+        cache: Option<PreviewCache>,
         event_ts: Sender<AppEvent>,
     ) -> PreviewContext {
         let (sender_script, receiver) = mpsc::channel::<(PathBuf, Rect)>();
         let thread_script_event_ts = event_ts.clone();
+        let thread_script_cache = cache.clone();
         thread::spawn(move || {
             for (path, rect) in receiver {
-                if let Some(ref script) = script {
-                    PreviewContext::spawn_command(
-                        path.clone(),
-                        script.to_path_buf(),
+                let res = script.as_ref().map(|script| {
+                    PreviewContext::run_script(
+                        path.as_path(),
+                        script,
                         rect,
-                        thread_script_event_ts.clone(),
-                    );
+                        thread_script_cache.as_ref(),
+                    )
+                });
+                // syntax highlighting is only used when the script has nothing to show
+                let res = match res {
+                    Some(Ok(preview)) if !preview.output.trim().is_empty() => Some(Ok(preview)),
+                    res => syntax_highlight
+                        .and_then(|max_size| {
+                            FilePreview::highlighted(path.as_path(), max_size)
+                                .ok()
+                                .flatten()
+                        })
+                        .map(Ok)
+                        .or(res),
+                };
+                // fall back to a hexdump for binary files the script can't handle
+                let res = match res {
+                    Some(Ok(preview)) => Some(Ok(preview)),
+                    res if hex_preview => FilePreview::hexdump(path.as_path())
+                        .ok()
+                        .flatten()
+                        .map(Ok)
+                        .or(res),
+                    res => res,
+                };
+                if let Some(res) = res {
+                    let _ = thread_script_event_ts.send(AppEvent::PreviewFile {
+                        path,
+                        res: res.map(|preview| PreviewData::Script(Box::new(preview))),
+                    });
                 }
             }
         });
@@ -56,6 +100,7 @@ impl PreviewContext {
         let (sender_image, receiver) = mpsc::channel::<(PathBuf, Rect)>();
         let sender_image = picker.map(|mut picker| {
             let thread_image_event_ts = event_ts.clone();
+            let thread_image_cache = cache.clone();
             thread::spawn(move || loop {
                 // Get last, or block for next.
                 if let Some((path, rect)) = receiver
@@ -63,8 +108,7 @@ impl PreviewContext {
                     .last()
                     .or_else(|| receiver.iter().next())
                 {
-                    let proto = image::io::Reader::open(path.as_path())
-                        .and_then(|reader| reader.decode().map_err(Self::map_io_err))
+                    let proto = Self::load_image(path.as_path(), thread_image_cache.as_ref())
                         .and_then(|dyn_img| {
                             picker
                                 .new_protocol(dyn_img, rect, Resize::Fit)
@@ -93,47 +137,86 @@ impl PreviewContext {
             sender_script,
             sender_image,
             event_ts,
+            cache,
+            refresh_pending: false,
         }
     }
 
-    fn spawn_command(
-        path: PathBuf,
-        script: PathBuf,
+    fn run_script(
+        path: &path::Path,
+        script: &path::Path,
         rect: Rect,
-        thread_event_ts: Sender<AppEvent>,
-    ) {
+        cache: Option<&PreviewCache>,
+    ) -> io::Result<FilePreview> {
+        let cache_key = cache.and_then(|_| {
+            PreviewCache::key(
+                path,
+                Some(script),
+                "script",
+                &format!("{}x{}", rect.width, rect.height),
+            )
+        });
+        if let (Some(cache), Some(key)) = (cache, cache_key.as_ref()) {
+            if let Some(data) = cache.get(key) {
+                return Ok(FilePreview::from_string(
+                    String::from_utf8_lossy(&data).to_string(),
+                ));
+            }
+        }
+
         let output = Command::new(script)
             .stdout(Stdio::piped())
             .stderr(Stdio::null())
             .arg("--path")
-            .arg(path.as_path())
+            .arg(path)
             .arg("--preview-width")
             .arg(rect.width.to_string())
             .arg("--preview-height")
             .arg(rect.height.to_string())
-            .output();
+            .output()
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err}")))?;
+        if !output.status.success() {
+            return Err(io::Error::new(io::ErrorKind::Other, "nonzero status"));
+        }
+        let preview = FilePreview::from(output);
+        if let (Some(cache), Some(key)) = (cache, cache_key.as_ref()) {
+            let _ = cache.put(key, preview.output.as_bytes());
+        }
+        Ok(preview)
+    }
 
-        let res = match output {
-            Ok(output) => {
-                if output.status.success() {
-                    let preview = FilePreview::from(output);
-                    AppEvent::PreviewFile {
-                        path,
-                        res: Ok(PreviewData::Script(Box::new(preview))),
-                    }
-                } else {
-                    AppEvent::PreviewFile {
-                        path,
-                        res: Err(io::Error::new(io::ErrorKind::Other, "nonzero status")),
-                    }
-                }
+    /// Decodes the image at `path`, large images are scaled down and cached
+    fn load_image(path: &path::Path, cache: Option<&PreviewCache>) -> io::Result<DynamicImage> {
+        let cache_key = cache.and_then(|_| PreviewCache::key(path, None, "thumbnail", ""));
+        if let (Some(cache), Some(key)) = (cache, cache_key.as_ref()) {
+            if let Some(img) = cache
+                .get(key)
+                .and_then(|data| image::load_from_memory(&data).ok())
+            {
+                return Ok(img);
             }
-            Err(err) => AppEvent::PreviewFile {
-                path,
-                res: Err(io::Error::new(io::ErrorKind::Other, format!("{err}"))),
-            },
-        };
-        let _ = thread_event_ts.send(res);
+        }
+
+        let img = image::io::Reader::open(path)?
+            .decode()
+            .map_err(Self::map_io_err)?;
+        if let (Some(cache), Some(key)) = (cache, cache_key.as_ref()) {
+            let thumbnail = if img.width() > THUMBNAIL_SIZE || img.height() > THUMBNAIL_SIZE {
+                img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+            } else {
+                img
+            };
+            let mut data = io::Cursor::new(Vec::new());
+            if thumbnail.write_to(&mut data, ImageFormat::Png).is_ok() {
+                let _ = cache.put(key, data.get_ref());
+            }
+            return Ok(thumbnail);
+        }
+        Ok(img)
+    }
+
+    pub fn cache_ref(&self) -> Option<&PreviewCache> {
+        self.cache.as_ref()
     }
 
     pub fn previews_ref(&self) -> &FilePreviewMetadata {
@@ -152,6 +235,24 @@ impl PreviewContext {
         self.image_preview = preview;
     }
 
+    /// Sends a `PreviewChanged` event for `path` after a short delay,
+    /// unless one is already pending
+    pub fn schedule_refresh(&mut self, path: PathBuf) {
+        if self.refresh_pending {
+            return;
+        }
+        self.refresh_pending = true;
+        let event_ts = self.event_ts.clone();
+        thread::spawn(move || {
+            thread::sleep(PREVIEW_REFRESH_INTERVAL);
+            let _ = event_ts.send(AppEvent::PreviewChanged { path });
+        });
+    }
+
+    pub fn finish_refresh(&mut self) {
+        self.refresh_pending = false;
+    }
+
     pub fn load_preview_script(
         &self,
         context: &AppContext,
@@ -12,10 +12,13 @@ pub struct WorkerContext {
     child_pool: HashMap<u32, thread::JoinHandle<()>>,
     // to send info
     event_tx: mpsc::Sender<AppEvent>,
-    // queue of IO workers
+    // queue of IO workers, started in order
     worker_queue: VecDeque<IoWorkerThread>,
-    // current worker
-    worker: Option<IoWorkerObserver>,
+    // running workers by id, oldest first
+    workers: Vec<(usize, IoWorkerObserver)>,
+    next_id: usize,
+    // progress of background jobs other than IO workers by job, e.g. checksums
+    progress_msgs: Vec<(&'static str, String)>,
 }
 
 impl WorkerContext {
@@ -24,7 +27,9 @@ impl WorkerContext {
             child_pool: HashMap::new(),
             event_tx,
             worker_queue: VecDeque::new(),
-            worker: None,
+            workers: Vec::new(),
+            next_id: 0,
+            progress_msgs: Vec::new(),
         }
     }
     pub fn clone_event_tx(&self) -> mpsc::Sender<AppEvent> {
@@ -37,69 +42,117 @@ impl WorkerContext {
         let _ = self.event_tx.send(AppEvent::IoWorkerCreate);
     }
     pub fn is_busy(&self) -> bool {
-        self.worker.is_some()
+        !self.workers.is_empty()
     }
     pub fn is_empty(&self) -> bool {
         self.worker_queue.is_empty()
     }
 
+    /// Drops the workers which have not started yet
+    pub fn clear_queue(&mut self) {
+        self.worker_queue.clear();
+    }
+
     pub fn iter(&self) -> Iter<IoWorkerThread> {
         self.worker_queue.iter()
     }
-    pub fn worker_ref(&self) -> Option<&IoWorkerObserver> {
-        self.worker.as_ref()
+    /// Running workers, oldest first
+    pub fn workers_iter(&self) -> impl Iterator<Item = &IoWorkerObserver> {
+        self.workers.iter().map(|(_, worker)| worker)
+    }
+    pub fn running_count(&self) -> usize {
+        self.workers.len()
     }
 
-    pub fn set_progress(&mut self, res: FileOperationProgress) {
-        if let Some(s) = self.worker.as_mut() {
+    fn worker_mut(&mut self, id: usize) -> Option<&mut IoWorkerObserver> {
+        self.workers
+            .iter_mut()
+            .find(|(i, _)| *i == id)
+            .map(|(_, worker)| worker)
+    }
+
+    pub fn set_progress(&mut self, id: usize, res: FileOperationProgress) {
+        if let Some(s) = self.worker_mut(id) {
             s.set_progress(res);
         }
     }
 
+    /// Progress of the oldest running worker, or of another background job
     pub fn get_msg(&self) -> Option<&str> {
-        let worker = self.worker.as_ref()?;
-        Some(worker.get_msg())
+        match self.workers.first() {
+            Some((_, worker)) => Some(worker.get_msg()),
+            None => self.progress_msgs.first().map(|(_, msg)| msg.as_str()),
+        }
     }
-    pub fn update_msg(&mut self) {
-        if let Some(s) = self.worker.as_mut() {
+    /// Shows the progress of the background job `job`, replacing its previous message
+    pub fn set_progress_msg(&mut self, job: &'static str, msg: String) {
+        match self.progress_msgs.iter_mut().find(|(j, _)| *j == job) {
+            Some((_, old_msg)) => *old_msg = msg,
+            None => self.progress_msgs.push((job, msg)),
+        }
+    }
+    /// Removes the progress of `job` once it is done, other jobs keep theirs
+    pub fn clear_progress_msg(&mut self, job: &'static str) {
+        self.progress_msgs.retain(|(j, _)| *j != job);
+    }
+    pub fn update_msg(&mut self, id: usize) {
+        if let Some(s) = self.worker_mut(id) {
             s.update_msg();
         }
     }
 
-    pub fn start_next_job(&mut self) {
+    /// Starts queued workers until `max_workers` are running,
+    /// each using up to `threads` threads
+    pub fn start_next_jobs(&mut self, max_workers: usize, threads: usize) {
+        while self.workers.len() < max_workers.max(1) && !self.worker_queue.is_empty() {
+            self.start_next_job(threads);
+        }
+    }
+
+    fn start_next_job(&mut self, threads: usize) {
         let tx = self.clone_event_tx();
+        let id = self.next_id;
 
         if let Some(worker) = self.worker_queue.pop_front() {
+            self.next_id += 1;
+            log::info!(
+                "{} {} items to {} ({})",
+                worker.kind().actioning_str(),
+                worker.paths.len(),
+                worker.dest.display(),
+                worker.options
+            );
             let src = worker.paths[0].parent().unwrap().to_path_buf();
             let dest = worker.dest.clone();
             let handle = thread::spawn(move || {
                 let (wtx, wrx) = mpsc::channel();
                 // start worker
-                let worker_handle = thread::spawn(move || worker.start(wtx));
+                let worker_handle = thread::spawn(move || worker.start(wtx, threads));
                 // relay worker info to event loop
                 while let Ok(progress) = wrx.recv() {
-                    let _ = tx.send(AppEvent::FileOperationProgress(progress));
+                    let _ = tx.send(AppEvent::FileOperationProgress(id, progress));
                 }
                 let result = worker_handle.join();
 
                 match result {
                     Ok(res) => {
-                        let _ = tx.send(AppEvent::IoWorkerResult(res));
+                        let _ = tx.send(AppEvent::IoWorkerResult(id, res));
                     }
                     Err(_) => {
                         let err =
                             AppError::new(AppErrorKind::UnknownError, "Sending Error".to_string());
-                        let _ = tx.send(AppEvent::IoWorkerResult(Err(err)));
+                        let _ = tx.send(AppEvent::IoWorkerResult(id, Err(err)));
                     }
                 }
             });
             let observer = IoWorkerObserver::new(handle, src, dest);
-            self.worker = Some(observer);
+            self.workers.push((id, observer));
         }
     }
 
-    pub fn remove_worker(&mut self) -> Option<IoWorkerObserver> {
-        self.worker.take()
+    pub fn remove_worker(&mut self, id: usize) -> Option<IoWorkerObserver> {
+        let index = self.workers.iter().position(|(i, _)| *i == id)?;
+        Some(self.workers.remove(index).1)
     }
 
     pub fn push_child(&mut self, child_id: u32, handle: thread::JoinHandle<()>) {
@@ -22,7 +22,6 @@ impl AppError {
         Err(Self::error(cause))
     }
 
-    #[allow(dead_code)]
     pub fn kind(&self) -> &AppErrorKind {
         &self._kind
     }
@@ -3,6 +3,7 @@ use std::io;
 use std::path;
 use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
 use ratatui_image::protocol::Protocol;
 use signal_hook::consts::signal;
@@ -15,9 +16,14 @@ use termion::input::TermRead;
 use uuid::Uuid;
 
 use crate::error::AppResult;
-use crate::fs::JoshutoDirList;
+use crate::fs::{JoshutoDirList, JoshutoMetadata};
 use crate::io::FileOperationProgress;
 use crate::preview::preview_file::FilePreview;
+use crate::util::checksum::{ChecksumAlgorithm, ChecksumEntry, ChecksumOutput};
+use crate::util::dir_compare::DirDifference;
+use crate::util::dir_stats::DirStats;
+use crate::util::duplicates::DuplicateGroup;
+use crate::util::keyparse;
 
 pub enum PreviewData {
     Script(Box<FilePreview>),
@@ -38,14 +44,54 @@ pub enum AppEvent {
     // User input events
     Termion(Event),
 
-    // background IO worker events
+    // background IO worker events, identified by the id given by `WorkerContext`
     IoWorkerCreate,
-    FileOperationProgress(FileOperationProgress),
-    IoWorkerResult(AppResult<FileOperationProgress>),
+    FileOperationProgress(usize, FileOperationProgress),
+    IoWorkerResult(usize, AppResult<FileOperationProgress>),
+
+    // checksum thread events, see `commands::checksum`
+    ChecksumProgress {
+        processed: usize,
+        total: usize,
+    },
+    ChecksumResult {
+        algorithm: ChecksumAlgorithm,
+        entries: Vec<ChecksumEntry>,
+        output: ChecksumOutput,
+    },
+
+    // duplicate finder thread events, see `commands::find_duplicates`
+    DuplicatesProgress {
+        processed: usize,
+        total: usize,
+    },
+    DuplicatesResult {
+        root: path::PathBuf,
+        res: io::Result<Vec<DuplicateGroup>>,
+    },
+
+    // directory comparison thread result, see `commands::compare_tabs`
+    DirCompareResult {
+        left: path::PathBuf,
+        right: path::PathBuf,
+        res: io::Result<Vec<DirDifference>>,
+    },
+
+    // directory statistics thread events, see `commands::dir_stats`
+    DirStatsProgress {
+        scanned: usize,
+    },
+    DirStatsResult {
+        root: path::PathBuf,
+        res: io::Result<DirStats>,
+    },
 
     // forked process events
     ChildProcessComplete(u32),
 
+    // commands sent by other processes through the ipc socket
+    Remote(String),
+
     // preview thread events
     PreviewDir {
         id: Uuid,
@@ -56,6 +102,15 @@ pub enum AppEvent {
         path: path::PathBuf,
         res: io::Result<PreviewData>,
     },
+    // the previewed file changed, sent once changes settled, see `PreviewContext::schedule_refresh`
+    PreviewChanged {
+        path: path::PathBuf,
+    },
+    // metadata of entries listed with placeholders, see `fs::metadata_loader`
+    DirMetadata {
+        path: path::PathBuf,
+        entries: Vec<(path::PathBuf, Option<JoshutoMetadata>)>,
+    },
     // terminal size change events
     Signal(i32),
     // filesystem change events
@@ -86,6 +141,11 @@ impl Events {
         Ok(event)
     }
 
+    pub fn next_timeout(&self, timeout: Duration) -> Result<AppEvent, mpsc::RecvTimeoutError> {
+        let event = self.event_rx.recv_timeout(timeout)?;
+        Ok(event)
+    }
+
     pub fn flush(&self) {
         loop {
             if self.input_tx.send(()).is_ok() {
@@ -106,7 +166,12 @@ impl std::default::Default for Events {
         // signal thread
         let event_tx2 = event_tx.clone();
         let _ = thread::spawn(move || {
-            let sigs = vec![signal::SIGWINCH];
+            let sigs = vec![
+                signal::SIGWINCH,
+                signal::SIGTSTP,
+                signal::SIGTERM,
+                signal::SIGHUP,
+            ];
             let mut signals = SignalsInfo::<SignalOnly>::new(sigs).unwrap();
             for signal in &mut signals {
                 if let Err(e) = event_tx2.send(AppEvent::Signal(signal)) {
@@ -125,6 +190,7 @@ impl std::default::Default for Events {
             loop {
                 let _ = input_rx.recv();
                 if let Some(Ok(event)) = events.next() {
+                    let event = keyparse::normalize_event(event);
                     let _ = event_tx2.send(AppEvent::Termion(event));
                 }
             }
@@ -0,0 +1,170 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::event::AppEvent;
+use crate::USERNAME;
+
+pub const SOCKET_ENV: &str = "JOSHUTO_SOCKET";
+
+/// Listens on a unix socket and forwards every line received as a command
+/// to the main event loop.
+///
+/// The socket file is removed once the server is dropped.
+pub struct IpcServer {
+    socket_path: path::PathBuf,
+}
+
+impl IpcServer {
+    pub fn start(event_tx: mpsc::Sender<AppEvent>) -> io::Result<Self> {
+        let socket_path = default_socket_path()?;
+        // a stale socket left behind by a crashed instance with the same pid
+        let _ = fs::remove_file(&socket_path);
+
+        let listener = UnixListener::bind(&socket_path)?;
+        let _ = thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let event_tx = event_tx.clone();
+                let _ = thread::spawn(move || {
+                    let reader = BufReader::new(stream);
+                    for line in reader.lines() {
+                        let line = match line {
+                            Ok(line) => line,
+                            Err(_) => return,
+                        };
+                        let line = line.trim();
+                        if line.is_empty() {
+                            continue;
+                        }
+                        if event_tx.send(AppEvent::Remote(line.to_string())).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        // let child processes know how to talk back to us
+        std::env::set_var(SOCKET_ENV, &socket_path);
+        Ok(Self { socket_path })
+    }
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
+
+fn default_socket_path() -> io::Result<path::PathBuf> {
+    let dir = match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => path::PathBuf::from(dir),
+        None => {
+            let dir = std::env::temp_dir().join(format!("joshuto-{}", USERNAME.as_str()));
+            create_private_dir(&dir)?;
+            dir
+        }
+    };
+    Ok(dir.join(format!("joshuto-{}.sock", std::process::id())))
+}
+
+/// Creates a directory only the current user can access.
+///
+/// The temporary directory is shared with other users, who could have created
+/// the directory beforehand to read or replace the socket, so an existing
+/// directory is only used if it is owned by the current user and private.
+fn create_private_dir(dir: &path::Path) -> io::Result<()> {
+    match fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    // not following symlinks, which could point anywhere
+    let metadata = fs::symlink_metadata(dir)?;
+    if !metadata.is_dir()
+        || metadata.uid() != nix::unistd::getuid().as_raw()
+        || metadata.mode() & 0o077 != 0
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{}: not a directory private to the current user",
+                dir.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Sends a command to a running instance listening on `socket_path`.
+pub fn send_command(socket_path: &path::Path, command: &str) -> io::Result<()> {
+    let mut stream = UnixStream::connect(socket_path)?;
+    writeln!(stream, "{}", command)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_dir_is_only_accessible_by_the_user() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("ipc");
+        create_private_dir(&dir).unwrap();
+        assert_eq!(fs::metadata(&dir).unwrap().mode() & 0o777, 0o700);
+    }
+
+    #[test]
+    fn private_dir_is_reused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("ipc");
+        create_private_dir(&dir).unwrap();
+        create_private_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn directories_accessible_by_others_are_refused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("ipc");
+        create_private_dir(&dir).unwrap();
+        fs::set_permissions(&dir, std::os::unix::fs::PermissionsExt::from_mode(0o777)).unwrap();
+        assert!(create_private_dir(&dir).is_err());
+    }
+
+    #[test]
+    fn commands_are_received_over_the_socket() {
+        let (event_tx, event_rx) = mpsc::channel();
+        let server = IpcServer::start(event_tx).unwrap();
+        let socket_path = server.socket_path.clone();
+        assert_eq!(
+            std::env::var_os(SOCKET_ENV),
+            Some(socket_path.clone().into_os_string())
+        );
+
+        send_command(&socket_path, "  cd /tmp  ").unwrap();
+        send_command(&socket_path, "").unwrap();
+        send_command(&socket_path, "toggle_hidden").unwrap();
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            match event_rx.recv_timeout(std::time::Duration::from_secs(5)) {
+                Ok(AppEvent::Remote(command)) => received.push(command),
+                _ => panic!("command not received"),
+            }
+        }
+        // each connection is served by its own thread
+        received.sort();
+        assert_eq!(received, vec!["cd /tmp", "toggle_hidden"]);
+        assert!(event_rx
+            .recv_timeout(std::time::Duration::from_millis(100))
+            .is_err());
+
+        drop(server);
+        assert!(!socket_path.exists());
+        assert!(send_command(&socket_path, "cd /tmp").is_err());
+    }
+}
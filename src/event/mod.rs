@@ -1,4 +1,5 @@
 pub mod app_event;
+pub mod ipc;
 pub mod process_event;
 
 pub use self::app_event::*;
@@ -1,28 +1,34 @@
 use std::io;
 use std::path;
+use std::str::FromStr;
 
-use notify;
+use notify::event::{AccessKind, AccessMode};
+use notify::EventKind;
 use ratatui::layout::{Constraint, Direction, Layout};
 use signal_hook::consts::signal;
 use termion::event::{Event, Key, MouseButton, MouseEvent};
 use uuid::Uuid;
 
-use crate::commands::{cursor_move, parent_cursor_move, reload};
+use crate::commands::quit::QuitAction;
+use crate::commands::{
+    checksum, cursor_move, dir_stats, find_duplicates, parent_cursor_move, reload, reload_config,
+};
 use crate::config::clean::keymap::AppKeyMapping;
 use crate::config::clean::keymap::KeyMapping;
-use crate::context::AppContext;
+use crate::context::{AppContext, MessageLevel};
 use crate::error::AppResult;
 use crate::event::AppEvent;
 use crate::event::PreviewData;
-use crate::fs::JoshutoDirList;
+use crate::fs::{JoshutoDirList, JoshutoMetadata};
 use crate::history::DirectoryHistory;
 use crate::io::FileOperationProgress;
 use crate::key_command::{AppExecute, Command, CommandKeybind};
 use crate::preview::preview_dir::PreviewDirState;
 use crate::preview::preview_file::PreviewFileState;
+use crate::run;
 use crate::ui;
 use crate::ui::views::TuiCommandMenu;
-use crate::util::format;
+use crate::util::{format, notification};
 
 pub fn poll_event_until_simple_keybind<'a>(
     backend: &mut ui::AppBackend,
@@ -53,20 +59,75 @@ pub fn poll_event_until_simple_keybind<'a>(
                     }
                     context.flush_event();
                 }
-                event => process_noninteractive(event, context),
+                event => {
+                    if process_in_modal(event, context, backend) {
+                        return None;
+                    }
+                }
             }
         }
     }
 }
 
+/// Processes the events a view waiting for input (e.g. `show_help`) leaves alone,
+/// signals the way the main loop does. Returns whether the view should close
+/// because joshuto is quitting, e.g. after SIGTERM
+pub fn process_in_modal(
+    event: AppEvent,
+    context: &mut AppContext,
+    backend: &mut ui::AppBackend,
+) -> bool {
+    match event {
+        AppEvent::Signal(signal) => run::process_signal(context, backend, signal),
+        event => process_noninteractive(event, context),
+    }
+    context.quit != QuitAction::DoNot
+}
+
 pub fn process_noninteractive(event: AppEvent, context: &mut AppContext) {
     match event {
         AppEvent::IoWorkerCreate => process_new_worker(context),
-        AppEvent::FileOperationProgress(res) => process_worker_progress(context, res),
-        AppEvent::IoWorkerResult(res) => process_finished_worker(context, res),
+        AppEvent::FileOperationProgress(id, res) => process_worker_progress(context, id, res),
+        AppEvent::IoWorkerResult(id, res) => process_finished_worker(context, id, res),
         AppEvent::PreviewDir { id, path, res } => process_dir_preview(context, id, path, *res),
         AppEvent::PreviewFile { path, res } => process_file_preview(context, path, res),
+        AppEvent::DirMetadata { path, entries } => process_dir_metadata(context, path, entries),
+        // reloading needs the backend, outside of the main loop the change is dropped
+        AppEvent::PreviewChanged { .. } => context.preview_context_mut().finish_refresh(),
+        AppEvent::ChecksumProgress { processed, total } => {
+            let msg = format!("computing checksums ({}/{})", processed, total);
+            context
+                .worker_context_mut()
+                .set_progress_msg(checksum::PROGRESS_JOB, msg);
+        }
+        AppEvent::DuplicatesProgress { processed, total } => {
+            let msg = format!("finding duplicates ({}/{})", processed, total);
+            context
+                .worker_context_mut()
+                .set_progress_msg(find_duplicates::PROGRESS_JOB, msg);
+        }
+        AppEvent::DuplicatesResult { root, res } => {
+            find_duplicates::process_duplicates_result(context, root, res)
+        }
+        AppEvent::DirStatsProgress { scanned } => {
+            let msg = format!("computing statistics ({} files)", scanned);
+            context
+                .worker_context_mut()
+                .set_progress_msg(dir_stats::PROGRESS_JOB, msg);
+        }
+        AppEvent::DirStatsResult { root, res } => {
+            dir_stats::process_dir_stats_result(context, root, res)
+        }
+        AppEvent::ChecksumResult {
+            algorithm,
+            entries,
+            output,
+        } => checksum::process_checksum_result(context, algorithm, entries, output),
         AppEvent::Signal(signal::SIGWINCH) => {}
+        // terminated or the terminal went away, leave through the regular exit path
+        AppEvent::Signal(signal @ (signal::SIGTERM | signal::SIGHUP)) => {
+            context.quit = QuitAction::Terminate(signal);
+        }
         AppEvent::Filesystem(e) => process_filesystem_event(e, context),
         AppEvent::ChildProcessComplete(child_id) => {
             context.worker_context_mut().join_child(child_id);
@@ -75,25 +136,63 @@ pub fn process_noninteractive(event: AppEvent, context: &mut AppContext) {
     }
 }
 
-fn process_filesystem_event(_event: notify::Event, context: &mut AppContext) {
+fn process_filesystem_event(event: notify::Event, context: &mut AppContext) {
     let _ = reload::soft_reload_curr_tab(context);
+
+    if context.config_ref().watch_config && !matches!(event.kind, EventKind::Access(_)) {
+        let config_paths = reload_config::config_paths();
+        if event.paths.iter().any(|p| config_paths.contains(p)) {
+            if let Err(e) = reload_config::reload_config(context) {
+                context.message_queue_mut().push_error(e.to_string());
+            }
+        }
+    }
+
+    if !context.config_ref().preview_options_ref().watch_preview {
+        return;
+    }
+    let content_changed = matches!(
+        event.kind,
+        EventKind::Create(_)
+            | EventKind::Modify(_)
+            | EventKind::Access(AccessKind::Close(AccessMode::Write))
+    );
+    if !content_changed {
+        return;
+    }
+    let changed_path = context
+        .previewed_file()
+        .filter(|path| event.paths.iter().any(|p| p == path))
+        .map(|path| path.to_path_buf());
+    if let Some(path) = changed_path {
+        context.preview_context_mut().schedule_refresh(path);
+    }
 }
 
 pub fn process_new_worker(context: &mut AppContext) {
-    if !context.worker_context_ref().is_busy() && !context.worker_context_ref().is_empty() {
-        context.worker_context_mut().start_next_job();
-    }
+    let max_tasks = context.config_ref().max_concurrent_tasks;
+    let threads = context.config_ref().task_threads;
+    context
+        .worker_context_mut()
+        .start_next_jobs(max_tasks, threads);
 }
 
-pub fn process_worker_progress(context: &mut AppContext, res: FileOperationProgress) {
+pub fn process_worker_progress(context: &mut AppContext, id: usize, res: FileOperationProgress) {
     let worker_context = context.worker_context_mut();
-    worker_context.set_progress(res);
-    worker_context.update_msg();
+    worker_context.set_progress(id, res);
+    worker_context.update_msg(id);
 }
 
-pub fn process_finished_worker(context: &mut AppContext, res: AppResult<FileOperationProgress>) {
+pub fn process_finished_worker(
+    context: &mut AppContext,
+    id: usize,
+    res: AppResult<FileOperationProgress>,
+) {
     let worker_context = context.worker_context_mut();
-    let observer = worker_context.remove_worker().unwrap();
+    let observer = match worker_context.remove_worker(id) {
+        Some(observer) => observer,
+        None => return,
+    };
     let options = context.config_ref().display_options_ref().clone();
     for (_, tab) in context.tab_context_mut().iter_mut() {
         let tab_options = tab.option_ref().clone();
@@ -114,7 +213,7 @@ pub fn process_finished_worker(context: &mut AppContext, res: AppResult<FileOper
     }
 
     observer.join();
-    match res {
+    let (msg, level) = match res {
         Ok(progress) => {
             let op = progress.kind().actioned_str();
             let processed_size = format::file_size_to_string(progress.bytes_processed());
@@ -126,16 +225,34 @@ pub fn process_finished_worker(context: &mut AppContext, res: AppResult<FileOper
                 processed_size,
                 total_size,
             );
-            context.message_queue_mut().push_success(msg);
-        }
-        Err(e) => {
-            let msg = format!("{}", e);
-            context.message_queue_mut().push_error(msg);
+            (msg, MessageLevel::Success)
         }
+        Err(e) => (format!("{}", e), MessageLevel::Error),
+    };
+    match level {
+        MessageLevel::Error => log::error!("file operation failed: {}", msg),
+        _ => log::info!("{}", msg),
     }
+    if context.config_ref().desktop_notifications {
+        notification::send_notification(&msg, level);
+    }
+    context.message_queue_mut().push_notification(msg, level);
+
+    let max_tasks = context.config_ref().max_concurrent_tasks;
+    let threads = context.config_ref().task_threads;
+    context
+        .worker_context_mut()
+        .start_next_jobs(max_tasks, threads);
+}
 
-    if !context.worker_context_ref().is_busy() && !context.worker_context_ref().is_empty() {
-        context.worker_context_mut().start_next_job();
+/// Message shown in place of the directory preview when it can't be read
+fn dir_preview_error(e: &io::Error) -> String {
+    match e.kind() {
+        io::ErrorKind::PermissionDenied => {
+            "Permission denied: cannot list this directory".to_string()
+        }
+        io::ErrorKind::NotFound => "Directory no longer exists".to_string(),
+        _ => format!("Cannot list this directory: {}", e),
     }
 }
 
@@ -161,7 +278,7 @@ pub fn process_dir_preview(
                     tab.history_metadata_mut().insert(
                         path,
                         PreviewDirState::Error {
-                            message: e.to_string(),
+                            message: dir_preview_error(&e),
                         },
                     );
                 }
@@ -171,6 +288,21 @@ pub fn process_dir_preview(
     }
 }
 
+pub fn process_dir_metadata(
+    context: &mut AppContext,
+    path: path::PathBuf,
+    entries: Vec<(path::PathBuf, Option<JoshutoMetadata>)>,
+) {
+    let ui_context = context.ui_context_ref().clone();
+    let options = context.config_ref().display_options_ref().clone();
+    for (_, tab) in context.tab_context_mut().iter_mut() {
+        let tab_options = tab.option_ref().clone();
+        if let Some(dirlist) = tab.history_mut().get_mut(path.as_path()) {
+            dirlist.update_metadata(entries.clone(), &ui_context, &options, &tab_options);
+        }
+    }
+}
+
 pub fn process_file_preview(
     context: &mut AppContext,
     path: path::PathBuf,
@@ -199,6 +331,20 @@ pub fn process_file_preview(
     };
 }
 
+pub fn process_remote(
+    context: &mut AppContext,
+    backend: &mut ui::AppBackend,
+    keymap_t: &AppKeyMapping,
+    line: &str,
+) {
+    let line = context.config_ref().expand_alias(line).into_owned();
+    let res =
+        Command::from_str(&line).and_then(|command| command.execute(context, backend, keymap_t));
+    if let Err(e) = res {
+        context.message_queue_mut().push_error(e.to_string());
+    }
+}
+
 pub fn process_unsupported(
     context: &mut AppContext,
     backend: &mut ui::AppBackend,
@@ -1,11 +1,13 @@
+use std::collections::HashMap;
 use std::slice::{Iter, IterMut};
+use std::time::Instant;
 use std::{io, path};
 
 use crate::config::clean::app::display::tab::TabDisplayOption;
 use crate::config::clean::app::display::DisplayOption;
 use crate::context::UiContext;
 use crate::fs::{JoshutoDirEntry, JoshutoMetadata};
-use crate::history::read_directory;
+use crate::history::{read_directory, sort_contents};
 
 #[derive(Clone, Debug)]
 pub struct JoshutoDirList {
@@ -19,6 +21,9 @@ pub struct JoshutoDirList {
     /// The index in this dir list where visual mode has started or None if not in visual mode
     visual_mode_anchor_index: Option<usize>,
     _need_update: bool,
+    // whether loading the metadata of placeholder entries has been started
+    _metadata_requested: bool,
+    _last_used: Instant,
 }
 
 impl JoshutoDirList {
@@ -38,6 +43,8 @@ impl JoshutoDirList {
             viewport_index,
             visual_mode_anchor_index,
             _need_update: false,
+            _metadata_requested: false,
+            _last_used: Instant::now(),
         }
     }
 
@@ -47,9 +54,8 @@ impl JoshutoDirList {
         tab_options: &TabDisplayOption,
     ) -> io::Result<Self> {
         let filter_func = options.filter_func();
-        let mut contents = read_directory(path.as_path(), filter_func, options, tab_options)?;
-
-        contents.sort_by(|f1, f2| tab_options.sort_options_ref().compare(f1, f2));
+        let contents = read_directory(path.as_path(), filter_func, options, tab_options)?;
+        let contents = sort_contents(path.as_path(), contents, options, tab_options);
 
         let index = if contents.is_empty() { None } else { Some(0) };
         let metadata = JoshutoMetadata::from(&path)?;
@@ -59,6 +65,8 @@ impl JoshutoDirList {
             contents,
             metadata,
             _need_update: false,
+            _metadata_requested: false,
+            _last_used: Instant::now(),
             index,
             viewport_index: if let Some(ix) = index { ix } else { 0 },
             visual_mode_anchor_index: None,
@@ -69,16 +77,6 @@ impl JoshutoDirList {
         self.index
     }
 
-    pub fn get_index_from_name(&self, name: &str) -> Option<usize> {
-        for (index, entry) in self.iter().enumerate() {
-            if name == entry.file_name() {
-                return Some(index);
-            }
-        }
-
-        None
-    }
-
     pub fn get_visual_mode_anchor_index(&self) -> Option<usize> {
         self.visual_mode_anchor_index
     }
@@ -135,18 +133,21 @@ impl JoshutoDirList {
         }
     }
 
+    /// Scroll buffer size, corrected in case of too small terminal
+    fn scroll_offset(height: usize, options: &DisplayOption) -> usize {
+        if height < 4 {
+            0
+        } else if options.scroll_offset() * 2 > height - 1 {
+            height / 2 - 1
+        } else {
+            options.scroll_offset()
+        }
+    }
+
     pub fn update_viewport(&mut self, ui_context: &UiContext, options: &DisplayOption) {
         if let Some(ix) = self.index {
             let height = ui_context.layout[0].height as usize;
-
-            // get scroll buffer size, corrected in case of too small terminal
-            let scroll_offset = if height < 4 {
-                0
-            } else if options.scroll_offset() * 2 > height - 1 {
-                height / 2 - 1
-            } else {
-                options.scroll_offset()
-            };
+            let scroll_offset = Self::scroll_offset(height, options);
 
             // calculate viewport
             let viewport_end = self.viewport_index + height;
@@ -166,6 +167,48 @@ impl JoshutoDirList {
         }
     }
 
+    /// Moves the viewport by `offset` lines (up if negative).
+    /// The cursor only moves when it would end up in the scroll buffer or off screen.
+    pub fn scroll_viewport(
+        &mut self,
+        offset: isize,
+        ui_context: &UiContext,
+        options: &DisplayOption,
+    ) {
+        let ix = match self.index {
+            Some(ix) if !ui_context.layout.is_empty() => ix,
+            _ => return,
+        };
+        let height = ui_context.layout[0].height as usize;
+        // nothing is shown, e.g. in a terminal too small for the layout
+        if height == 0 {
+            return;
+        }
+        let scroll_offset = Self::scroll_offset(height, options);
+        let max_viewport_index = self.len().saturating_sub(height);
+
+        self.viewport_index = if offset < 0 {
+            self.viewport_index.saturating_sub(offset.unsigned_abs())
+        } else {
+            self.viewport_index.saturating_add(offset as usize)
+        }
+        .min(max_viewport_index);
+
+        // the scroll buffer does not apply at the very top and bottom
+        let min_index = if self.viewport_index == 0 {
+            0
+        } else {
+            self.viewport_index + scroll_offset
+        };
+        let max_index = if self.viewport_index >= max_viewport_index {
+            self.len() - 1
+        } else {
+            self.viewport_index + height - 1 - scroll_offset
+        };
+        self.index = Some(ix.max(min_index).min(max_index));
+        self.update_visual_mode_selection();
+    }
+
     pub fn set_index(
         &mut self,
         index: Option<usize>,
@@ -206,6 +249,71 @@ impl JoshutoDirList {
             .unwrap_or(false)
     }
 
+    /// Paths of the entries showing placeholder metadata, once per dir list
+    pub fn take_pending_metadata(&mut self) -> Vec<path::PathBuf> {
+        if self._metadata_requested {
+            return Vec::new();
+        }
+        self._metadata_requested = true;
+        self.contents
+            .iter()
+            .filter(|e| !e.metadata.is_loaded())
+            .map(|e| e.file_path_buf())
+            .collect()
+    }
+
+    /// Fills in loaded metadata, `None` for entries it couldn't be loaded for,
+    /// and sorts the entries again, keeping the cursor and visual mode anchor on their entries
+    pub fn update_metadata(
+        &mut self,
+        metadata: Vec<(path::PathBuf, Option<JoshutoMetadata>)>,
+        ui_context: &UiContext,
+        options: &DisplayOption,
+        tab_options: &TabDisplayOption,
+    ) {
+        let mut metadata: HashMap<path::PathBuf, Option<JoshutoMetadata>> =
+            metadata.into_iter().collect();
+        for entry in self.contents.iter_mut() {
+            match metadata.remove(entry.file_path()) {
+                Some(Some(m)) => entry.set_metadata(m, options),
+                Some(None) => entry.metadata.set_load_failed(),
+                None => {}
+            }
+        }
+        let path_at = |contents: &[JoshutoDirEntry], index: Option<usize>| {
+            index
+                .and_then(|i| contents.get(i))
+                .map(|e| e.file_path_buf())
+        };
+        let curr_path = path_at(&self.contents, self.index);
+        let anchor_path = path_at(&self.contents, self.visual_mode_anchor_index);
+        let contents = std::mem::take(&mut self.contents);
+        self.contents = sort_contents(self.path.as_path(), contents, options, tab_options);
+
+        let position = |contents: &[JoshutoDirEntry], path: &path::Path| {
+            contents.iter().position(|e| e.file_path() == path)
+        };
+        if let Some(anchor_path) = anchor_path {
+            self.visual_mode_anchor_index = position(&self.contents, &anchor_path);
+        }
+        if let Some(curr_path) = curr_path {
+            let index = position(&self.contents, &curr_path);
+            self.set_index(index, ui_context, options);
+        }
+        // the visual mode selection goes from the anchor to the cursor, wherever they moved
+        self.update_visual_mode_selection();
+    }
+
+    /// When this dir list was last shown as the current directory
+    /// (see `DirectoryHistory::evict_least_recently_used`)
+    pub fn last_used(&self) -> Instant {
+        self._last_used
+    }
+
+    pub fn touch(&mut self) {
+        self._last_used = Instant::now();
+    }
+
     pub fn depreciate(&mut self) {
         self._need_update = true;
     }
@@ -271,3 +379,121 @@ impl JoshutoDirList {
         self.viewport_index
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::config::clean::app::display::sort_type::SortType;
+
+    fn lazy_options() -> (DisplayOption, TabDisplayOption) {
+        let options = DisplayOption {
+            _lazy_metadata: true,
+            ..DisplayOption::default()
+        };
+        let mut tab_options = TabDisplayOption::default();
+        tab_options.sort_options.set_sort_method(SortType::Size);
+        (options, tab_options)
+    }
+
+    // a directory listed with placeholder metadata, sorted by size
+    fn lazy_dirlist() -> (tempfile::TempDir, JoshutoDirList) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("sub")).unwrap();
+        fs::write(root.join("a"), "123").unwrap();
+        fs::write(root.join("b"), "1").unwrap();
+        fs::write(root.join("c"), "12").unwrap();
+        std::os::unix::fs::symlink(root.join("sub"), root.join("link")).unwrap();
+
+        let (options, tab_options) = lazy_options();
+        let dirlist =
+            JoshutoDirList::from_path(root.to_path_buf(), &options, &tab_options).unwrap();
+        (dir, dirlist)
+    }
+
+    // loads the metadata of `dirlist` with the cursor on "b", selecting up to "c",
+    // loading "c" fails
+    fn load_metadata(dirlist: &mut JoshutoDirList) {
+        let (options, tab_options) = lazy_options();
+        let ui_context = UiContext { layout: Vec::new() };
+        dirlist.set_index(Some(4), &ui_context, &options);
+        dirlist.toggle_visual_mode();
+        dirlist.set_index(Some(3), &ui_context, &options);
+
+        let metadata = dirlist
+            .take_pending_metadata()
+            .iter()
+            .map(|p| {
+                let metadata = if p.ends_with("c") {
+                    None
+                } else {
+                    JoshutoMetadata::from(p).ok()
+                };
+                (p.clone(), metadata)
+            })
+            .collect();
+        dirlist.update_metadata(metadata, &ui_context, &options, &tab_options);
+    }
+
+    fn names(dirlist: &JoshutoDirList) -> Vec<String> {
+        dirlist.iter().map(|e| e.file_name().to_string()).collect()
+    }
+
+    #[test]
+    fn placeholders_list_directories_first() {
+        let (_dir, dirlist) = lazy_dirlist();
+        assert_eq!(names(&dirlist), vec!["link", "sub", "a", "b", "c"]);
+        assert!(dirlist.contents[0].metadata.is_dir());
+    }
+
+    #[test]
+    fn metadata_of_all_placeholders_is_requested_once() {
+        let (_dir, mut dirlist) = lazy_dirlist();
+        assert_eq!(dirlist.take_pending_metadata().len(), 5);
+        assert!(dirlist.take_pending_metadata().is_empty());
+    }
+
+    #[test]
+    fn loaded_metadata_sorts_the_list_again() {
+        let (_dir, mut dirlist) = lazy_dirlist();
+        load_metadata(&mut dirlist);
+        assert_eq!(names(&dirlist), vec!["link", "sub", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn failed_metadata_keeps_the_placeholder() {
+        let (_dir, mut dirlist) = lazy_dirlist();
+        load_metadata(&mut dirlist);
+        assert!(dirlist.contents[2].metadata.load_failed());
+    }
+
+    #[test]
+    fn cursor_and_visual_selection_follow_their_entries() {
+        let (_dir, mut dirlist) = lazy_dirlist();
+        load_metadata(&mut dirlist);
+        assert_eq!(dirlist.curr_entry_ref().unwrap().file_name(), "b");
+        assert_eq!(dirlist.get_visual_mode_anchor_index(), Some(2));
+        assert!(dirlist.contents[2].is_selected());
+        assert!(!dirlist.contents[4].is_selected());
+    }
+
+    #[test]
+    fn scrolling_without_a_layout_height_does_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a"), "").unwrap();
+        fs::write(dir.path().join("b"), "").unwrap();
+
+        let options = DisplayOption::default();
+        let tab_options = TabDisplayOption::default();
+        let ui_context = UiContext {
+            layout: vec![ratatui::layout::Rect::new(0, 0, 10, 0)],
+        };
+        let mut dirlist =
+            JoshutoDirList::from_path(dir.path().to_path_buf(), &options, &tab_options).unwrap();
+        dirlist.scroll_viewport(-1, &ui_context, &options);
+        assert_eq!(dirlist.first_index_for_viewport(), 0);
+        assert_eq!(dirlist.get_index(), Some(0));
+    }
+}
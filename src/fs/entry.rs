@@ -1,8 +1,9 @@
-use std::{fs, io, path};
+use std::{ffi, fs, io, path};
 
 use crate::{
     config::clean::app::display::DisplayOption,
-    fs::{FileType, JoshutoMetadata},
+    fs::{FileType, JoshutoMetadata, LinkType},
+    util::string::escape_os_str,
 };
 
 #[cfg(feature = "devicons")]
@@ -10,8 +11,14 @@ use crate::ICONS_T;
 
 #[derive(Clone, Debug)]
 pub struct JoshutoDirEntry {
+    /// `os_name` escaped for display, see `escape_os_str`
     name: String,
+    os_name: ffi::OsString,
     label: String,
+    /// `/` or `*` drawn after the (possibly truncated) label, see `file_markers`
+    marker: &'static str,
+    /// indentation and marker shown before the label in tree mode
+    tree_prefix: Option<String>,
     path: path::PathBuf,
     pub metadata: JoshutoMetadata,
     /// Directly selected by the user, _not_ by a current visual mode selection
@@ -29,34 +36,38 @@ impl JoshutoDirEntry {
     ) -> io::Result<Self> {
         let path = direntry.path().to_path_buf();
 
-        let name = direntry
+        let os_name = direntry
             .path()
             .strip_prefix(base)
             .unwrap()
-            .to_string_lossy()
-            .to_string();
+            .as_os_str()
+            .to_os_string();
+        let name = escape_os_str(&os_name);
 
-        let mut metadata = JoshutoMetadata::from(&path)?;
+        let mut metadata = if options.lazy_metadata() {
+            JoshutoMetadata::placeholder(direntry)?
+        } else {
+            JoshutoMetadata::from(&path)?
+        };
 
-        if options.automatically_count_files() && metadata.file_type().is_dir() {
+        if options.automatically_count_files()
+            && metadata.is_loaded()
+            && metadata.file_type().is_dir()
+        {
             if let Ok(size) = get_directory_size(path.as_path()) {
                 metadata.update_directory_size(size);
             }
         }
 
-        #[cfg(feature = "devicons")]
-        let label = if options.show_icons() {
-            create_icon_label(name.as_str(), &metadata)
-        } else {
-            name.clone()
-        };
-
-        #[cfg(not(feature = "devicons"))]
-        let label = name.clone();
+        let label = create_label(name.as_str(), path.as_path(), &metadata, options);
+        let marker = file_marker(&metadata, options);
 
         Ok(Self {
             name,
+            os_name,
             label,
+            marker,
+            tree_prefix: None,
             path,
             metadata,
             permanent_selected: false,
@@ -69,10 +80,19 @@ impl JoshutoDirEntry {
         self.name.as_str()
     }
 
+    /// The actual file name, which might not be valid UTF-8
+    pub fn file_name_os(&self) -> &ffi::OsStr {
+        self.os_name.as_os_str()
+    }
+
     pub fn label(&self) -> &str {
         self.label.as_str()
     }
 
+    pub fn marker(&self) -> &'static str {
+        self.marker
+    }
+
     pub fn file_path(&self) -> &path::Path {
         self.path.as_path()
     }
@@ -81,6 +101,46 @@ impl JoshutoDirEntry {
         self.path.clone()
     }
 
+    /// Replaces placeholder metadata, see `JoshutoMetadata::placeholder`
+    pub fn set_metadata(&mut self, mut metadata: JoshutoMetadata, options: &DisplayOption) {
+        if metadata.directory_size().is_none() {
+            if let Some(size) = self.metadata.directory_size() {
+                metadata.update_directory_size(size);
+            }
+        }
+        self.metadata = metadata;
+        self.update_label(options);
+    }
+
+    /// Shows only the file name behind `prefix` instead of the path relative to
+    /// the listed directory
+    pub fn set_tree_prefix(&mut self, prefix: String, options: &DisplayOption) {
+        self.tree_prefix = Some(prefix);
+        self.update_label(options);
+    }
+
+    fn update_label(&mut self, options: &DisplayOption) {
+        self.marker = file_marker(&self.metadata, options);
+        self.label = match self.tree_prefix.as_ref() {
+            Some(prefix) => {
+                let name = self.path.file_name().map(escape_os_str).unwrap_or_default();
+                let label = create_label(&name, self.path.as_path(), &self.metadata, options);
+                format!("{}{}", prefix, label)
+            }
+            None => create_label(
+                self.name.as_str(),
+                self.path.as_path(),
+                &self.metadata,
+                options,
+            ),
+        };
+    }
+
+    /// A directory which can be expanded in tree mode, symlinks are not followed
+    pub fn is_expandable(&self) -> bool {
+        self.metadata.is_dir() && matches!(self.metadata.link_type(), LinkType::Normal)
+    }
+
     pub fn is_selected(&self) -> bool {
         self.permanent_selected || self.visual_mode_selected
     }
@@ -141,33 +201,74 @@ impl std::cmp::Ord for JoshutoDirEntry {
     }
 }
 
+#[allow(unused_variables)]
+fn create_label(
+    name: &str,
+    path: &path::Path,
+    metadata: &JoshutoMetadata,
+    options: &DisplayOption,
+) -> String {
+    #[cfg(feature = "devicons")]
+    if options.show_icons() {
+        return create_icon_label(name, path, metadata);
+    }
+    name.to_string()
+}
+
 #[cfg(feature = "devicons")]
-fn create_icon_label(name: &str, metadata: &JoshutoMetadata) -> String {
-    let label = {
-        let icon = match metadata.file_type() {
-            FileType::Directory => ICONS_T
-                .directory_exact
-                .get(name)
-                .cloned()
-                .unwrap_or(ICONS_T.default_dir.clone()),
-            _ => ICONS_T
-                .file_exact
-                .get(name)
-                .cloned()
-                .unwrap_or(match name.rsplit_once('.') {
-                    Some((_, ext)) => ICONS_T
-                        .ext
-                        .get(ext)
-                        .unwrap_or(&ICONS_T.default_file)
-                        .to_string(),
-                    None => ICONS_T.default_file.clone(),
-                }),
+fn create_icon_label(name: &str, path: &path::Path, metadata: &JoshutoMetadata) -> String {
+    let label =
+        {
+            let icon =
+                match metadata.file_type() {
+                    FileType::Directory => ICONS_T
+                        .directory_exact
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_else(|| match ICONS_T.default_git_dir.as_ref() {
+                            // only looked for if configured, as it costs a stat per directory
+                            Some(icon) if path.join(".git").exists() => icon.clone(),
+                            _ => ICONS_T.default_dir.clone(),
+                        }),
+                    _ => ICONS_T.file_exact.get(name).cloned().unwrap_or(
+                        match name.rsplit_once('.') {
+                            Some((_, ext)) => ICONS_T
+                                .ext
+                                .get(ext)
+                                .unwrap_or(&ICONS_T.default_file)
+                                .to_string(),
+                            None => ICONS_T.default_file.clone(),
+                        },
+                    ),
+                };
+            format!("{} {}", icon, name)
         };
-        format!("{} {}", icon, name)
-    };
     label
 }
 
-fn get_directory_size(path: &path::Path) -> io::Result<usize> {
+/// Marks directories and executables like `ls -F`, for when icons are not shown
+fn file_marker(metadata: &JoshutoMetadata, options: &DisplayOption) -> &'static str {
+    #[cfg(unix)]
+    use std::os::unix::fs::PermissionsExt;
+
+    #[cfg(feature = "devicons")]
+    if options.show_icons() {
+        return "";
+    }
+    if !options.file_markers() {
+        return "";
+    }
+    match metadata.file_type() {
+        FileType::Directory => "/",
+        // the permissions of symlinks are those of their target
+        #[cfg(unix)]
+        FileType::File if crate::util::unix::is_executable(metadata.permissions_ref().mode()) => {
+            "*"
+        }
+        FileType::File => "",
+    }
+}
+
+pub fn get_directory_size(path: &path::Path) -> io::Result<usize> {
     fs::read_dir(path).map(|s| s.count())
 }
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+/// Builds a matcher for file names from `.gitignore` style `patterns`,
+/// invalid patterns are skipped
+pub fn build_ignore_patterns(patterns: &[String]) -> Gitignore {
+    let mut builder = GitignoreBuilder::new("");
+    for pattern in patterns {
+        let _ = builder.add_line(None, pattern);
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Decides which entries of a directory listing are ignored, by the global
+/// `ignore_patterns` or by the `.gitignore` files from the root of the git
+/// repository down to the entry
+pub struct IgnoreFilter<'a> {
+    patterns: &'a Gitignore,
+    repo_root: Option<PathBuf>,
+    // matchers of the directories seen so far, `None` if they have no `.gitignore`
+    dirs: HashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl<'a> IgnoreFilter<'a> {
+    pub fn new(dir: &Path, patterns: &'a Gitignore) -> Self {
+        let repo_root = dir
+            .ancestors()
+            .find(|p| p.join(".git").exists())
+            .map(Path::to_path_buf);
+        Self {
+            patterns,
+            repo_root,
+            dirs: HashMap::new(),
+        }
+    }
+
+    fn dir_matcher(&mut self, dir: &Path) -> Option<&Gitignore> {
+        let is_root = self.repo_root.as_deref() == Some(dir);
+        self.dirs
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let mut builder = GitignoreBuilder::new(dir);
+                let mut files = vec![dir.join(".gitignore")];
+                if is_root {
+                    files.push(dir.join(".git").join("info").join("exclude"));
+                }
+                let mut found = false;
+                for file in files.iter().filter(|f| f.is_file()) {
+                    found = true;
+                    let _ = builder.add(file);
+                }
+                if found {
+                    builder.build().ok()
+                } else {
+                    None
+                }
+            })
+            .as_ref()
+    }
+
+    pub fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        if let Some(file_name) = path.file_name() {
+            if self.patterns.matched(file_name, is_dir).is_ignore() {
+                return true;
+            }
+        }
+        let root = match self.repo_root.clone() {
+            Some(root) => root,
+            None => return false,
+        };
+        // the closest `.gitignore` with a matching pattern decides
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(&root) {
+                break;
+            }
+            if let Some(gitignore) = self.dir_matcher(dir) {
+                match gitignore.matched(path, is_dir) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    // a repository with the directory `sub`, and the configured patterns
+    fn repository() -> (tempfile::TempDir, Gitignore) {
+        let root = tempfile::tempdir().unwrap();
+        let sub = root.path().join("sub");
+        fs::create_dir(root.path().join(".git")).unwrap();
+        fs::create_dir(&sub).unwrap();
+        fs::write(root.path().join(".gitignore"), "target/\n*.log\n").unwrap();
+        fs::write(sub.join(".gitignore"), "!keep.log\n").unwrap();
+        (root, build_ignore_patterns(&["*.o".to_string()]))
+    }
+
+    #[test]
+    fn directory_patterns_only_match_directories() {
+        let (root, patterns) = repository();
+        let mut filter = IgnoreFilter::new(&root.path().join("sub"), &patterns);
+        assert!(filter.is_ignored(&root.path().join("target"), true));
+        assert!(!filter.is_ignored(&root.path().join("target"), false));
+    }
+
+    #[test]
+    fn parent_gitignore_files_apply() {
+        let (root, patterns) = repository();
+        let mut filter = IgnoreFilter::new(&root.path().join("sub"), &patterns);
+        assert!(filter.is_ignored(&root.path().join("sub/debug.log"), false));
+    }
+
+    #[test]
+    fn nested_gitignore_files_take_precedence() {
+        let (root, patterns) = repository();
+        let mut filter = IgnoreFilter::new(&root.path().join("sub"), &patterns);
+        assert!(!filter.is_ignored(&root.path().join("sub/keep.log"), false));
+    }
+
+    #[test]
+    fn configured_patterns_apply() {
+        let (root, patterns) = repository();
+        let mut filter = IgnoreFilter::new(&root.path().join("sub"), &patterns);
+        assert!(filter.is_ignored(&root.path().join("sub/main.o"), false));
+        assert!(!filter.is_ignored(&root.path().join("sub/main.rs"), false));
+    }
+}
@@ -22,11 +22,21 @@ pub enum LinkType {
     Symlink { target: String, valid: bool },
 }
 
+// whether the metadata was read from the file system, see `lazy_metadata`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LoadState {
+    Placeholder,
+    Loaded,
+    Failed,
+}
+
 #[derive(Clone, Debug)]
 pub struct JoshutoMetadata {
     _len: u64,
     _directory_size: Option<usize>,
     _modified: time::SystemTime,
+    _accessed: time::SystemTime,
+    _changed: time::SystemTime,
     _permissions: fs::Permissions,
     _file_type: FileType,
     _link_type: LinkType,
@@ -36,6 +46,7 @@ pub struct JoshutoMetadata {
     pub gid: u32,
     #[cfg(unix)]
     pub mode: u32,
+    _load_state: LoadState,
 }
 
 impl JoshutoMetadata {
@@ -53,6 +64,14 @@ impl JoshutoMetadata {
                 symlink_metadata.permissions(),
             ),
         };
+        let m = metadata.as_ref().unwrap_or(&symlink_metadata);
+        // not every file system records access times
+        let _accessed = m.accessed().unwrap_or(_modified);
+        #[cfg(unix)]
+        let _changed = time::UNIX_EPOCH
+            + time::Duration::new(m.ctime().max(0) as u64, m.ctime_nsec().max(0) as u32);
+        #[cfg(not(unix))]
+        let _changed = _modified;
 
         let (_file_type, _directory_size) = match metadata.as_ref() {
             Ok(m) if m.file_type().is_dir() => (FileType::Directory, None),
@@ -60,13 +79,9 @@ impl JoshutoMetadata {
         };
 
         let _link_type = if symlink_metadata.file_type().is_symlink() {
-            let mut link = "".to_string();
-
-            if let Ok(path) = fs::read_link(path) {
-                if let Some(s) = path.to_str() {
-                    link = s.to_string();
-                }
-            }
+            let link = fs::read_link(path)
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_default();
 
             let exists = path.exists();
             LinkType::Symlink {
@@ -88,6 +103,8 @@ impl JoshutoMetadata {
             _len,
             _directory_size,
             _modified,
+            _accessed,
+            _changed,
             _permissions,
             _file_type,
             _link_type,
@@ -97,9 +114,67 @@ impl JoshutoMetadata {
             gid,
             #[cfg(unix)]
             mode,
+            _load_state: LoadState::Loaded,
+        })
+    }
+
+    /// Metadata to show until the actual one is loaded, see `lazy_metadata`.
+    /// Only the file type is known, from reading the directory and resolving symlinks
+    #[cfg(unix)]
+    pub fn placeholder(direntry: &walkdir::DirEntry) -> io::Result<Self> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (is_dir, _link_type) = if direntry.path_is_symlink() {
+            let target = fs::metadata(direntry.path());
+            let is_dir = target.as_ref().map_or(false, |m| m.is_dir());
+            let link_type = LinkType::Symlink {
+                target: String::new(),
+                valid: target.is_ok(),
+            };
+            (is_dir, link_type)
+        } else {
+            (direntry.file_type().is_dir(), LinkType::Normal)
+        };
+        Ok(Self {
+            _len: 0,
+            _directory_size: None,
+            _modified: time::UNIX_EPOCH,
+            _accessed: time::UNIX_EPOCH,
+            _changed: time::UNIX_EPOCH,
+            _permissions: fs::Permissions::from_mode(0o000),
+            _file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::File
+            },
+            _link_type,
+            uid: 0,
+            gid: 0,
+            mode: 0,
+            _load_state: LoadState::Placeholder,
         })
     }
 
+    #[cfg(not(unix))]
+    pub fn placeholder(direntry: &walkdir::DirEntry) -> io::Result<Self> {
+        Self::from(direntry.path())
+    }
+
+    /// Whether this is not a placeholder anymore, either loaded or failed to load
+    pub fn is_loaded(&self) -> bool {
+        self._load_state != LoadState::Placeholder
+    }
+
+    /// Whether loading the actual metadata failed, the placeholder is kept
+    pub fn load_failed(&self) -> bool {
+        self._load_state == LoadState::Failed
+    }
+
+    /// Keeps the placeholder for good, loading the metadata failed
+    pub fn set_load_failed(&mut self) {
+        self._load_state = LoadState::Failed;
+    }
+
     pub fn len(&self) -> u64 {
         self._len
     }
@@ -116,6 +191,15 @@ impl JoshutoMetadata {
         self._modified
     }
 
+    pub fn accessed(&self) -> time::SystemTime {
+        self._accessed
+    }
+
+    /// Time of the last status change (`ctime`)
+    pub fn changed(&self) -> time::SystemTime {
+        self._changed
+    }
+
     pub fn permissions_ref(&self) -> &fs::Permissions {
         &self._permissions
     }
@@ -0,0 +1,72 @@
+use std::path;
+use std::thread;
+
+use crate::context::AppContext;
+use crate::event::AppEvent;
+use crate::fs::entry::get_directory_size;
+use crate::fs::JoshutoMetadata;
+
+// number of entries sent back at once, so that large directories fill in gradually
+const CHUNK_SIZE: usize = 256;
+
+/// Loads the metadata of entries listed with placeholders (see `lazy_metadata`)
+/// in the background, for the directories shown by the current tab.
+/// Entries whose metadata can't be read are sent as `None`
+pub fn load_pending(context: &mut AppContext) {
+    let display_options = context.config_ref().display_options_ref();
+    if !display_options.lazy_metadata() {
+        return;
+    }
+    let count_files = display_options.automatically_count_files();
+    let event_tx = context.clone_event_tx();
+
+    let curr_tab = context.tab_context_mut().curr_tab_mut();
+    let mut dirs: Vec<path::PathBuf> = curr_tab
+        .cwd()
+        .ancestors()
+        .take(2)
+        .map(|p| p.to_path_buf())
+        .collect();
+    if let Some(entry) = curr_tab
+        .curr_list_ref()
+        .and_then(|list| list.curr_entry_ref())
+    {
+        dirs.push(entry.file_path_buf());
+    }
+
+    for dir in dirs {
+        let paths = match curr_tab.history_mut().get_mut(dir.as_path()) {
+            Some(dirlist) => dirlist.take_pending_metadata(),
+            None => continue,
+        };
+        if paths.is_empty() {
+            continue;
+        }
+        let event_tx = event_tx.clone();
+        thread::spawn(move || {
+            for chunk in paths.chunks(CHUNK_SIZE) {
+                let entries = chunk
+                    .iter()
+                    .map(|p| {
+                        let mut metadata = JoshutoMetadata::from(p).ok();
+                        if let Some(metadata) = metadata.as_mut() {
+                            if count_files && metadata.is_dir() {
+                                if let Ok(size) = get_directory_size(p) {
+                                    metadata.update_directory_size(size);
+                                }
+                            }
+                        }
+                        (p.clone(), metadata)
+                    })
+                    .collect();
+                let event = AppEvent::DirMetadata {
+                    path: dir.clone(),
+                    entries,
+                };
+                if event_tx.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+}
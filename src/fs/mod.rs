@@ -1,6 +1,8 @@
 mod dirlist;
 mod entry;
+pub mod gitignore;
 mod metadata;
+pub mod metadata_loader;
 
 pub use self::dirlist::JoshutoDirList;
 pub use self::entry::JoshutoDirEntry;
@@ -1,14 +1,19 @@
 use std::collections::HashMap;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use walkdir::WalkDir;
 
 use crate::config::clean::app::display::dirlist::DirListDisplayOptions;
+use crate::config::clean::app::display::sort::SortOption;
 use crate::config::clean::app::display::tab::TabDisplayOption;
 use crate::config::clean::app::display::DisplayOption;
-use crate::context::UiContext;
+use crate::config::clean::app::search::CaseSensitivity;
+use crate::context::{MatchContext, UiContext};
+use crate::fs::gitignore::IgnoreFilter;
 use crate::fs::{JoshutoDirEntry, JoshutoDirList, JoshutoMetadata};
+use crate::FOLDER_SETTINGS_T;
 
 pub trait DirectoryHistory {
     fn populate_to_root(
@@ -39,6 +44,10 @@ pub trait DirectoryHistory {
     fn depreciate_all_entries(&mut self);
 
     fn depreciate_entry(&mut self, path: &Path);
+
+    /// Drops the least recently used dir lists, except `keep`,
+    /// until all dir lists hold at most `max_entries` entries
+    fn evict_least_recently_used(&mut self, max_entries: usize, keep: &[PathBuf]);
 }
 
 pub type JoshutoHistory = HashMap<PathBuf, JoshutoDirList>;
@@ -55,7 +64,20 @@ impl DirectoryHistory for JoshutoHistory {
 
         let mut prev: Option<&Path> = None;
         for curr in path.ancestors() {
-            if self.contains_key(curr) {
+            if self
+                .get(curr)
+                .map_or(false, |dirlist| !dirlist.need_update())
+            {
+                // up to date, no need to read it again
+                if let Some(dirlist) = self.get_mut(curr) {
+                    if let Some(ancestor) = prev.as_ref() {
+                        if let Some(i) = get_index_of_value(&dirlist.contents, ancestor) {
+                            dirlist.set_index(Some(i), ui_context, options);
+                        }
+                    }
+                    dirlist.touch();
+                }
+            } else if self.contains_key(curr) {
                 let mut new_dirlist =
                     create_dirlist_with_history(self, curr, options, tab_options)?;
                 if let Some(ancestor) = prev.as_ref() {
@@ -139,6 +161,26 @@ impl DirectoryHistory for JoshutoHistory {
             v.depreciate();
         }
     }
+
+    fn evict_least_recently_used(&mut self, max_entries: usize, keep: &[PathBuf]) {
+        let mut total: usize = self.values().map(|dirlist| dirlist.len()).sum();
+        if total <= max_entries {
+            return;
+        }
+        let mut candidates: Vec<(Instant, PathBuf, usize)> = self
+            .iter()
+            .filter(|(path, _)| !keep.contains(path))
+            .map(|(path, dirlist)| (dirlist.last_used(), path.clone(), dirlist.len()))
+            .collect();
+        candidates.sort_by_key(|(last_used, _, _)| *last_used);
+        for (_, path, len) in candidates {
+            if total <= max_entries {
+                break;
+            }
+            self.remove(&path);
+            total -= len;
+        }
+    }
 }
 
 fn get_index_of_value(arr: &[JoshutoDirEntry], val: &Path) -> Option<usize> {
@@ -182,8 +224,7 @@ pub fn create_dirlist_with_history(
         }
     }
 
-    let sort_options = tab_options.sort_options_ref();
-    contents.sort_by(|f1, f2| sort_options.compare(f1, f2));
+    let contents = sort_contents(path, contents, options, tab_options);
 
     let contents_len = contents.len();
     let index = if contents_len == 0 {
@@ -242,6 +283,59 @@ pub fn create_dirlist_with_history(
     Ok(dirlist)
 }
 
+/// Sorts the entries of `path`, in tree mode the contents of each directory
+/// follow it and are sorted among themselves
+pub fn sort_contents(
+    path: &Path,
+    mut contents: Vec<JoshutoDirEntry>,
+    options: &DisplayOption,
+    tab_options: &TabDisplayOption,
+) -> Vec<JoshutoDirEntry> {
+    let sort_options = folder_sort_options(path, tab_options);
+    contents.sort_by(|f1, f2| sort_options.compare(f1, f2));
+
+    let dirlist_opts = match tab_options.dirlist_options_ref(&path.to_path_buf()) {
+        Some(opts) if opts.tree() => opts,
+        _ => return contents,
+    };
+    let mut children: HashMap<PathBuf, Vec<JoshutoDirEntry>> = HashMap::new();
+    let len = contents.len();
+    for entry in contents {
+        let parent = entry.file_path().parent().unwrap_or(path).to_path_buf();
+        children.entry(parent).or_default().push(entry);
+    }
+
+    let mut results = Vec::with_capacity(len);
+    let mut stack: Vec<(std::vec::IntoIter<JoshutoDirEntry>, usize)> = Vec::new();
+    if let Some(entries) = children.remove(path) {
+        stack.push((entries.into_iter(), 0));
+    }
+    while let Some((entries, depth)) = stack.last_mut() {
+        let depth = *depth;
+        let mut entry = match entries.next() {
+            Some(entry) => entry,
+            None => {
+                stack.pop();
+                continue;
+            }
+        };
+        let marker = if !entry.is_expandable() {
+            "  "
+        } else if dirlist_opts.is_expanded(entry.file_path()) {
+            "▾ "
+        } else {
+            "▸ "
+        };
+        entry.set_tree_prefix(format!("{}{}", "  ".repeat(depth), marker), options);
+        let nested = children.remove(entry.file_path());
+        results.push(entry);
+        if let Some(nested) = nested {
+            stack.push((nested.into_iter(), depth + 1));
+        }
+    }
+    results
+}
+
 pub fn read_directory<F>(
     path: &Path,
     filter_func: F,
@@ -251,30 +345,109 @@ pub fn read_directory<F>(
 where
     F: Fn(&walkdir::DirEntry, &DisplayOption, &DirListDisplayOptions) -> bool,
 {
-    let dirlist_opts = tab_options
+    let mut dirlist_opts = tab_options
         .dirlist_options_ref(&path.to_path_buf())
         .map(|v| v.to_owned())
         .unwrap_or_default();
 
-    let results: Vec<JoshutoDirEntry> = WalkDir::new(path)
-        .max_depth(dirlist_opts.depth() as usize + 1)
+    // apply the settings saved for this directory
+    if let Some(setting) = FOLDER_SETTINGS_T.lock().unwrap().get(path) {
+        dirlist_opts.set_show_hidden(setting.show_hidden);
+        match setting.filter.as_ref() {
+            Some(filter) if dirlist_opts.filter_context_ref().is_none() => dirlist_opts
+                .set_filter_context(MatchContext::new_string(filter, CaseSensitivity::Smart)),
+            _ => {}
+        }
+    }
+
+    let mut ignore_filter = options
+        .hide_ignored()
+        .then(|| IgnoreFilter::new(path, options.ignore_patterns()));
+
+    let max_depth = if dirlist_opts.tree() {
+        usize::MAX
+    } else {
+        dirlist_opts.depth() as usize + 1
+    };
+    let mut walker = WalkDir::new(path)
+        .max_depth(max_depth)
         .into_iter()
         .filter_entry(|e| {
-            if e.path().to_str().cmp(&path.to_str()).is_ne() {
+            if e.path() != path {
                 filter_func(e, options, &dirlist_opts)
+                    && !ignore_filter
+                        .as_mut()
+                        .map_or(false, |f| f.is_ignored(e.path(), e.file_type().is_dir()))
             } else {
                 true
             }
-        })
-        .filter(|e| {
-            if let Ok(e) = e.as_ref() {
-                e.path().to_str().cmp(&path.to_str()).is_ne()
-            } else {
-                true
-            }
-        })
-        .filter_map(|res| JoshutoDirEntry::from(&res.ok()?, path, options).ok())
-        .collect();
+        });
+
+    let mut results = Vec::new();
+    while let Some(res) = walker.next() {
+        let e = match res {
+            Ok(e) if e.path() != path => e,
+            _ => continue,
+        };
+        // in tree mode, only the expanded directories are read
+        if dirlist_opts.tree() && e.file_type().is_dir() && !dirlist_opts.is_expanded(e.path()) {
+            walker.skip_current_dir();
+        }
+        if let Ok(entry) = JoshutoDirEntry::from(&e, path, options) {
+            results.push(entry);
+        }
+    }
 
     Ok(results)
 }
+
+/// Sort options of the tab with the settings saved for `path` applied
+pub fn folder_sort_options(path: &Path, tab_options: &TabDisplayOption) -> SortOption {
+    match FOLDER_SETTINGS_T.lock().unwrap().get(path) {
+        Some(setting) => setting.sort_options(tab_options.sort_options_ref()),
+        None => tab_options.sort_options_ref().clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    // a tree with `a` expanded
+    fn tree_contents() -> (tempfile::TempDir, Vec<JoshutoDirEntry>) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::create_dir_all(root.join("a").join("c")).unwrap();
+        fs::create_dir(root.join("b")).unwrap();
+        fs::write(root.join("a").join("x"), "").unwrap();
+        fs::write(root.join("a").join("c").join("y"), "").unwrap();
+        fs::write(root.join("z"), "").unwrap();
+
+        let options = DisplayOption::default();
+        let mut tab_options = TabDisplayOption::default();
+        let opts = tab_options.dirlist_options_mut(&root);
+        opts.set_tree(true);
+        opts.set_expanded(&root.join("a"), true);
+
+        let contents =
+            read_directory(&root, options.filter_func(), &options, &tab_options).unwrap();
+        let contents = sort_contents(&root, contents, &options, &tab_options);
+        (dir, contents)
+    }
+
+    #[test]
+    fn expanded_directories_are_followed_by_their_entries() {
+        let (_dir, contents) = tree_contents();
+        let names: Vec<&str> = contents.iter().map(|e| e.file_name()).collect();
+        assert_eq!(names, vec!["a", "a/c", "a/x", "b", "z"]);
+    }
+
+    #[test]
+    fn tree_labels_are_indented_with_an_expansion_marker() {
+        let (_dir, contents) = tree_contents();
+        assert!(contents[0].label().starts_with("▾ "));
+        assert!(contents[1].label().starts_with("  ▸ "));
+    }
+}
@@ -1,5 +1,7 @@
 use std::path;
 
+use crate::util::name_resolution::rename_filename_conflict;
+
 #[derive(Clone, Copy, Debug)]
 pub enum FileOperation {
     Cut,
@@ -38,14 +40,39 @@ impl std::fmt::Display for FileOperation {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct FileOperationOptions {
-    // cut, copy
+    // cut, copy, symlink
     pub overwrite: bool,
     pub skip_exist: bool,
+    // names for existing files are generated from this, see `unique_file_name`
+    pub rename_pattern: String,
 
     // delete
     pub permanently: bool,
+    // overwrite files this many times before deleting them, 0 to not overwrite
+    pub shred_passes: usize,
+}
+
+impl FileOperationOptions {
+    /// Makes `dest` not clash with an existing file unless it should be overwritten,
+    /// returns false if the file should be skipped instead
+    pub fn resolve_conflict(&self, dest: &mut path::PathBuf) -> bool {
+        if self.overwrite {
+            true
+        } else if self.skip_exist {
+            dest.symlink_metadata().is_err()
+        } else {
+            rename_filename_conflict(dest, &self.rename_pattern)
+        }
+    }
+
+    /// With `skip_exist`, a directory pasted onto an existing one is merged into it,
+    /// only its entries which exist already are skipped
+    pub fn merges_into(&self, src: &path::Path, dest: &path::Path) -> bool {
+        let is_dir = |path: &path::Path| path.symlink_metadata().map_or(false, |m| m.is_dir());
+        !self.overwrite && self.skip_exist && is_dir(dest) && is_dir(src)
+    }
 }
 
 impl std::fmt::Display for FileOperationOptions {
@@ -122,4 +149,10 @@ impl FileOperationProgress {
     pub fn total_bytes(&self) -> u64 {
         self._total_bytes
     }
+
+    /// Takes files which are left alone off the totals
+    pub fn skip(&mut self, files: usize, bytes: u64) {
+        self._total_files = self._total_files.saturating_sub(files);
+        self._total_bytes = self._total_bytes.saturating_sub(bytes);
+    }
 }
@@ -2,17 +2,77 @@ use std::fs;
 use std::io;
 use std::path;
 use std::process::{Command, Stdio};
-use std::sync::mpsc;
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
 
 #[cfg(unix)]
 use std::os::unix;
 
+use rayon::prelude::*;
+
 use crate::error::AppError;
 use crate::error::AppErrorKind;
 use crate::error::AppResult;
 use crate::io::{FileOperation, FileOperationOptions, FileOperationProgress};
 use crate::util::fs::query_number_of_items;
-use crate::util::name_resolution::rename_filename_conflict;
+
+// how often progress is sent while working through directory trees in parallel
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Progress of a file operation, updated from several threads at once
+pub struct SharedProgress {
+    state: Mutex<(FileOperationProgress, Instant)>,
+    tx: Mutex<mpsc::Sender<FileOperationProgress>>,
+}
+
+impl SharedProgress {
+    pub fn new(progress: FileOperationProgress, tx: mpsc::Sender<FileOperationProgress>) -> Self {
+        Self {
+            state: Mutex::new((progress, Instant::now())),
+            tx: Mutex::new(tx),
+        }
+    }
+
+    /// Records that `path` is done, sending the progress if it wasn't sent for a while
+    pub fn add(&self, path: &path::Path, files: usize, bytes: u64) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (progress, last_sent) = &mut *state;
+        progress.set_current_file(path.to_path_buf());
+        progress.set_files_processed(progress.files_processed() + files);
+        progress.set_bytes_processed(progress.bytes_processed() + bytes);
+        if last_sent.elapsed() >= PROGRESS_INTERVAL {
+            *last_sent = Instant::now();
+            self.send(progress.clone());
+        }
+    }
+
+    /// Records that `path` is skipped, see `FileOperationProgress::skip`
+    pub fn skip(&self, path: &path::Path, files: usize, bytes: u64) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (progress, _) = &mut *state;
+        progress.set_current_file(path.to_path_buf());
+        progress.skip(files, bytes);
+    }
+
+    pub fn send_now(&self) {
+        let progress = self.progress();
+        self.send(progress);
+    }
+
+    fn send(&self, progress: FileOperationProgress) {
+        if let Ok(tx) = self.tx.lock() {
+            let _ = tx.send(progress);
+        }
+    }
+
+    pub fn progress(&self) -> FileOperationProgress {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .0
+            .clone()
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct IoWorkerThread {
@@ -41,17 +101,25 @@ impl IoWorkerThread {
         self._kind
     }
 
+    /// Runs the operation, directories are copied and deleted with up to `threads` threads.
+    /// The threads belong to this operation alone, so operations running at the same time
+    /// don't share the global thread pool
     pub fn start(
         &self,
         tx: mpsc::Sender<FileOperationProgress>,
+        threads: usize,
     ) -> AppResult<FileOperationProgress> {
-        match self.kind() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.max(1))
+            .build()
+            .map_err(|e| AppError::new(AppErrorKind::Io(io::ErrorKind::Other), e.to_string()))?;
+        pool.install(|| match self.kind() {
             FileOperation::Cut => self.paste_cut(tx),
             FileOperation::Copy => self.paste_copy(tx),
             FileOperation::Symlink { relative: false } => self.paste_link_absolute(tx),
             FileOperation::Symlink { relative: true } => self.paste_link_relative(tx),
             FileOperation::Delete => self.delete(tx),
-        }
+        })
     }
 
     fn paste_copy(
@@ -59,7 +127,7 @@ impl IoWorkerThread {
         tx: mpsc::Sender<FileOperationProgress>,
     ) -> AppResult<FileOperationProgress> {
         let (total_files, total_bytes) = query_number_of_items(&self.paths)?;
-        let mut progress = FileOperationProgress::new(
+        let progress = FileOperationProgress::new(
             self.kind(),
             self.paths[0].to_path_buf(),
             0,
@@ -67,17 +135,17 @@ impl IoWorkerThread {
             0,
             total_bytes,
         );
+        let progress = SharedProgress::new(progress, tx);
         for path in self.paths.iter() {
-            let _ = tx.send(progress.clone());
+            progress.send_now();
             recursive_copy(
-                &tx,
+                &progress,
                 path.as_path(),
                 self.dest.as_path(),
-                self.options,
-                &mut progress,
+                &self.options,
             )?;
         }
-        Ok(progress)
+        Ok(progress.progress())
     }
 
     fn paste_cut(
@@ -99,7 +167,7 @@ impl IoWorkerThread {
                 &tx,
                 path.as_path(),
                 self.dest.as_path(),
-                self.options,
+                &self.options,
                 &mut progress,
             )?;
         }
@@ -128,8 +196,9 @@ impl IoWorkerThread {
             if let Some(s) = src.file_name() {
                 dest_buf.push(s);
             }
-            if !self.options.overwrite {
-                rename_filename_conflict(&mut dest_buf);
+            if !self.options.resolve_conflict(&mut dest_buf) {
+                progress.skip(1, 1);
+                continue;
             }
             unix::fs::symlink(src, &dest_buf)?;
             progress.set_files_processed(progress.files_processed() + 1);
@@ -160,8 +229,9 @@ impl IoWorkerThread {
             if let Some(s) = src.file_name() {
                 dest_buf.push(s);
             }
-            if !self.options.overwrite {
-                rename_filename_conflict(&mut dest_buf);
+            if !self.options.resolve_conflict(&mut dest_buf) {
+                progress.skip(1, 1);
+                continue;
             }
             let mut src_components = src.components();
             let mut dest_components = dest_buf.components();
@@ -191,76 +261,96 @@ impl IoWorkerThread {
         Ok(progress)
     }
 
-    fn delete(&self, _tx: mpsc::Sender<FileOperationProgress>) -> AppResult<FileOperationProgress> {
+    fn delete(&self, tx: mpsc::Sender<FileOperationProgress>) -> AppResult<FileOperationProgress> {
         let (total_files, total_bytes) = query_number_of_items(&self.paths)?;
-        let progress = FileOperationProgress::new(
+        if self.options.shred_passes > 0 {
+            let progress = FileOperationProgress::new(
+                self.kind(),
+                self.paths[0].to_path_buf(),
+                0,
+                total_files,
+                0,
+                total_bytes,
+            );
+            let progress = SharedProgress::new(progress, tx);
+            for path in self.paths.iter() {
+                shred_recursive(&progress, path, self.options.shred_passes)?;
+            }
+            return Ok(progress.progress());
+        }
+
+        if self.options.permanently {
+            let progress = FileOperationProgress::new(
+                self.kind(),
+                self.paths[0].to_path_buf(),
+                0,
+                total_files,
+                0,
+                total_bytes,
+            );
+            let progress = SharedProgress::new(progress, tx);
+            remove_files(&progress, &self.paths)?;
+            return Ok(progress.progress());
+        }
+
+        trash_files(&self.paths)?;
+        Ok(FileOperationProgress::new(
             self.kind(),
             self.paths[0].to_path_buf(),
             total_files,
             total_files,
             total_bytes,
             total_bytes,
-        );
-
-        if self.options.permanently {
-            remove_files(&self.paths)?;
-        } else {
-            trash_files(&self.paths)?;
-        }
-
-        Ok(progress)
+        ))
     }
 }
 
+/// Lists the paths of the entries of directory `path`
+fn read_dir_paths(path: &path::Path) -> io::Result<Vec<path::PathBuf>> {
+    fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect()
+}
+
+/// Copies `src` into `dest`, the entries of directories are copied in parallel
 pub fn recursive_copy(
-    tx: &mpsc::Sender<FileOperationProgress>,
+    progress: &SharedProgress,
     src: &path::Path,
     dest: &path::Path,
-    options: FileOperationOptions,
-    progress: &mut FileOperationProgress,
+    options: &FileOperationOptions,
 ) -> io::Result<()> {
     let mut dest_buf = dest.to_path_buf();
     if let Some(s) = src.file_name() {
         dest_buf.push(s);
     }
-    if !options.overwrite {
-        rename_filename_conflict(&mut dest_buf);
+    let merge = options.merges_into(src, &dest_buf);
+    if !merge && !options.resolve_conflict(&mut dest_buf) {
+        let (files, bytes) = query_number_of_items(&[src.to_path_buf()])?;
+        progress.skip(src, files, bytes);
+        return Ok(());
     }
 
-    progress.set_current_file(src.to_path_buf());
-
     let file_type = fs::symlink_metadata(src)?.file_type();
     if file_type.is_dir() {
         match fs::create_dir(dest_buf.as_path()) {
             Ok(_) => {}
             e => {
-                if !options.overwrite {
+                if !options.overwrite && !merge {
                     return e;
                 }
             }
         }
-        for entry in fs::read_dir(src)? {
-            let entry = entry?;
-            let entry_path = entry.path();
-            recursive_copy(
-                tx,
-                entry_path.as_path(),
-                dest_buf.as_path(),
-                options,
-                progress,
-            )?;
-            let _ = tx.send(progress.clone());
-        }
-        Ok(())
+        read_dir_paths(src)?.par_iter().try_for_each(|entry_path| {
+            recursive_copy(progress, entry_path.as_path(), dest_buf.as_path(), options)
+        })
     } else if file_type.is_file() {
-        let bytes_processed = progress.bytes_processed() + fs::copy(src, dest_buf)?;
-        progress.set_bytes_processed(bytes_processed);
-        progress.set_files_processed(progress.files_processed() + 1);
+        let bytes = fs::copy(src, dest_buf)?;
+        progress.add(src, 1, bytes);
         Ok(())
     } else if file_type.is_symlink() {
         let link_path = fs::read_link(src)?;
         std::os::unix::fs::symlink(link_path, dest_buf)?;
-        progress.set_files_processed(progress.files_processed() + 1);
+        progress.add(src, 1, 0);
         Ok(())
     } else {
         Ok(())
@@ -271,22 +361,31 @@ pub fn recursive_cut(
     tx: &mpsc::Sender<FileOperationProgress>,
     src: &path::Path,
     dest: &path::Path,
-    options: FileOperationOptions,
+    options: &FileOperationOptions,
     progress: &mut FileOperationProgress,
 ) -> io::Result<()> {
     let mut dest_buf = dest.to_path_buf();
     if let Some(s) = src.file_name() {
         dest_buf.push(s);
     }
-    if !options.overwrite {
-        rename_filename_conflict(&mut dest_buf);
+    let merge = options.merges_into(src, &dest_buf);
+    if !merge && !options.resolve_conflict(&mut dest_buf) {
+        let (files, bytes) = query_number_of_items(&[src.to_path_buf()])?;
+        progress.skip(files, bytes);
+        return Ok(());
     }
     let metadata = fs::symlink_metadata(src)?;
     let file_type = metadata.file_type();
 
     progress.set_current_file(src.to_path_buf());
 
-    match fs::rename(src, dest_buf.as_path()) {
+    // renaming onto the directory merged into fails, or replaces it if it is empty
+    let renamed = if merge {
+        Err(io::ErrorKind::AlreadyExists.into())
+    } else {
+        fs::rename(src, dest_buf.as_path())
+    };
+    match renamed {
         Ok(_) => {
             let bytes_processed = progress.bytes_processed() + metadata.len();
             progress.set_bytes_processed(bytes_processed);
@@ -295,7 +394,9 @@ pub fn recursive_cut(
         }
         Err(_e) => {
             if file_type.is_dir() {
-                fs::create_dir(dest_buf.as_path())?;
+                if !merge {
+                    fs::create_dir(dest_buf.as_path())?;
+                }
                 for entry in fs::read_dir(src)? {
                     let entry_path = entry?.path();
                     recursive_cut(
@@ -307,7 +408,11 @@ pub fn recursive_cut(
                     )?;
                     let _ = tx.send(progress.clone());
                 }
-                fs::remove_dir(src)?;
+                // skipped entries stay behind when merging
+                let skipped = merge && fs::read_dir(src)?.next().is_some();
+                if !skipped {
+                    fs::remove_dir(src)?;
+                }
             } else if file_type.is_symlink() {
                 let link_path = fs::read_link(src)?;
                 std::os::unix::fs::symlink(link_path, dest_buf)?;
@@ -326,22 +431,83 @@ pub fn recursive_cut(
     }
 }
 
-fn remove_files<P>(paths: &[P]) -> std::io::Result<()>
+/// Overwrites the files under `path` with random data and deletes them,
+/// the entries of directories are shredded in parallel.
+///
+/// Symlinks are removed without touching their target.
+fn shred_recursive(progress: &SharedProgress, path: &path::Path, passes: usize) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        read_dir_paths(path)?
+            .par_iter()
+            .try_for_each(|entry_path| shred_recursive(progress, entry_path.as_path(), passes))?;
+        return fs::remove_dir(path);
+    }
+    let bytes = if metadata.is_file() {
+        shred_file(path, metadata.len(), passes)?;
+        metadata.len()
+    } else {
+        0
+    };
+    fs::remove_file(path)?;
+    progress.add(path, 1, bytes);
+    Ok(())
+}
+
+fn shred_file(path: &path::Path, len: u64, passes: usize) -> io::Result<()> {
+    use rand::RngCore;
+    use std::io::{Seek, SeekFrom, Write};
+
+    let mut file = fs::OpenOptions::new().write(true).open(path)?;
+    let mut buf = vec![0; 64 * 1024];
+    let mut rng = rand::thread_rng();
+    for _ in 0..passes {
+        file.seek(SeekFrom::Start(0))?;
+        let mut remaining = len;
+        while remaining > 0 {
+            let n = remaining.min(buf.len() as u64) as usize;
+            rng.fill_bytes(&mut buf[..n]);
+            file.write_all(&buf[..n])?;
+            remaining -= n as u64;
+        }
+        // make sure every pass reaches the disk
+        file.sync_all()?;
+    }
+    // don't leave the original size behind
+    file.set_len(0)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+fn remove_files<P>(progress: &SharedProgress, paths: &[P]) -> std::io::Result<()>
 where
     P: AsRef<path::Path>,
 {
     for path in paths {
-        if let Ok(metadata) = fs::symlink_metadata(path) {
-            if metadata.is_dir() {
-                fs::remove_dir_all(path)?;
-            } else {
-                fs::remove_file(path)?;
-            }
+        // already gone
+        if fs::symlink_metadata(path).is_err() {
+            continue;
         }
+        remove_recursive(progress, path.as_ref())?;
     }
     Ok(())
 }
 
+/// Removes `path`, the entries of directories are removed in parallel
+fn remove_recursive(progress: &SharedProgress, path: &path::Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        read_dir_paths(path)?
+            .par_iter()
+            .try_for_each(|entry_path| remove_recursive(progress, entry_path.as_path()))?;
+        fs::remove_dir(path)
+    } else {
+        fs::remove_file(path)?;
+        progress.add(path, 1, metadata.len());
+        Ok(())
+    }
+}
+
 fn trash_files<P>(paths: &[P]) -> AppResult
 where
     P: AsRef<path::Path>,
@@ -385,3 +551,180 @@ where
         "Failed to trash file".to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(tx: mpsc::Sender<FileOperationProgress>) -> SharedProgress {
+        let progress =
+            FileOperationProgress::new(FileOperation::Copy, path::PathBuf::new(), 0, 0, 0, 0);
+        SharedProgress::new(progress, tx)
+    }
+
+    // `dirs` directories of `files` files of 4 bytes each
+    fn tree(root: &path::Path, dirs: usize, files: usize) {
+        for i in 0..dirs {
+            let dir = root.join(format!("dir{}", i));
+            fs::create_dir_all(&dir).unwrap();
+            for j in 0..files {
+                fs::write(dir.join(format!("file{}", j)), b"1234").unwrap();
+            }
+        }
+    }
+
+    fn run(
+        kind: FileOperation,
+        paths: Vec<path::PathBuf>,
+        dest: &path::Path,
+        options: FileOperationOptions,
+        threads: usize,
+    ) -> FileOperationProgress {
+        let worker = IoWorkerThread::new(kind, paths, dest.to_path_buf(), options);
+        let (tx, _rx) = mpsc::channel();
+        worker.start(tx, threads).unwrap()
+    }
+
+    #[test]
+    fn recursive_copy_copies_the_whole_tree() {
+        let root = tempfile::tempdir().unwrap();
+        let (src, dest) = (root.path().join("src"), root.path().join("dest"));
+        tree(&src, 4, 8);
+        fs::create_dir(&dest).unwrap();
+
+        let (tx, _rx) = mpsc::channel();
+        let progress = progress(tx);
+        recursive_copy(&progress, &src, &dest, &FileOperationOptions::default()).unwrap();
+        assert_eq!(progress.progress().files_processed(), 32);
+        assert_eq!(progress.progress().bytes_processed(), 128);
+        assert_eq!(fs::read(dest.join("src/dir3/file7")).unwrap(), b"1234");
+    }
+
+    #[test]
+    fn remove_files_removes_the_whole_tree() {
+        let root = tempfile::tempdir().unwrap();
+        let src = root.path().join("src");
+        tree(&src, 4, 8);
+
+        let (tx, _rx) = mpsc::channel();
+        let progress = progress(tx);
+        remove_files(&progress, std::slice::from_ref(&src)).unwrap();
+        assert_eq!(progress.progress().files_processed(), 32);
+        assert!(!src.exists());
+    }
+
+    #[test]
+    fn copy_with_one_thread() {
+        let root = tempfile::tempdir().unwrap();
+        let (src, dest) = (root.path().join("src"), root.path().join("dest"));
+        tree(&src, 1, 1);
+        fs::create_dir(&dest).unwrap();
+
+        let progress = run(
+            FileOperation::Copy,
+            vec![src],
+            &dest,
+            FileOperationOptions::default(),
+            1,
+        );
+        assert_eq!(progress.files_processed(), 1);
+        assert_eq!(fs::read(dest.join("src/dir0/file0")).unwrap(), b"1234");
+    }
+
+    // moves `src/dir` onto `dest/dir`, where `both` exists already
+    fn cut_skipping_existing() -> (tempfile::TempDir, FileOperationProgress) {
+        let root = tempfile::tempdir().unwrap();
+        let (src, dest) = (root.path().join("src"), root.path().join("dest"));
+        fs::create_dir_all(src.join("dir")).unwrap();
+        fs::write(src.join("dir/new"), b"new").unwrap();
+        fs::write(src.join("dir/both"), b"new").unwrap();
+        fs::create_dir_all(dest.join("dir")).unwrap();
+        fs::write(dest.join("dir/both"), b"old").unwrap();
+        let options = FileOperationOptions {
+            skip_exist: true,
+            ..FileOperationOptions::default()
+        };
+
+        let progress = run(FileOperation::Cut, vec![src.join("dir")], &dest, options, 1);
+        (root, progress)
+    }
+
+    #[test]
+    fn skip_exist_merges_directories() {
+        let (root, _) = cut_skipping_existing();
+        let dest = root.path().join("dest");
+        assert_eq!(fs::read(dest.join("dir/new")).unwrap(), b"new");
+        assert_eq!(fs::read(dest.join("dir/both")).unwrap(), b"old");
+    }
+
+    #[test]
+    fn skip_exist_leaves_skipped_files_behind() {
+        let (root, _) = cut_skipping_existing();
+        let src = root.path().join("src");
+        assert!(!src.join("dir/new").exists());
+        assert!(src.join("dir/both").exists());
+    }
+
+    #[test]
+    fn skip_exist_does_not_count_skipped_files() {
+        let (_root, progress) = cut_skipping_existing();
+        assert_eq!(progress.files_processed(), 1);
+        assert_eq!(progress.total_files(), 1);
+        assert_eq!(progress.bytes_processed(), 3);
+    }
+
+    #[test]
+    fn shred_file_empties_the_file() {
+        let root = tempfile::tempdir().unwrap();
+        let file = root.path().join("file");
+        fs::write(&file, b"secret").unwrap();
+
+        shred_file(&file, 6, 3).unwrap();
+        assert_eq!(fs::read(&file).unwrap(), b"");
+    }
+
+    fn shred_options() -> FileOperationOptions {
+        FileOperationOptions {
+            shred_passes: 2,
+            ..FileOperationOptions::default()
+        }
+    }
+
+    #[test]
+    fn shred_removes_the_whole_tree() {
+        let root = tempfile::tempdir().unwrap();
+        let src = root.path().join("src");
+        tree(&src, 4, 4);
+
+        let progress = run(
+            FileOperation::Delete,
+            vec![src.clone()],
+            root.path(),
+            shred_options(),
+            4,
+        );
+        assert_eq!(progress.files_processed(), 16);
+        assert_eq!(progress.bytes_processed(), 64);
+        assert!(!src.exists());
+    }
+
+    #[test]
+    fn shred_keeps_symlink_targets() {
+        let root = tempfile::tempdir().unwrap();
+        let (src, target) = (root.path().join("src"), root.path().join("target"));
+        fs::create_dir(&src).unwrap();
+        fs::write(&target, b"keep").unwrap();
+        unix::fs::symlink(&target, src.join("link")).unwrap();
+
+        let progress = run(
+            FileOperation::Delete,
+            vec![src.clone()],
+            root.path(),
+            shred_options(),
+            4,
+        );
+        assert_eq!(progress.files_processed(), 1);
+        assert!(!src.exists());
+        assert_eq!(fs::read(&target).unwrap(), b"keep");
+    }
+}
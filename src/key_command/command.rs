@@ -1,14 +1,19 @@
 use std::path;
 
+use termion::event::Event;
+
 use crate::commands::case_sensitivity::SetType;
 use crate::commands::quit::QuitAction;
 use crate::commands::select::SelectOption;
 use crate::config::clean::app::display::line_mode::LineMode;
 use crate::config::clean::app::display::line_number::LineNumberStyle;
 use crate::config::clean::app::display::new_tab::NewTabMode;
+use crate::config::clean::app::display::size_format::SizeFormat;
 use crate::config::clean::app::display::sort_type::SortType;
 use crate::config::clean::app::search::CaseSensitivity;
 use crate::io::FileOperationOptions;
+use crate::util::checksum::ChecksumAlgorithm;
+use crate::util::name_transform::NameTransform;
 
 #[derive(Clone, Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -22,6 +27,7 @@ pub enum Command {
     },
     ParentDirectory,
     PreviousDirectory,
+    FollowSymlink,
 
     CommandLine {
         prefix: String,
@@ -47,6 +53,7 @@ pub enum Command {
         background: bool,
         permanently: bool,
         noconfirm: bool,
+        shred: bool,
     },
 
     CursorMoveUp {
@@ -57,11 +64,20 @@ pub enum Command {
     },
     CursorMoveHome,
     CursorMoveEnd,
+    CursorMoveTo {
+        index: usize,
+    },
     CursorMovePageUp(f64),
     CursorMovePageDown(f64),
     CursorMovePageHome,
     CursorMovePageMiddle,
     CursorMovePageEnd,
+    ScrollViewUp {
+        offset: usize,
+    },
+    ScrollViewDown {
+        offset: usize,
+    },
 
     SetLineMode(LineMode),
 
@@ -88,9 +104,17 @@ pub enum Command {
     OpenFileWith {
         index: Option<usize>,
     },
+    OpenAll {
+        /// program and arguments as written, expanded when run
+        command: String,
+    },
     Quit(QuitAction),
+    QuitAll(QuitAction),
+    Suspend,
 
     ReloadDirList,
+    ReloadConfig,
+    ClearPreviewCache,
     RenameFile {
         new_name: path::PathBuf,
     },
@@ -98,6 +122,12 @@ pub enum Command {
     RenameFileAppendBase,
     RenameFilePrepend,
     RenameFileKeepExt,
+    RenameRegex {
+        expression: String,
+    },
+    RenameTransform {
+        transforms: Vec<NameTransform>,
+    },
     TouchFile {
         file_name: String,
     },
@@ -116,6 +146,9 @@ pub enum Command {
     },
     SearchNext,
     SearchPrev,
+    JumpToChar,
+    JumpNext,
+    JumpPrev,
 
     SelectGlob {
         pattern: String,
@@ -136,14 +169,41 @@ pub enum Command {
     },
     SetMode,
     SubProcess {
-        words: Vec<String>,
+        /// shell words as written, expanded when run
+        command: String,
         spawn: bool,
     },
     ShowTasks,
+    Messages,
+    ViewFile {
+        hex: bool,
+    },
+    ShowProperties,
+    Checksum {
+        algorithm: ChecksumAlgorithm,
+        copy: bool,
+    },
+    VerifyChecksums,
+    FindDuplicates,
+    ShowDuplicates,
+    DirStats,
+    CompareTabs,
+    XattrList,
+    XattrSet {
+        name: String,
+        value: String,
+    },
+    AclEdit,
+    OpenTerminal,
+    Dragdrop,
 
     ToggleHiddenFiles,
+    ToggleIgnoredFiles,
     SwitchLineNums(LineNumberStyle),
+    SetSizeFormat(Option<SizeFormat>),
 
+    ToggleTree,
+    TreeCollapse,
     Flat {
         depth: usize,
     },
@@ -154,6 +214,9 @@ pub enum Command {
     Sort(SortType),
     SortReverse,
 
+    SaveFolderSettings,
+    ClearFolderSettings,
+
     FilterGlob {
         pattern: String,
     },
@@ -175,6 +238,7 @@ pub enum Command {
         index: usize,
     },
     Help,
+    CommandPalette,
 
     SearchFzf,
     SubdirFzf,
@@ -187,6 +251,164 @@ pub enum Command {
     CustomSearch(Vec<String>),
     CustomSearchInteractive(Vec<String>),
 
+    Script(Vec<String>),
+
+    Map {
+        keys: Vec<Event>,
+        command: Box<Command>,
+    },
+    Unmap {
+        keys: Vec<Event>,
+    },
+
     BookmarkAdd,
     BookmarkChangeDirectory,
 }
+
+impl Command {
+    /// Whether the command changes files or runs other programs,
+    /// these are refused in `readonly` mode
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Self::BulkRename
+                | Self::SymlinkFiles { .. }
+                | Self::PasteFiles { .. }
+                | Self::DeleteFiles { .. }
+                | Self::NewDirectory { .. }
+                | Self::RenameFile { .. }
+                | Self::RenameFileAppend
+                | Self::RenameFileAppendBase
+                | Self::RenameFilePrepend
+                | Self::RenameFileKeepExt
+                | Self::RenameRegex { .. }
+                | Self::RenameTransform { .. }
+                | Self::TouchFile { .. }
+                | Self::SubProcess { .. }
+                | Self::XattrSet { .. }
+                | Self::AclEdit
+                | Self::OpenTerminal
+                | Self::Script(_)
+                | Self::Suspend
+                | Self::OpenFile
+                | Self::OpenFileWith { .. }
+                | Self::OpenAll { .. }
+                | Self::CustomSearch(_)
+                | Self::CustomSearchInteractive(_)
+                | Self::SearchFzf
+                | Self::SubdirFzf
+                | Self::SelectFzf { .. }
+                | Self::Zoxide(_)
+                | Self::ZoxideInteractive
+                | Self::CopyFileName
+                | Self::CopyFileNameWithoutExtension
+                | Self::CopyFilePath { .. }
+                | Self::CopyDirPath
+                | Self::Checksum { copy: true, .. }
+                | Self::ClearPreviewCache
+                | Self::Dragdrop
+                | Self::BookmarkAdd
+                | Self::SetMode
+                | Self::SaveFolderSettings
+                | Self::ClearFolderSettings
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::key_command::constants::commands;
+
+    #[test]
+    fn new_names_are_literal_test() {
+        match Command::from_str("rename price$5 ~.txt").unwrap() {
+            Command::RenameFile { new_name } => {
+                assert_eq!(new_name, path::PathBuf::from("price$5 ~.txt"))
+            }
+            _ => panic!(),
+        }
+        match Command::from_str("mkdir $HOME").unwrap() {
+            Command::NewDirectory { path } => assert_eq!(path, path::PathBuf::from("$HOME")),
+            _ => panic!(),
+        }
+        match Command::from_str("touch a$b").unwrap() {
+            Command::TouchFile { file_name } => assert_eq!(file_name, "a$b"),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn readonly_refused_commands_test() {
+        let mut refused = Vec::new();
+        for name in commands() {
+            // commands needing an argument are given one
+            let command = [
+                "",
+                " 1",
+                " a quit",
+                " lowercase",
+                " smart",
+                " lexical",
+                " size",
+            ]
+            .iter()
+            .find_map(|arg| Command::from_str(&format!("{}{}", name, arg)).ok())
+            .unwrap_or_else(|| panic!("`{}` does not parse", name));
+            if command.is_mutating() {
+                refused.push(name);
+            }
+        }
+        refused.sort_unstable();
+        assert_eq!(
+            refused,
+            vec![
+                "acl_edit",
+                "add_bookmark",
+                "bulk_rename",
+                "clear_folder_settings",
+                "clear_preview_cache",
+                "copy_dirpath",
+                "copy_filename",
+                "copy_filename_without_extension",
+                "copy_filepath",
+                "custom_search",
+                "custom_search_interactive",
+                "delete_files",
+                "dragdrop",
+                "mkdir",
+                "open",
+                "open_all",
+                "open_terminal",
+                "open_with",
+                "paste_files",
+                "rename",
+                "rename_append",
+                "rename_append_base",
+                "rename_keep_ext",
+                "rename_prepend",
+                "rename_regex",
+                "rename_transform",
+                "save_folder_settings",
+                "script",
+                "search_fzf",
+                "select_fzf",
+                "set_mode",
+                "shell",
+                "spawn",
+                "subdir_fzf",
+                "suspend",
+                "symlink_files",
+                "touch",
+                "xattr_set",
+                "z",
+                "zi",
+            ]
+        );
+        // only copying runs the clipboard program
+        assert!(!Command::from_str("checksum").unwrap().is_mutating());
+        assert!(Command::from_str("checksum --copy").unwrap().is_mutating());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{config::clean::keymap::KeyMapping, key_command::Command};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum CommandKeybind {
     SimpleKeybind {
         commands: Vec<Command>,
@@ -1,5 +1,9 @@
+use std::str::FromStr;
+
 use rustyline::completion::Pair;
 
+use crate::key_command::{Command, CommandComment};
+
 pub const CMD_COMMAND_LINE: &str = ":";
 
 macro_rules! cmd_constants {
@@ -16,9 +20,12 @@ macro_rules! cmd_constants {
 
 cmd_constants![
     (CMD_QUIT, "quit"),
+    (CMD_QUIT_ALL, "quit_all"),
+    (CMD_SUSPEND, "suspend"),
     (CMD_BULK_RENAME, "bulk_rename"),
     (CMD_CHANGE_DIRECTORY, "cd"),
     (CMD_PARENT_DIRECTORY, "cd .."),
+    (CMD_FOLLOW_SYMLINK, "follow_symlink"),
     (CMD_PREVIOUS_DIRECTORY, "cd -"),
     (CMD_NEW_TAB, "new_tab"),
     (CMD_CLOSE_TAB, "close_tab"),
@@ -38,8 +45,13 @@ cmd_constants![
     (CMD_CURSOR_MOVE_DOWN, "cursor_move_down"),
     (CMD_CURSOR_MOVE_HOME, "cursor_move_home"),
     (CMD_CURSOR_MOVE_END, "cursor_move_end"),
+    (CMD_CURSOR_MOVE_TO, "cursor_move_to"),
     (CMD_CURSOR_MOVE_PAGEUP, "cursor_move_page_up"),
     (CMD_CURSOR_MOVE_PAGEDOWN, "cursor_move_page_down"),
+    (CMD_CURSOR_MOVE_PAGEUP_HALF, "page_up_half"),
+    (CMD_CURSOR_MOVE_PAGEDOWN_HALF, "page_down_half"),
+    (CMD_SCROLL_VIEW_UP, "scroll_view_up"),
+    (CMD_SCROLL_VIEW_DOWN, "scroll_view_down"),
     (CMD_CURSOR_MOVE_PAGEHOME, "cursor_move_page_home"),
     (CMD_CURSOR_MOVE_PAGEMIDDLE, "cursor_move_page_middle"),
     (CMD_CURSOR_MOVE_PAGEEND, "cursor_move_page_end"),
@@ -50,17 +62,25 @@ cmd_constants![
     (CMD_NEW_DIRECTORY, "mkdir"),
     (CMD_OPEN_FILE, "open"),
     (CMD_OPEN_FILE_WITH, "open_with"),
+    (CMD_OPEN_ALL, "open_all"),
     (CMD_RELOAD_DIRECTORY_LIST, "reload_dirlist"),
+    (CMD_RELOAD_CONFIG, "reload_config"),
+    (CMD_CLEAR_PREVIEW_CACHE, "clear_preview_cache"),
     (CMD_RENAME_FILE, "rename"),
     (CMD_RENAME_FILE_APPEND, "rename_append"),
     (CMD_RENAME_FILE_APPEND_BASE, "rename_append_base"),
     (CMD_RENAME_FILE_PREPEND, "rename_prepend"),
     (CMD_RENAME_FILE_KEEP_EXT, "rename_keep_ext"),
+    (CMD_RENAME_REGEX, "rename_regex"),
+    (CMD_RENAME_TRANSFORM, "rename_transform"),
     (CMD_SEARCH_STRING, "search"),
     (CMD_SEARCH_INCREMENTAL, "search_inc"),
     (CMD_SEARCH_GLOB, "search_glob"),
     (CMD_SEARCH_REGEX, "search_regex"),
     (CMD_SEARCH_NEXT, "search_next"),
+    (CMD_JUMP_TO_CHAR, "jump_to_char"),
+    (CMD_JUMP_NEXT, "jump_next"),
+    (CMD_JUMP_PREV, "jump_prev"),
     (CMD_SEARCH_PREV, "search_prev"),
     (CMD_SELECT_GLOB, "select_glob"),
     (CMD_SELECT_REGEX, "select_regex"),
@@ -72,14 +92,31 @@ cmd_constants![
     (CMD_SUBPROCESS_FOREGROUND, "shell"),
     (CMD_SUBPROCESS_BACKGROUND, "spawn"),
     (CMD_SHOW_TASKS, "show_tasks"),
+    (CMD_MESSAGES, "messages"),
     (CMD_TAB_SWITCH, "tab_switch"),
     (CMD_TAB_SWITCH_INDEX, "tab_switch_index"),
     (CMD_TOGGLE_HIDDEN, "toggle_hidden"),
+    (CMD_TOGGLE_IGNORE, "toggle_ignore"),
     (CMD_TOGGLE_VISUAL, "toggle_visual"),
     (CMD_SWITCH_LINE_NUMBERS, "line_nums"),
+    (CMD_SIZE_FORMAT, "size_format"),
     (CMD_SET_LINEMODE, "linemode"),
     (CMD_TOUCH_FILE, "touch"),
+    (CMD_VIEW_FILE, "view"),
+    (CMD_SHOW_PROPERTIES, "show_properties"),
+    (CMD_CHECKSUM, "checksum"),
+    (CMD_VERIFY_CHECKSUMS, "verify_checksums"),
+    (CMD_FIND_DUPLICATES, "find_duplicates"),
+    (CMD_SHOW_DUPLICATES, "show_duplicates"),
+    (CMD_DIR_STATS, "stats"),
+    (CMD_COMPARE_TABS, "compare_tabs"),
+    (CMD_OPEN_TERMINAL, "open_terminal"),
+    (CMD_DRAGDROP, "dragdrop"),
+    (CMD_XATTR_LIST, "xattr_list"),
+    (CMD_XATTR_SET, "xattr_set"),
+    (CMD_ACL_EDIT, "acl_edit"),
     (CMD_HELP, "help"),
+    (CMD_COMMAND_PALETTE, "command_palette"),
     (CMD_SEARCH_FZF, "search_fzf"),
     (CMD_SUBDIR_FZF, "subdir_fzf"),
     (CMD_SELECT_FZF, "select_fzf"),
@@ -87,6 +124,9 @@ cmd_constants![
     (CMD_ZOXIDE_INTERACTIVE, "zi"),
     (CMD_NUMBERED_COMMAND, "numbered_command"),
     (CMD_FLAT, "flat"),
+    (CMD_FLATTEN, "flatten"),
+    (CMD_TOGGLE_TREE, "toggle_tree"),
+    (CMD_TREE_COLLAPSE, "tree_collapse"),
     (CMD_ESCAPE, "escape"),
     (CMD_FILTER_GLOB, "filter_glob"),
     (CMD_FILTER_REGEX, "filter_regex"),
@@ -95,8 +135,165 @@ cmd_constants![
     (CMD_BOOKMARK_CHANGE_DIRECTORY, "cd_bookmark"),
     (CMD_CUSTOM_SEARCH, "custom_search"),
     (CMD_CUSTOM_SEARCH_INTERACTIVE, "custom_search_interactive"),
+    (CMD_SCRIPT, "script"),
+    (CMD_MAP, "map"),
+    (CMD_SAVE_FOLDER_SETTINGS, "save_folder_settings"),
+    (CMD_CLEAR_FOLDER_SETTINGS, "clear_folder_settings"),
+    (CMD_UNMAP, "unmap"),
+];
+
+/// Sections of the help page, in display order
+pub const COMMAND_CATEGORIES: [&str; 7] = [
+    "Navigation",
+    "Files",
+    "Selection",
+    "Search",
+    "Tabs",
+    "View",
+    "Other",
 ];
 
+pub fn command_category(command: &str) -> &'static str {
+    match command {
+        CMD_CHANGE_DIRECTORY
+        | CMD_PARENT_DIRECTORY
+        | CMD_PREVIOUS_DIRECTORY
+        | CMD_FOLLOW_SYMLINK
+        | CMD_CURSOR_MOVE_UP
+        | CMD_CURSOR_MOVE_DOWN
+        | CMD_CURSOR_MOVE_HOME
+        | CMD_CURSOR_MOVE_END
+        | CMD_CURSOR_MOVE_TO
+        | CMD_CURSOR_MOVE_PAGEUP
+        | CMD_CURSOR_MOVE_PAGEDOWN
+        | CMD_CURSOR_MOVE_PAGEUP_HALF
+        | CMD_CURSOR_MOVE_PAGEDOWN_HALF
+        | CMD_SCROLL_VIEW_UP
+        | CMD_SCROLL_VIEW_DOWN
+        | CMD_CURSOR_MOVE_PAGEHOME
+        | CMD_CURSOR_MOVE_PAGEMIDDLE
+        | CMD_CURSOR_MOVE_PAGEEND
+        | CMD_PARENT_CURSOR_MOVE_UP
+        | CMD_PARENT_CURSOR_MOVE_DOWN
+        | CMD_PREVIEW_CURSOR_MOVE_UP
+        | CMD_PREVIEW_CURSOR_MOVE_DOWN
+        | CMD_NUMBERED_COMMAND
+        | CMD_SUBDIR_FZF
+        | CMD_ZOXIDE
+        | CMD_ZOXIDE_INTERACTIVE
+        | CMD_BOOKMARK_ADD
+        | CMD_BOOKMARK_CHANGE_DIRECTORY => "Navigation",
+        CMD_BULK_RENAME
+        | CMD_CUT_FILES
+        | CMD_COPY_FILES
+        | CMD_COPY_FILENAME
+        | CMD_COPY_FILENAME_WITHOUT_EXTENSION
+        | CMD_COPY_FILEPATH
+        | CMD_COPY_DIRECTORY_PATH
+        | CMD_SYMLINK_FILES
+        | CMD_PASTE_FILES
+        | CMD_DELETE_FILES
+        | CMD_NEW_DIRECTORY
+        | CMD_TOUCH_FILE
+        | CMD_OPEN_FILE
+        | CMD_OPEN_FILE_WITH
+        | CMD_OPEN_ALL
+        | CMD_RENAME_FILE
+        | CMD_RENAME_FILE_APPEND
+        | CMD_RENAME_FILE_APPEND_BASE
+        | CMD_RENAME_FILE_PREPEND
+        | CMD_RENAME_FILE_KEEP_EXT
+        | CMD_RENAME_REGEX
+        | CMD_RENAME_TRANSFORM
+        | CMD_SET_MODE
+        | CMD_VIEW_FILE
+        | CMD_SHOW_PROPERTIES
+        | CMD_CHECKSUM
+        | CMD_VERIFY_CHECKSUMS
+        | CMD_FIND_DUPLICATES
+        | CMD_SHOW_DUPLICATES
+        | CMD_DIR_STATS
+        | CMD_XATTR_LIST
+        | CMD_XATTR_SET
+        | CMD_ACL_EDIT
+        | CMD_OPEN_TERMINAL
+        | CMD_DRAGDROP
+        | CMD_SUBPROCESS_FOREGROUND
+        | CMD_SUBPROCESS_BACKGROUND => "Files",
+        CMD_SELECT_GLOB | CMD_SELECT_REGEX | CMD_SELECT_STRING | CMD_SELECT_FZF
+        | CMD_TOGGLE_VISUAL | CMD_ESCAPE => "Selection",
+        CMD_SEARCH_STRING
+        | CMD_SEARCH_INCREMENTAL
+        | CMD_SEARCH_GLOB
+        | CMD_SEARCH_REGEX
+        | CMD_SEARCH_NEXT
+        | CMD_SEARCH_PREV
+        | CMD_JUMP_TO_CHAR
+        | CMD_JUMP_NEXT
+        | CMD_JUMP_PREV
+        | CMD_SEARCH_FZF
+        | CMD_SET_CASE_SENSITIVITY
+        | CMD_FILTER_GLOB
+        | CMD_FILTER_REGEX
+        | CMD_FILTER_STRING
+        | CMD_CUSTOM_SEARCH
+        | CMD_CUSTOM_SEARCH_INTERACTIVE => "Search",
+        CMD_NEW_TAB | CMD_CLOSE_TAB | CMD_TAB_SWITCH | CMD_TAB_SWITCH_INDEX | CMD_COMPARE_TABS => {
+            "Tabs"
+        }
+        CMD_SORT
+        | CMD_SORT_REVERSE
+        | CMD_TOGGLE_HIDDEN
+        | CMD_TOGGLE_IGNORE
+        | CMD_SWITCH_LINE_NUMBERS
+        | CMD_SIZE_FORMAT
+        | CMD_SET_LINEMODE
+        | CMD_FLAT
+        | CMD_FLATTEN
+        | CMD_TOGGLE_TREE
+        | CMD_TREE_COLLAPSE
+        | CMD_SAVE_FOLDER_SETTINGS
+        | CMD_CLEAR_FOLDER_SETTINGS
+        | CMD_RELOAD_DIRECTORY_LIST
+        | CMD_CLEAR_PREVIEW_CACHE => "View",
+        _ => "Other",
+    }
+}
+
+/// The comment of `command` shown at the help page. Commands needing an argument
+/// do not parse without one and the comments of some depend on it, so these are
+/// described by name
+pub fn command_comment(command: &str) -> &'static str {
+    match command {
+        CMD_CURSOR_MOVE_TO => "Move cursor to entry number",
+        CMD_NEW_DIRECTORY => "Make a new directory",
+        CMD_RENAME_FILE => "Rename file",
+        CMD_RENAME_REGEX => "Rename selected files with a regex substitution",
+        CMD_RENAME_TRANSFORM => "Change case or sanitize names of selected files",
+        CMD_SEARCH_STRING => "Search",
+        CMD_SEARCH_GLOB => "Search with globbing",
+        CMD_SEARCH_REGEX => "Search with regex",
+        CMD_SELECT_GLOB => "Select files with globbing",
+        CMD_SELECT_REGEX => "Select files with regex",
+        CMD_SET_CASE_SENSITIVITY => "Set case sensitivity",
+        CMD_SORT => "Sort files",
+        CMD_SUBPROCESS_FOREGROUND => "Run a shell command",
+        CMD_SUBPROCESS_BACKGROUND => "Run command in background",
+        CMD_TAB_SWITCH => "Switch to the next tab",
+        CMD_TAB_SWITCH_INDEX => "Switch to a given tab",
+        CMD_SET_LINEMODE => "Show files with the given attributes",
+        CMD_XATTR_SET => "Set extended attribute of selected files",
+        CMD_NUMBERED_COMMAND => "Jump via input number",
+        CMD_FLAT | CMD_FLATTEN => "Flatten directory list",
+        CMD_SCRIPT => "Run a script",
+        CMD_MAP => "Bind a key sequence to a command",
+        CMD_UNMAP => "Remove a key binding",
+        _ => Command::from_str(command)
+            .map(|c| c.comment())
+            .unwrap_or_default(),
+    }
+}
+
 pub fn complete_command(partial_command: &str) -> Vec<Pair> {
     commands()
         .into_iter()
@@ -107,3 +304,21 @@ pub fn complete_command(partial_command: &str) -> Vec<Pair> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_comment_test() {
+        for command in commands() {
+            assert!(
+                !command_comment(command).is_empty(),
+                "`{}` has no comment",
+                command
+            );
+        }
+        assert_eq!(command_comment(CMD_SORT), "Sort files");
+        assert_eq!(command_comment(CMD_QUIT), "Quit the program");
+    }
+}
@@ -5,8 +5,11 @@ impl AppCommand for Command {
     fn command(&self) -> &'static str {
         match self {
             Self::Help => CMD_HELP,
+            Self::CommandPalette => CMD_COMMAND_PALETTE,
 
             Self::Quit(_) => CMD_QUIT,
+            Self::QuitAll(_) => CMD_QUIT_ALL,
+            Self::Suspend => CMD_SUSPEND,
 
             Self::ToggleVisualMode => CMD_TOGGLE_VISUAL,
             Self::Escape => CMD_ESCAPE,
@@ -16,6 +19,7 @@ impl AppCommand for Command {
             Self::ChangeDirectory { .. } => CMD_CHANGE_DIRECTORY,
             Self::ParentDirectory => CMD_PARENT_DIRECTORY,
             Self::PreviousDirectory => CMD_PREVIOUS_DIRECTORY,
+            Self::FollowSymlink => CMD_FOLLOW_SYMLINK,
 
             Self::NewTab { .. } => CMD_NEW_TAB,
             Self::CloseTab => CMD_CLOSE_TAB,
@@ -36,11 +40,14 @@ impl AppCommand for Command {
             Self::CursorMoveDown { .. } => CMD_CURSOR_MOVE_DOWN,
             Self::CursorMoveHome => CMD_CURSOR_MOVE_HOME,
             Self::CursorMoveEnd => CMD_CURSOR_MOVE_END,
+            Self::CursorMoveTo { .. } => CMD_CURSOR_MOVE_TO,
             Self::CursorMovePageUp(_) => CMD_CURSOR_MOVE_PAGEUP,
             Self::CursorMovePageDown(_) => CMD_CURSOR_MOVE_PAGEDOWN,
             Self::CursorMovePageHome => CMD_CURSOR_MOVE_PAGEUP,
             Self::CursorMovePageMiddle => CMD_CURSOR_MOVE_PAGEDOWN,
             Self::CursorMovePageEnd => CMD_CURSOR_MOVE_PAGEDOWN,
+            Self::ScrollViewUp { .. } => CMD_SCROLL_VIEW_UP,
+            Self::ScrollViewDown { .. } => CMD_SCROLL_VIEW_DOWN,
 
             Self::ParentCursorMoveUp { .. } => CMD_PARENT_CURSOR_MOVE_UP,
             Self::ParentCursorMoveDown { .. } => CMD_PARENT_CURSOR_MOVE_DOWN,
@@ -51,13 +58,18 @@ impl AppCommand for Command {
             Self::NewDirectory { .. } => CMD_NEW_DIRECTORY,
             Self::OpenFile => CMD_OPEN_FILE,
             Self::OpenFileWith { .. } => CMD_OPEN_FILE_WITH,
+            Self::OpenAll { .. } => CMD_OPEN_ALL,
 
             Self::ReloadDirList => CMD_RELOAD_DIRECTORY_LIST,
+            Self::ReloadConfig => CMD_RELOAD_CONFIG,
+            Self::ClearPreviewCache => CMD_CLEAR_PREVIEW_CACHE,
             Self::RenameFile { .. } => CMD_RENAME_FILE,
             Self::RenameFileAppend => CMD_RENAME_FILE_APPEND,
             Self::RenameFileAppendBase => CMD_RENAME_FILE_APPEND_BASE,
             Self::RenameFilePrepend => CMD_RENAME_FILE_PREPEND,
             Self::RenameFileKeepExt => CMD_RENAME_FILE_KEEP_EXT,
+            Self::RenameRegex { .. } => CMD_RENAME_REGEX,
+            Self::RenameTransform { .. } => CMD_RENAME_TRANSFORM,
 
             Self::SearchString { .. } => CMD_SEARCH_STRING,
             Self::SearchIncremental { .. } => CMD_SEARCH_INCREMENTAL,
@@ -65,6 +77,9 @@ impl AppCommand for Command {
             Self::SearchRegex { .. } => CMD_SEARCH_REGEX,
             Self::SearchNext => CMD_SEARCH_NEXT,
             Self::SearchPrev => CMD_SEARCH_PREV,
+            Self::JumpToChar => CMD_JUMP_TO_CHAR,
+            Self::JumpNext => CMD_JUMP_NEXT,
+            Self::JumpPrev => CMD_JUMP_PREV,
 
             Self::SelectGlob { .. } => CMD_SELECT_GLOB,
             Self::SelectRegex { .. } => CMD_SELECT_REGEX,
@@ -74,13 +89,32 @@ impl AppCommand for Command {
             Self::SetMode => CMD_SET_MODE,
 
             Self::ShowTasks => CMD_SHOW_TASKS,
+            Self::Messages => CMD_MESSAGES,
+            Self::ViewFile { .. } => CMD_VIEW_FILE,
+            Self::ShowProperties => CMD_SHOW_PROPERTIES,
+            Self::Checksum { .. } => CMD_CHECKSUM,
+            Self::VerifyChecksums => CMD_VERIFY_CHECKSUMS,
+            Self::FindDuplicates => CMD_FIND_DUPLICATES,
+            Self::ShowDuplicates => CMD_SHOW_DUPLICATES,
+            Self::DirStats => CMD_DIR_STATS,
+            Self::CompareTabs => CMD_COMPARE_TABS,
+            Self::XattrList => CMD_XATTR_LIST,
+            Self::XattrSet { .. } => CMD_XATTR_SET,
+            Self::AclEdit => CMD_ACL_EDIT,
+            Self::OpenTerminal => CMD_OPEN_TERMINAL,
+            Self::Dragdrop => CMD_DRAGDROP,
 
             Self::Flat { .. } => CMD_FLAT,
+            Self::ToggleTree => CMD_TOGGLE_TREE,
+            Self::TreeCollapse => CMD_TREE_COLLAPSE,
             Self::NumberedCommand { .. } => CMD_NUMBERED_COMMAND,
 
             Self::Sort(_) => CMD_SORT,
             Self::SortReverse => CMD_SORT_REVERSE,
 
+            Self::SaveFolderSettings => CMD_SAVE_FOLDER_SETTINGS,
+            Self::ClearFolderSettings => CMD_CLEAR_FOLDER_SETTINGS,
+
             Self::FilterGlob { .. } => CMD_FILTER_GLOB,
             Self::FilterRegex { .. } => CMD_FILTER_REGEX,
             Self::FilterString { .. } => CMD_FILTER_STRING,
@@ -88,11 +122,13 @@ impl AppCommand for Command {
             Self::SubProcess { spawn: false, .. } => CMD_SUBPROCESS_FOREGROUND,
             Self::SubProcess { spawn: true, .. } => CMD_SUBPROCESS_BACKGROUND,
             Self::SwitchLineNums(_) => CMD_SWITCH_LINE_NUMBERS,
+            Self::SetSizeFormat(_) => CMD_SIZE_FORMAT,
             Self::SetLineMode(_) => CMD_SET_LINEMODE,
 
             Self::TabSwitch { .. } => CMD_TAB_SWITCH,
             Self::TabSwitchIndex { .. } => CMD_TAB_SWITCH_INDEX,
             Self::ToggleHiddenFiles => CMD_TOGGLE_HIDDEN,
+            Self::ToggleIgnoredFiles => CMD_TOGGLE_IGNORE,
             Self::TouchFile { .. } => CMD_TOUCH_FILE,
 
             Self::SearchFzf => CMD_SEARCH_FZF,
@@ -104,6 +140,11 @@ impl AppCommand for Command {
             Self::CustomSearch(_) => CMD_CUSTOM_SEARCH,
             Self::CustomSearchInteractive(_) => CMD_CUSTOM_SEARCH_INTERACTIVE,
 
+            Self::Script(_) => CMD_SCRIPT,
+
+            Self::Map { .. } => CMD_MAP,
+            Self::Unmap { .. } => CMD_UNMAP,
+
             Self::BookmarkAdd => CMD_BOOKMARK_ADD,
             Self::BookmarkChangeDirectory => CMD_BOOKMARK_CHANGE_DIRECTORY,
         }
@@ -1,9 +1,10 @@
 use crate::context::AppContext;
-use crate::error::AppResult;
+use crate::error::{AppError, AppErrorKind, AppResult};
 use crate::ui::AppBackend;
+use crate::util::expand;
 use crate::{commands::*, config::clean::keymap::AppKeyMapping};
 
-use super::{AppExecute, Command};
+use super::{AppCommand, AppExecute, Command};
 
 impl AppExecute for Command {
     fn execute(
@@ -12,6 +13,28 @@ impl AppExecute for Command {
         backend: &mut AppBackend,
         keymap_t: &AppKeyMapping,
     ) -> AppResult {
+        log::debug!("running `{}`", self);
+        let res = self._execute(context, backend, keymap_t);
+        if let Err(e) = &res {
+            log::warn!("`{}` failed: {}", self, e);
+        }
+        res
+    }
+}
+
+impl Command {
+    fn _execute(
+        &self,
+        context: &mut AppContext,
+        backend: &mut AppBackend,
+        keymap_t: &AppKeyMapping,
+    ) -> AppResult {
+        if context.config_ref().readonly && self.is_mutating() {
+            return Err(AppError::new(
+                AppErrorKind::Io(std::io::ErrorKind::PermissionDenied),
+                format!("{}: not allowed in readonly mode", self.command()),
+            ));
+        }
         match self {
             Self::Escape => escape::escape(context),
             Self::ToggleVisualMode => uimodes::toggle_visual_mode(context),
@@ -24,6 +47,7 @@ impl AppExecute for Command {
             }
             Self::ParentDirectory => change_directory::parent_directory(context),
             Self::PreviousDirectory => change_directory::previous_directory(context),
+            Self::FollowSymlink => follow_symlink::follow_symlink(context),
 
             Self::NewTab { mode } => tab_ops::new_tab(context, mode),
             Self::CloseTab => tab_ops::close_tab(context),
@@ -47,22 +71,27 @@ impl AppExecute for Command {
             Self::CopyDirPath => file_ops::copy_dirpath(context),
             Self::SymlinkFiles { relative: true } => file_ops::symlink_relative(context),
             Self::SymlinkFiles { relative: false } => file_ops::symlink_absolute(context),
-            Self::PasteFiles { options } => file_ops::paste(context, *options),
+            Self::PasteFiles { options } => file_ops::paste(context, options.clone()),
 
             Self::DeleteFiles {
                 background,
                 permanently,
                 noconfirm,
+                shred,
             } => delete_files::delete_selected_files(
                 context,
                 backend,
                 *background,
                 *permanently,
                 *noconfirm,
+                *shred,
             ),
 
             Self::CursorMoveUp { offset } => cursor_move::up(context, *offset),
             Self::CursorMoveDown { offset } => cursor_move::down(context, *offset),
+            Self::CursorMoveTo { index } => cursor_move::to_index(context, *index),
+            Self::ScrollViewUp { offset } => cursor_move::scroll_view(context, -(*offset as isize)),
+            Self::ScrollViewDown { offset } => cursor_move::scroll_view(context, *offset as isize),
             Self::CursorMoveHome => cursor_move::home(context),
             Self::CursorMoveEnd => cursor_move::end(context),
             Self::CursorMovePageUp(p) => cursor_move::page_up(context, backend, *p),
@@ -92,10 +121,18 @@ impl AppExecute for Command {
             Self::OpenFileWith { index: Some(i) } => {
                 open_file::open_with_index(context, backend, *i)
             }
+            Self::OpenAll { command } => {
+                let words = expand::split_shell_words(command)?;
+                open_file::open_all(context, backend, &words)
+            }
 
-            Self::Quit(action) => quit::quit_with_action(context, *action),
+            Self::Quit(action) => quit::quit_with_confirm(context, backend, *action, false),
+            Self::QuitAll(action) => quit::quit_with_confirm(context, backend, *action, true),
+            Self::Suspend => suspend::suspend(context, backend),
 
             Self::ReloadDirList => reload::reload_dirlist(context),
+            Self::ReloadConfig => reload_config::reload_config(context),
+            Self::ClearPreviewCache => preview_cache::clear_preview_cache(context),
             Self::RenameFile { new_name } => rename_file::rename_file(context, new_name.as_path()),
             Self::RenameFileAppend => rename_file::rename_file_append(context, backend, keymap_t),
             Self::RenameFileAppendBase => {
@@ -105,6 +142,12 @@ impl AppExecute for Command {
             Self::RenameFileKeepExt => {
                 rename_file::rename_file_keep_ext(context, backend, keymap_t)
             }
+            Self::RenameRegex { expression } => {
+                rename_regex::rename_regex(context, backend, expression)
+            }
+            Self::RenameTransform { transforms } => {
+                rename_transform::rename_transform(context, backend, transforms)
+            }
             Self::TouchFile { file_name } => touch_file::touch_file(context, file_name),
             Self::SearchGlob { pattern } => search_glob::search_glob(context, pattern.as_str()),
             Self::SearchRegex { pattern } => search_regex::search_regex(context, pattern.as_str()),
@@ -118,6 +161,9 @@ impl AppExecute for Command {
             Self::SearchIncremental { .. } => Ok(()),
             Self::SearchNext => search::search_next(context),
             Self::SearchPrev => search::search_prev(context),
+            Self::JumpToChar => jump_to_char::jump_to_char(context, backend),
+            Self::JumpNext => jump_to_char::jump_next(context),
+            Self::JumpPrev => jump_to_char::jump_prev(context),
 
             Self::SelectGlob { pattern, options } => {
                 select_glob::select_glob(context, pattern, options)
@@ -134,15 +180,36 @@ impl AppExecute for Command {
             } => case_sensitivity::set_case_sensitivity(context, *case_sensitivity, *set_type),
             Self::SetMode => set_mode::set_mode(context, backend),
             Self::ShowTasks => show_tasks::show_tasks(context, backend, keymap_t),
+            Self::Messages => show_messages::show_messages(context, backend, keymap_t),
+            Self::ViewFile { hex } => view_file::view_file(context, backend, keymap_t, *hex),
+            Self::ShowProperties => show_properties::show_properties(context, backend),
+            Self::Checksum { algorithm, copy } => checksum::checksum(context, *algorithm, *copy),
+            Self::VerifyChecksums => checksum::verify_checksums(context),
+            Self::FindDuplicates => find_duplicates::find_duplicates(context),
+            Self::ShowDuplicates => find_duplicates::show_duplicates(context, backend, keymap_t),
+            Self::DirStats => dir_stats::dir_stats(context, backend, keymap_t),
+            Self::CompareTabs => compare_tabs::compare_tabs(context, backend, keymap_t),
+            Self::XattrList => xattr::xattr_list(context, backend),
+            Self::XattrSet { name, value } => xattr::xattr_set(context, name, value),
+            Self::AclEdit => xattr::acl_edit(context, backend),
+            Self::OpenTerminal => open_terminal::open_terminal(context, backend),
+            Self::Dragdrop => dragdrop::dragdrop(context),
             Self::Sort(t) => sort::set_sort(context, *t),
             Self::SetLineMode(mode) => linemode::set_linemode(context, *mode),
             Self::SortReverse => sort::toggle_reverse(context),
-            Self::SubProcess { words, spawn } => {
+
+            Self::SaveFolderSettings => folder_settings::save_folder_settings(context),
+            Self::ClearFolderSettings => folder_settings::clear_folder_settings(context),
+            Self::SubProcess { command, spawn } => {
+                let words = expand::split_shell_words(command)?;
                 sub_process::sub_process(context, backend, words.as_slice(), *spawn)
             }
             Self::SwitchLineNums(d) => line_nums::switch_line_numbering(context, *d),
+            Self::SetSizeFormat(f) => size_format::set_size_format(context, *f),
 
             Self::Flat { depth } => flat::flatten(context, *depth),
+            Self::ToggleTree => tree::toggle_tree(context),
+            Self::TreeCollapse => tree::tree_collapse(context),
             Self::NumberedCommand { initial } => {
                 numbered_command::numbered_command(context, backend, keymap_t, *initial)
             }
@@ -154,12 +221,14 @@ impl AppExecute for Command {
             }
 
             Self::ToggleHiddenFiles => show_hidden::toggle_hidden(context),
+            Self::ToggleIgnoredFiles => show_hidden::toggle_ignored(context),
 
             Self::TabSwitch { offset } => {
                 tab_ops::tab_switch(context, *offset).map_err(|e| e.into())
             }
             Self::TabSwitchIndex { index } => tab_ops::tab_switch_index(context, *index),
             Self::Help => show_help::help_loop(context, backend, keymap_t),
+            Self::CommandPalette => command_palette::command_palette(context, backend, keymap_t),
 
             Self::SearchFzf => search_fzf::search_fzf(context, backend),
             Self::SubdirFzf => subdir_fzf::subdir_fzf(context, backend),
@@ -176,6 +245,10 @@ impl AppExecute for Command {
             Self::CustomSearchInteractive(words) => {
                 custom_search::custom_search(context, backend, words.as_slice(), true)
             }
+            Self::Script(words) => script::run_script(context, backend, keymap_t, words.as_slice()),
+
+            Self::Map { keys, command } => map::map(context, keys, command),
+            Self::Unmap { keys } => map::unmap(context, keys),
         }
     }
 }
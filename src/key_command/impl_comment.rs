@@ -25,6 +25,7 @@ impl CommandComment for Command {
             Self::ChangeDirectory { .. } => "Change directory",
             Self::ParentDirectory => "CD to parent directory",
             Self::PreviousDirectory => "CD to the last dir in history",
+            Self::FollowSymlink => "CD to the target of symlink",
 
             Self::NewTab { .. } => "Open a new tab",
             Self::CloseTab => "Close current tab",
@@ -59,10 +60,14 @@ impl CommandComment for Command {
                 (false, true) => "Paste, skip existing files",
                 _ => "Paste",
             },
+            Self::DeleteFiles { shred: true, .. } => "Shred selected files",
             Self::DeleteFiles { .. } => "Delete selected files",
 
             Self::CursorMoveUp { .. } => "Move cursor up",
             Self::CursorMoveDown { .. } => "Move cursor down",
+            Self::CursorMoveTo { .. } => "Move cursor to entry number",
+            Self::ScrollViewUp { .. } => "Scroll file list up",
+            Self::ScrollViewDown { .. } => "Scroll file list down",
             Self::CursorMoveHome => "Move cursor to the very top",
             Self::CursorMoveEnd => "Move cursor to the ver bottom",
             Self::CursorMovePageUp(_) => "Move cursor one page up",
@@ -81,15 +86,22 @@ impl CommandComment for Command {
             Self::NewDirectory { .. } => "Make a new directory",
             Self::OpenFile => "Open a file",
             Self::OpenFileWith { .. } => "Open using selected program",
+            Self::OpenAll { .. } => "Open all selected files at once",
 
             Self::Quit(_) => "Quit the program",
+            Self::QuitAll(_) => "Quit the program, closing all tabs",
+            Self::Suspend => "Suspend the program",
             Self::ReloadDirList => "Reload current dir listing",
+            Self::ReloadConfig => "Reload config files",
+            Self::ClearPreviewCache => "Clear cached previews",
             Self::RenameFile { .. } => "Rename file",
             Self::TouchFile { .. } => "Touch file",
             Self::RenameFileAppend => "Rename a file",
             Self::RenameFileAppendBase => "Rename a file",
             Self::RenameFilePrepend => "Rename a file",
             Self::RenameFileKeepExt => "Rename a file",
+            Self::RenameRegex { .. } => "Rename selected files with a regex substitution",
+            Self::RenameTransform { .. } => "Change case or sanitize names of selected files",
 
             Self::SearchString { .. } => "Search",
             Self::SearchIncremental { .. } => "Search as you type",
@@ -97,6 +109,9 @@ impl CommandComment for Command {
             Self::SearchRegex { .. } => "Search with regex",
             Self::SearchNext => "Next search entry",
             Self::SearchPrev => "Previous search entry",
+            Self::JumpToChar => "Jump to file beginning with typed characters",
+            Self::JumpNext => "Next file beginning with last typed characters",
+            Self::JumpPrev => "Previous file beginning with last typed characters",
 
             Self::SelectGlob { .. } => "Select files with globbing",
             Self::SelectRegex { .. } => "Select files with regex",
@@ -107,23 +122,47 @@ impl CommandComment for Command {
             Self::SubProcess { spawn: false, .. } => "Run a shell command",
             Self::SubProcess { spawn: true, .. } => "Run command in background",
             Self::ShowTasks => "Show running background tasks",
+            Self::Messages => "Show past messages",
+            Self::ViewFile { hex: false } => "View file in the built-in pager",
+            Self::ViewFile { hex: true } => "View file as a hexdump",
+            Self::ShowProperties => "Show file properties",
+            Self::Checksum { .. } => "Compute checksums of selected files",
+            Self::VerifyChecksums => "Verify checksums of selected files",
+            Self::FindDuplicates => "Find duplicate files in the current directory",
+            Self::ShowDuplicates => "Review duplicate files",
+            Self::DirStats => "Show statistics of the current directory",
+            Self::CompareTabs => "Compare the directories of two tabs",
+            Self::XattrList => "Show extended attributes and ACL of file",
+            Self::XattrSet { .. } => "Set extended attribute of selected files",
+            Self::AclEdit => "Edit ACL of file in text editor",
+            Self::OpenTerminal => "Open a terminal in the current directory",
+            Self::Dragdrop => "Drag and drop selected files",
 
             Self::ToggleHiddenFiles => "Toggle hidden files displaying",
+            Self::ToggleIgnoredFiles => "Toggle hiding files ignored by git",
 
             Self::SwitchLineNums(_) => "Switch line numbering",
+            Self::SetSizeFormat(_) => "Switch file size format",
 
-            Self::Flat { .. } => "Flattern directory list",
+            Self::Flat { .. } => "Flatten directory list",
+            Self::ToggleTree => "Toggle listing the directory as a tree",
+            Self::TreeCollapse => "Collapse directory of the tree",
             Self::NumberedCommand { .. } => "Jump via input number",
 
             Self::Sort(sort_type) => match sort_type {
                 SortType::Lexical => "Sort lexically",
                 SortType::Mtime => "Sort by modification time",
+                SortType::Atime => "Sort by access time",
+                SortType::Ctime => "Sort by status change time",
                 SortType::Natural => "Sort naturally",
                 SortType::Size => "Sort by size",
                 SortType::Ext => "Sort by extension",
             },
             Self::SortReverse => "Reverse sort order",
 
+            Self::SaveFolderSettings => "Remember view settings of the current directory",
+            Self::ClearFolderSettings => "Forget view settings of the current directory",
+
             Self::FilterGlob { .. } => "Filter directory list with globbing",
             Self::FilterRegex { .. } => "Filter directory list with regex",
             Self::FilterString { .. } => "Filter directory list",
@@ -131,6 +170,7 @@ impl CommandComment for Command {
             Self::TabSwitch { .. } => "Switch to the next tab",
             Self::TabSwitchIndex { .. } => "Switch to a given tab",
             Self::Help => "Open this help page",
+            Self::CommandPalette => "Search commands by name and run one",
 
             Self::SearchFzf => "Search via fzf",
             Self::SubdirFzf => "Switch to a child directory via fzf",
@@ -144,6 +184,10 @@ impl CommandComment for Command {
             Self::CustomSearchInteractive(_) => {
                 "Interactively find file based on the custom command"
             }
+            Self::Script(_) => "Run a script",
+
+            Self::Map { .. } => "Bind a key sequence to a command",
+            Self::Unmap { .. } => "Remove a key binding",
         }
     }
 }
@@ -1,3 +1,7 @@
+use termion::event::Event;
+
+use crate::traits::ToString;
+
 use super::{AppCommand, Command};
 
 impl std::fmt::Display for Command {
@@ -9,6 +13,12 @@ impl std::fmt::Display for Command {
             }
             Self::CursorMoveUp { offset } => write!(f, "{} {}", self.command(), offset),
             Self::CursorMoveDown { offset } => write!(f, "{} {}", self.command(), offset),
+            Self::SetSizeFormat(Some(size_format)) => {
+                write!(f, "{} {}", self.command(), size_format.as_str())
+            }
+            Self::CursorMoveTo { index } => write!(f, "{} {}", self.command(), index),
+            Self::ScrollViewUp { offset } => write!(f, "{} {}", self.command(), offset),
+            Self::ScrollViewDown { offset } => write!(f, "{} {}", self.command(), offset),
 
             Self::SetLineMode(mode) => write!(f, "{} {}", self.command(), mode.as_string()),
 
@@ -18,6 +28,8 @@ impl std::fmt::Display for Command {
             Self::PreviewCursorMoveUp { offset } => write!(f, "{} {}", self.command(), offset),
             Self::PreviewCursorMoveDown { offset } => write!(f, "{} {}", self.command(), offset),
 
+            Self::Flat { depth } => write!(f, "{} {}", self.command(), depth),
+
             Self::NewDirectory { path } => write!(f, "{} {:?}", self.command(), path),
 
             Self::SymlinkFiles { relative } => {
@@ -28,10 +40,11 @@ impl std::fmt::Display for Command {
                 background,
                 permanently,
                 noconfirm,
+                shred,
             } => {
                 write!(
                     f,
-                    "{}{}{}{}",
+                    "{}{}{}{}{}",
                     self.command(),
                     if !background {
                         " --foreground=true"
@@ -40,15 +53,50 @@ impl std::fmt::Display for Command {
                     },
                     if *permanently { " --permanently" } else { "" },
                     if *noconfirm { " --noconfirm" } else { "" },
+                    if *shred { " --shred" } else { "" },
                 )
             }
 
             Self::RenameFile { new_name } => write!(f, "{} {:?}", self.command(), new_name),
 
             Self::SearchGlob { pattern } => write!(f, "{} {}", self.command(), pattern),
+            Self::RenameRegex { expression } => write!(f, "{} {}", self.command(), expression),
+            Self::RenameTransform { transforms } => write!(
+                f,
+                "{} {}",
+                self.command(),
+                transforms
+                    .iter()
+                    .map(|t| t.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            ),
             Self::SearchRegex { pattern } => write!(f, "{} {}", self.command(), pattern),
             Self::SearchString { pattern } => write!(f, "{} {}", self.command(), pattern),
-            Self::SubProcess { words, .. } => write!(f, "{} {:?}", self.command(), words),
+            Self::OpenAll { command } if !command.is_empty() => {
+                write!(f, "{} {}", self.command(), command)
+            }
+            Self::SubProcess { command, .. } => write!(f, "{} {}", self.command(), command),
+            Self::Checksum { algorithm, copy } => write!(
+                f,
+                "{} {}{}",
+                self.command(),
+                algorithm.as_str(),
+                if *copy { " --copy" } else { "" }
+            ),
+            Self::XattrSet { name, value } => write!(f, "{} {} {}", self.command(), name, value),
+            Self::ViewFile { hex: true } => write!(f, "{} --hex", self.command()),
+            Self::Script(words) => write!(f, "{} {}", self.command(), words.join(" ")),
+            Self::Map { keys, command } => {
+                write!(
+                    f,
+                    "{} \"{}\" {}",
+                    self.command(),
+                    keys_to_string(keys),
+                    command
+                )
+            }
+            Self::Unmap { keys } => write!(f, "{} \"{}\"", self.command(), keys_to_string(keys)),
             Self::Sort(t) => write!(f, "{} {}", self.command(), t),
             Self::TabSwitch { offset } => write!(f, "{} {}", self.command(), offset),
             Self::TabSwitchIndex { index } => write!(f, "{} {}", self.command(), index),
@@ -56,3 +104,10 @@ impl std::fmt::Display for Command {
         }
     }
 }
+
+fn keys_to_string(keys: &[Event]) -> String {
+    keys.iter()
+        .map(|k| k.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
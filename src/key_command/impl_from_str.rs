@@ -1,16 +1,22 @@
 use std::path;
 
+use termion::event::Event;
+
 use crate::commands::case_sensitivity::SetType;
 use crate::commands::quit::QuitAction;
 use crate::commands::select::SelectOption;
 use crate::config::clean::app::display::line_mode::LineMode;
 use crate::config::clean::app::display::line_number::LineNumberStyle;
 use crate::config::clean::app::display::new_tab::NewTabMode;
+use crate::config::clean::app::display::size_format::SizeFormat;
 use crate::config::clean::app::display::sort_type::SortType;
 use crate::config::clean::app::search::CaseSensitivity;
-use crate::error::{AppError, AppErrorKind};
+use crate::error::{AppError, AppErrorKind, AppResult};
 use crate::io::FileOperationOptions;
-use crate::util::unix;
+use crate::util::checksum::ChecksumAlgorithm;
+use crate::util::expand;
+use crate::util::keyparse::str_to_event;
+use crate::util::name_transform::NameTransform;
 
 use crate::HOME_DIR;
 
@@ -48,6 +54,9 @@ impl std::str::FromStr for Command {
         simple_command_conversion_case!(command, CMD_CLOSE_TAB, Self::CloseTab);
 
         simple_command_conversion_case!(command, CMD_HELP, Self::Help);
+        simple_command_conversion_case!(command, CMD_COMMAND_PALETTE, Self::CommandPalette);
+        simple_command_conversion_case!(command, CMD_FOLLOW_SYMLINK, Self::FollowSymlink);
+        simple_command_conversion_case!(command, CMD_SUSPEND, Self::Suspend);
 
         simple_command_conversion_case!(command, CMD_BOOKMARK_ADD, Self::BookmarkAdd);
         simple_command_conversion_case!(
@@ -84,6 +93,8 @@ impl std::str::FromStr for Command {
         simple_command_conversion_case!(command, CMD_OPEN_FILE, Self::OpenFile);
 
         simple_command_conversion_case!(command, CMD_RELOAD_DIRECTORY_LIST, Self::ReloadDirList);
+        simple_command_conversion_case!(command, CMD_RELOAD_CONFIG, Self::ReloadConfig);
+        simple_command_conversion_case!(command, CMD_CLEAR_PREVIEW_CACHE, Self::ClearPreviewCache);
         simple_command_conversion_case!(command, CMD_RENAME_FILE_APPEND, Self::RenameFileAppend);
         simple_command_conversion_case!(
             command,
@@ -94,10 +105,37 @@ impl std::str::FromStr for Command {
         simple_command_conversion_case!(command, CMD_RENAME_FILE_KEEP_EXT, Self::RenameFileKeepExt);
         simple_command_conversion_case!(command, CMD_SEARCH_NEXT, Self::SearchNext);
         simple_command_conversion_case!(command, CMD_SEARCH_PREV, Self::SearchPrev);
+        simple_command_conversion_case!(command, CMD_JUMP_TO_CHAR, Self::JumpToChar);
+        simple_command_conversion_case!(command, CMD_JUMP_NEXT, Self::JumpNext);
+        simple_command_conversion_case!(command, CMD_JUMP_PREV, Self::JumpPrev);
         simple_command_conversion_case!(command, CMD_SHOW_TASKS, Self::ShowTasks);
+        simple_command_conversion_case!(command, CMD_MESSAGES, Self::Messages);
+        simple_command_conversion_case!(command, CMD_SHOW_PROPERTIES, Self::ShowProperties);
+        simple_command_conversion_case!(command, CMD_VERIFY_CHECKSUMS, Self::VerifyChecksums);
+        simple_command_conversion_case!(command, CMD_FIND_DUPLICATES, Self::FindDuplicates);
+        simple_command_conversion_case!(command, CMD_SHOW_DUPLICATES, Self::ShowDuplicates);
+        simple_command_conversion_case!(command, CMD_DIR_STATS, Self::DirStats);
+        simple_command_conversion_case!(command, CMD_COMPARE_TABS, Self::CompareTabs);
+        simple_command_conversion_case!(command, CMD_XATTR_LIST, Self::XattrList);
+        simple_command_conversion_case!(command, CMD_ACL_EDIT, Self::AclEdit);
+        simple_command_conversion_case!(command, CMD_OPEN_TERMINAL, Self::OpenTerminal);
+        simple_command_conversion_case!(command, CMD_DRAGDROP, Self::Dragdrop);
         simple_command_conversion_case!(command, CMD_SET_MODE, Self::SetMode);
         simple_command_conversion_case!(command, CMD_TOGGLE_HIDDEN, Self::ToggleHiddenFiles);
+        simple_command_conversion_case!(command, CMD_TOGGLE_IGNORE, Self::ToggleIgnoredFiles);
+        simple_command_conversion_case!(command, CMD_TOGGLE_TREE, Self::ToggleTree);
+        simple_command_conversion_case!(command, CMD_TREE_COLLAPSE, Self::TreeCollapse);
         simple_command_conversion_case!(command, CMD_BULK_RENAME, Self::BulkRename);
+        simple_command_conversion_case!(
+            command,
+            CMD_SAVE_FOLDER_SETTINGS,
+            Self::SaveFolderSettings
+        );
+        simple_command_conversion_case!(
+            command,
+            CMD_CLEAR_FOLDER_SETTINGS,
+            Self::ClearFolderSettings
+        );
 
         simple_command_conversion_case!(command, CMD_SEARCH_FZF, Self::SearchFzf);
         simple_command_conversion_case!(
@@ -114,13 +152,65 @@ impl std::str::FromStr for Command {
         simple_command_conversion_case!(command, CMD_ZOXIDE, Self::Zoxide(arg.to_string()));
         simple_command_conversion_case!(command, CMD_ZOXIDE_INTERACTIVE, Self::ZoxideInteractive);
 
-        if command == CMD_QUIT {
+        if command == CMD_CHECKSUM {
+            let mut algorithm = None;
+            let mut copy = false;
+            for arg in arg.split_whitespace() {
+                match arg {
+                    "--copy" => copy = true,
+                    arg => match ChecksumAlgorithm::from_str(arg) {
+                        Some(a) => algorithm = Some(a),
+                        None => {
+                            return Err(AppError::new(
+                                AppErrorKind::InvalidParameters,
+                                format!("{}: Unknown option '{}'", command, arg),
+                            ))
+                        }
+                    },
+                }
+            }
+            Ok(Self::Checksum {
+                algorithm: algorithm.unwrap_or(ChecksumAlgorithm::Sha256),
+                copy,
+            })
+        } else if command == CMD_XATTR_SET {
+            let (name, value) = match arg.find(' ') {
+                Some(i) => (&arg[..i], arg[i..].trim_start()),
+                None => (arg, ""),
+            };
+            if name.is_empty() {
+                return Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: Expected an attribute name", command),
+                ));
+            }
+            Ok(Self::XattrSet {
+                name: name.to_string(),
+                value: value.to_string(),
+            })
+        } else if command == CMD_VIEW_FILE {
             match arg {
-                "--force" => Ok(Self::Quit(QuitAction::Force)),
-                "--output-current-directory" => Ok(Self::Quit(QuitAction::OutputCurrentDirectory)),
-                "--output-selected-files" => Ok(Self::Quit(QuitAction::OutputSelectedFiles)),
-                _ => Ok(Self::Quit(QuitAction::Noop)),
+                "" => Ok(Self::ViewFile { hex: false }),
+                "--hex" => Ok(Self::ViewFile { hex: true }),
+                arg => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: Unknown option '{}'", command, arg),
+                )),
             }
+        } else if command == CMD_QUIT || command == CMD_QUIT_ALL {
+            let action = match arg {
+                "--force" => QuitAction::Force,
+                "--output-current-directory" => QuitAction::OutputCurrentDirectory,
+                "--output-selected-files" => QuitAction::OutputSelectedFiles,
+                _ => QuitAction::Noop,
+            };
+            if command == CMD_QUIT {
+                Ok(Self::Quit(action))
+            } else {
+                Ok(Self::QuitAll(action))
+            }
+        } else if command.strip_suffix('!') == Some(CMD_QUIT) {
+            Ok(Self::Quit(QuitAction::Force))
         } else if command == CMD_NEW_TAB {
             Ok(Self::NewTab {
                 mode: NewTabMode::from_str(arg),
@@ -137,7 +227,7 @@ impl std::str::FromStr for Command {
                 ".." => Ok(Self::ParentDirectory),
                 "-" => Ok(Self::PreviousDirectory),
                 arg => {
-                    let new_path = unix::expand_shell_string(arg);
+                    let new_path = expand::expand_path(arg)?;
                     Ok(Self::ChangeDirectory { path: new_path })
                 }
             }
@@ -149,12 +239,37 @@ impl std::str::FromStr for Command {
                     Err(e) => Err(AppError::new(AppErrorKind::ParseError, e.to_string())),
                 },
             }
+        } else if command == CMD_CURSOR_MOVE_TO {
+            match arg.trim().parse::<usize>() {
+                Ok(index) => Ok(Self::CursorMoveTo { index }),
+                Err(e) => Err(AppError::new(
+                    AppErrorKind::ParseError,
+                    format!("{}: {}", command, e),
+                )),
+            }
+        } else if command == CMD_SCROLL_VIEW_UP || command == CMD_SCROLL_VIEW_DOWN {
+            let offset = match arg {
+                "" => 1,
+                arg => arg
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|e| AppError::new(AppErrorKind::ParseError, e.to_string()))?,
+            };
+            if command == CMD_SCROLL_VIEW_UP {
+                Ok(Self::ScrollViewUp { offset })
+            } else {
+                Ok(Self::ScrollViewDown { offset })
+            }
         } else if command == CMD_CURSOR_MOVE_PAGEUP {
             let p = arg.trim().parse::<f64>().unwrap_or(1.);
             Ok(Self::CursorMovePageUp(p))
         } else if command == CMD_CURSOR_MOVE_PAGEDOWN {
             let p = arg.trim().parse::<f64>().unwrap_or(1.);
             Ok(Self::CursorMovePageDown(p))
+        } else if command == CMD_CURSOR_MOVE_PAGEUP_HALF {
+            Ok(Self::CursorMovePageUp(0.5))
+        } else if command == CMD_CURSOR_MOVE_PAGEDOWN_HALF {
+            Ok(Self::CursorMovePageDown(0.5))
         } else if command == CMD_CURSOR_MOVE_UP {
             match arg {
                 "" => Ok(Self::CursorMoveUp { offset: 1 }),
@@ -213,6 +328,16 @@ impl std::str::FromStr for Command {
                     Err(e) => Err(AppError::new(AppErrorKind::ParseError, e.to_string())),
                 },
             }
+        } else if command == CMD_OPEN_ALL {
+            match shell_words::split(arg) {
+                Ok(_) => Ok(Self::OpenAll {
+                    command: arg.to_string(),
+                }),
+                Err(e) => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: {}", arg, e),
+                )),
+            }
         } else if command == CMD_SYMLINK_FILES {
             let mut relative = false;
             for arg in arg.split_whitespace() {
@@ -261,13 +386,14 @@ impl std::str::FromStr for Command {
             }
             Ok(Self::PasteFiles { options })
         } else if command == CMD_DELETE_FILES {
-            let [mut permanently, mut background, mut noconfirm] = [false; 3];
+            let [mut permanently, mut background, mut noconfirm, mut shred] = [false; 4];
             for arg in arg.split_whitespace() {
                 match arg {
                     "--background=true" => background = true,
                     "--background=false" => background = false,
                     "--permanently" => permanently = true,
                     "--noconfirm" => noconfirm = true,
+                    "--shred" => shred = true,
                     _ => {
                         return Err(AppError::new(
                             AppErrorKind::UnrecognizedArgument,
@@ -280,6 +406,7 @@ impl std::str::FromStr for Command {
                 background,
                 permanently,
                 noconfirm,
+                shred,
             })
         } else if command == CMD_RENAME_FILE {
             match arg {
@@ -316,6 +443,35 @@ impl std::str::FromStr for Command {
                     pattern: arg.to_string(),
                 }),
             }
+        } else if command == CMD_RENAME_REGEX {
+            match arg {
+                "" => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: Expected s/pattern/replacement/", command),
+                )),
+                arg => Ok(Self::RenameRegex {
+                    expression: arg.to_string(),
+                }),
+            }
+        } else if command == CMD_RENAME_TRANSFORM {
+            let transforms = arg
+                .split_whitespace()
+                .map(|arg| {
+                    NameTransform::from_str(arg).ok_or_else(|| {
+                        AppError::new(
+                            AppErrorKind::InvalidParameters,
+                            format!("{}: Unknown transform '{}'", command, arg),
+                        )
+                    })
+                })
+                .collect::<AppResult<Vec<NameTransform>>>()?;
+            if transforms.is_empty() {
+                return Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: Expected at least 1 transform", command),
+                ));
+            }
+            Ok(Self::RenameTransform { transforms })
         } else if command == CMD_SEARCH_REGEX {
             match arg {
                 "" => Err(AppError::new(
@@ -471,9 +627,10 @@ impl std::str::FromStr for Command {
                 )),
             }
         } else if command == CMD_SUBPROCESS_FOREGROUND || command == CMD_SUBPROCESS_BACKGROUND {
+            // variables are expanded when the command is run
             match shell_words::split(arg) {
                 Ok(s) if !s.is_empty() => Ok(Self::SubProcess {
-                    words: s,
+                    command: arg.to_string(),
                     spawn: command == "spawn",
                 }),
                 Ok(_) => Err(AppError::new(
@@ -485,6 +642,41 @@ impl std::str::FromStr for Command {
                     format!("{}: {}", arg, e),
                 )),
             }
+        } else if command == CMD_SCRIPT {
+            match shell_words::split(arg) {
+                Ok(s) if !s.is_empty() => Ok(Self::Script(s)),
+                Ok(_) => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: no script name given", command),
+                )),
+                Err(e) => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: {}", arg, e),
+                )),
+            }
+        } else if command == CMD_MAP {
+            match split_key_sequence(arg) {
+                Some((keys, command_str)) if !command_str.is_empty() => Ok(Self::Map {
+                    keys,
+                    command: Box::new(Self::from_str(command_str)?),
+                }),
+                Some(_) => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: no command given", command),
+                )),
+                None => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: Failed to parse keys '{}'", command, arg),
+                )),
+            }
+        } else if command == CMD_UNMAP {
+            match split_key_sequence(arg) {
+                Some((keys, "")) => Ok(Self::Unmap { keys }),
+                _ => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: Failed to parse keys '{}'", command, arg),
+                )),
+            }
         } else if command == CMD_SORT {
             match arg {
                 "reverse" => Ok(Self::SortReverse),
@@ -518,6 +710,17 @@ impl std::str::FromStr for Command {
             Ok(Self::TouchFile {
                 file_name: arg.to_string(),
             })
+        } else if command == CMD_SIZE_FORMAT {
+            match arg {
+                "" => Ok(Self::SetSizeFormat(None)),
+                arg => match SizeFormat::from_str(arg) {
+                    Some(f) => Ok(Self::SetSizeFormat(Some(f))),
+                    None => Err(AppError::new(
+                        AppErrorKind::InvalidParameters,
+                        format!("{}: Unknown option '{}'", command, arg),
+                    )),
+                },
+            }
         } else if command == CMD_SWITCH_LINE_NUMBERS {
             let policy = match arg {
                 "absolute" | "1" => LineNumberStyle::Absolute,
@@ -525,9 +728,20 @@ impl std::str::FromStr for Command {
                 _ => LineNumberStyle::None,
             };
             Ok(Self::SwitchLineNums(policy))
-        } else if command == CMD_FLAT {
-            match arg.parse::<usize>() {
-                Ok(i) => Ok(Self::Flat { depth: i }),
+        } else if command == CMD_FLAT || command == CMD_FLATTEN {
+            match arg.trim().parse::<usize>() {
+                Ok(i) if i <= u8::MAX as usize => Ok(Self::Flat { depth: i }),
+                Ok(i) => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!("{}: depth {} is larger than {}", command, i, u8::MAX),
+                )),
+                Err(_) if arg.trim().is_empty() => Err(AppError::new(
+                    AppErrorKind::InvalidParameters,
+                    format!(
+                        "{}: expected a depth, 0 lists only the current directory",
+                        command
+                    ),
+                )),
                 Err(e) => Err(AppError::new(
                     AppErrorKind::InvalidParameters,
                     format!("{}: {}", command, e),
@@ -562,3 +776,27 @@ impl std::str::FromStr for Command {
         }
     }
 }
+
+// Splits `"g x" cd /tmp` or `x cd /tmp` into the key sequence and the rest of the arguments.
+// Keys are written the same way as in keymap.toml
+fn split_key_sequence(arg: &str) -> Option<(Vec<Event>, &str)> {
+    let (keys, rest) = match arg.strip_prefix('"') {
+        Some(s) => {
+            let i = s.find('"')?;
+            (&s[..i], s[i + 1..].trim_start())
+        }
+        None => match arg.find(' ') {
+            Some(i) => (&arg[..i], arg[i..].trim_start()),
+            None => (arg, ""),
+        },
+    };
+    let events: Vec<Event> = keys
+        .split_whitespace()
+        .map(str_to_event)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    if events.is_empty() {
+        return None;
+    }
+    Some((events, rest))
+}
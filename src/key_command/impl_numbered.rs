@@ -21,6 +21,16 @@ impl NumberedExecute for Command {
         match self {
             Self::CursorMoveUp { .. } => cursor_move::up(context, number_prefix),
             Self::CursorMoveDown { .. } => cursor_move::down(context, number_prefix),
+            // vim-like `5gg` and `5G`
+            Self::CursorMoveTo { .. } | Self::CursorMoveHome | Self::CursorMoveEnd => {
+                cursor_move::to_index(context, number_prefix)
+            }
+            Self::ScrollViewUp { .. } => {
+                cursor_move::scroll_view(context, -(number_prefix as isize))
+            }
+            Self::ScrollViewDown { .. } => {
+                cursor_move::scroll_view(context, number_prefix as isize)
+            }
             _ => Err(AppError::new(
                 AppErrorKind::UnrecognizedCommand,
                 "Command cannot be prefixed by a number".to_string(),
@@ -17,9 +17,9 @@ mod util;
 
 use std::fs::File;
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 
 use clap::{CommandFactory, Parser, Subcommand};
 use config::clean::app::AppConfig;
@@ -29,22 +29,32 @@ use config::clean::preview::FileEntryPreview;
 use lazy_static::lazy_static;
 
 use config::clean::bookmarks::Bookmarks;
+use config::clean::folder_settings::FolderSettings;
 use config::clean::mimetype::AppProgramRegistry;
 use config::clean::theme::AppTheme;
 use config::{ConfigType, TomlConfigFile};
-use util::cwd;
+use util::timing;
 
 use crate::commands::quit::QuitAction;
+use crate::config::raw::app::display::preview::PreviewProtocol;
 
 use crate::context::AppContext;
-use crate::error::AppError;
+use crate::error::{AppError, AppErrorKind};
+use crate::event::ipc;
 
 const PROGRAM_NAME: &str = "joshuto";
 const CONFIG_HOME: &str = "JOSHUTO_CONFIG_HOME";
 
 lazy_static! {
+    // set by `--config-dir` and `--no-config` before any config is loaded
+    static ref CONFIG_DIR_OVERRIDE: Mutex<Option<Vec<PathBuf>>> = Mutex::new(None);
+
     // dynamically builds the config hierarchy
     static ref CONFIG_HIERARCHY: Vec<PathBuf> = {
+        if let Some(config_dirs) = CONFIG_DIR_OVERRIDE.lock().unwrap().clone() {
+            return config_dirs;
+        }
+
         let mut config_dirs = vec![];
 
         if let Ok(p) = std::env::var(CONFIG_HOME) {
@@ -68,11 +78,18 @@ lazy_static! {
 
         config_dirs
     };
-    static ref THEME_T: AppTheme = AppTheme::get_config();
-    static ref MIMETYPE_T: AppProgramRegistry = AppProgramRegistry::get_config();
-    static ref PREVIEW_T: FileEntryPreview = FileEntryPreview::get_config();
-    static ref BOOKMARKS_T: Mutex<Bookmarks> = Mutex::new(Bookmarks::get_config());
-    static ref ICONS_T: Icons = Icons::get_config();
+    // replaced by `reload_config`
+    // parsed on first use, to keep them out of the way of the first paint
+    static ref THEME_T: RwLock<AppTheme> =
+        RwLock::new(timing::measure("theme.toml", AppTheme::get_config));
+    static ref MIMETYPE_T: RwLock<AppProgramRegistry> =
+        RwLock::new(timing::measure("mimetype.toml", AppProgramRegistry::get_config));
+    static ref PREVIEW_T: FileEntryPreview = timing::measure("preview.toml", FileEntryPreview::get_config);
+    static ref BOOKMARKS_T: Mutex<Bookmarks> =
+        Mutex::new(timing::measure("bookmarks.toml", Bookmarks::get_config));
+    static ref FOLDER_SETTINGS_T: Mutex<FolderSettings> =
+        Mutex::new(timing::measure("folder_settings.toml", FolderSettings::get_config));
+    static ref ICONS_T: Icons = timing::measure("icons.toml", Icons::get_config);
 
     static ref HOME_DIR: Option<PathBuf> = dirs_next::home_dir();
     static ref USERNAME: String = whoami::username();
@@ -106,6 +123,38 @@ pub struct Args {
     #[arg(long = "output-file")]
     output_file: Option<PathBuf>,
 
+    /// Execute a `;` separated list of commands without starting the user interface.
+    #[arg(long = "command")]
+    command: Option<String>,
+
+    /// Act as a file picker: opening a file writes its path to the given file and quits.
+    #[arg(long = "choosefile")]
+    choosefile: Option<PathBuf>,
+
+    /// Write the last visited directory to the given file on quit.
+    #[arg(long = "choosedir")]
+    choosedir: Option<PathBuf>,
+
+    /// Read config files from this directory only.
+    #[arg(long = "config-dir", conflicts_with = "no_config")]
+    config_dir: Option<PathBuf>,
+
+    /// Ignore all config files and use the default configuration.
+    #[arg(long = "no-config")]
+    no_config: bool,
+
+    /// Print how long each phase of the startup took once joshuto exits.
+    #[arg(long = "debug-timing")]
+    debug_timing: bool,
+
+    /// Log up to this level: off, error, warn, info, debug or trace.
+    #[arg(long = "log-level")]
+    log_level: Option<String>,
+
+    /// Refuse commands that change files or run shell commands.
+    #[arg(long = "readonly")]
+    readonly: bool,
+
     #[arg(name = "ARGUMENTS")]
     rest: Vec<PathBuf>,
 }
@@ -121,11 +170,21 @@ pub enum Commands {
         config_type: ConfigType,
     },
 
+    /// Send a command to a running joshuto instance.
+    Remote {
+        /// Socket of the instance, defaults to $JOSHUTO_SOCKET.
+        #[arg(long = "socket")]
+        socket: Option<PathBuf>,
+
+        /// Command to execute, e.g. "cd /tmp".
+        command: String,
+    },
+
     /// Print 'joshuto' build version.
     Version,
 }
 
-fn run_main(args: Args) -> Result<i32, AppError> {
+fn run_main(mut args: Args) -> Result<i32, AppError> {
     if let Some(command) = args.commands {
         let result = match command {
             Commands::Completions { shell } => {
@@ -141,6 +200,15 @@ fn run_main(args: Args) -> Result<i32, AppError> {
                     Ok(0)
                 }
             },
+            Commands::Remote { socket, command } => {
+                let socket =
+                    match socket.or_else(|| std::env::var_os(ipc::SOCKET_ENV).map(PathBuf::from)) {
+                        Some(socket) => socket,
+                        None => return AppError::fail(format!("{} is not set", ipc::SOCKET_ENV)),
+                    };
+                ipc::send_command(&socket, &command)?;
+                Ok(0)
+            }
             Commands::Version => print_version(),
         };
         return result;
@@ -150,63 +218,124 @@ fn run_main(args: Args) -> Result<i32, AppError> {
         return print_version();
     }
 
-    if let Some(path) = args.rest.first() {
-        cwd::set_current_dir(path)?;
+    if args.debug_timing {
+        timing::enable();
     }
 
-    // make sure all configs have been loaded before starting
-    let config = AppConfig::get_config();
-    let keymap = AppKeyMapping::get_config();
-    lazy_static::initialize(&THEME_T);
-    lazy_static::initialize(&MIMETYPE_T);
-    lazy_static::initialize(&PREVIEW_T);
-    lazy_static::initialize(&BOOKMARKS_T);
-    lazy_static::initialize(&ICONS_T);
+    if args.no_config {
+        *CONFIG_DIR_OVERRIDE.lock().unwrap() = Some(vec![]);
+    } else if let Some(config_dir) = args.config_dir.as_ref() {
+        if !config_dir.is_dir() {
+            return AppError::fail(format!("{}: not a directory", config_dir.display()));
+        }
+        *CONFIG_DIR_OVERRIDE.lock().unwrap() = Some(vec![config_dir.canonicalize()?]);
+    }
+
+    // joshuto changes the process' working directory as the user navigates,
+    // so relative output files have to be resolved before anything else happens
+    let output_files = [
+        args.output_file.as_mut(),
+        args.choosefile.as_mut(),
+        args.choosedir.as_mut(),
+    ];
+    for output_file in output_files.into_iter().flatten() {
+        if output_file.is_relative() {
+            *output_file = std::env::current_dir()?.join(&output_file);
+        }
+    }
 
-    lazy_static::initialize(&HOME_DIR);
-    lazy_static::initialize(&USERNAME);
-    lazy_static::initialize(&HOSTNAME);
+    // records made while the configs are read are kept until the log file is known
+    let log_level = match args.log_level.as_deref() {
+        Some(level) => Some(level.parse().map_err(|_| {
+            AppError::new(
+                AppErrorKind::InvalidParameters,
+                format!("{}: not a log level", level),
+            )
+        })?),
+        None => None,
+    };
+    util::logging::init(log_level.unwrap_or(log::LevelFilter::Trace));
+
+    // the other configs are parsed on first use
+    let mut config = timing::measure("joshuto.toml", AppConfig::get_config);
+    config.readonly |= args.readonly;
+    let keymap = timing::measure("keymap.toml", || {
+        AppKeyMapping::get_config_with_aliases(&config.cmd_aliases)
+    });
+    if let Some(level) = log_level {
+        config.log_level = level;
+    }
+    util::logging::set_file(config.log_file.clone(), config.log_level);
+    log::info!("joshuto {} started", env!("CARGO_PKG_VERSION"));
+    util::crash::install_panic_hook(config.crash_log.clone());
+    util::format::set_size_format(config.display_options_ref().size_format());
+    ui::AppBackend::set_colors(config.display_options_ref().colors().enabled());
+    if config.display_options_ref().high_contrast() {
+        *THEME_T.write().unwrap() = AppTheme::high_contrast();
+    }
+
+    timing::measure("user and host names", || {
+        lazy_static::initialize(&HOME_DIR);
+        lazy_static::initialize(&USERNAME);
+        lazy_static::initialize(&HOSTNAME);
+    });
+
+    if let Some(script) = args.command.as_ref() {
+        // image protocol detection talks to the terminal, which we don't have
+        config._preview_options_mut().preview_protocol = PreviewProtocol::Disabled;
+
+        let mut context = timing::measure("context", || AppContext::new(config, args.clone()));
+        let mut backend = ui::AppBackend::headless();
+        run::run_headless(&mut backend, &mut context, keymap, script)?;
+        run_quit(&args, &context)?;
+        if timing::is_enabled() {
+            eprint!("{}", timing::report());
+        }
+        return match context.quit {
+            QuitAction::DoNot => Ok(0),
+            quit => Ok(quit.exit_code()),
+        };
+    }
 
-    let mut context = AppContext::new(config, args.clone());
+    let mut context = timing::measure("context", || AppContext::new(config, args.clone()));
     {
-        let mut backend: ui::AppBackend = ui::AppBackend::new(context.config_ref().mouse_support)?;
+        let mut backend: ui::AppBackend = timing::measure("terminal", || {
+            ui::AppBackend::new(context.config_ref().mouse_support)
+        })?;
         run::run_loop(&mut backend, &mut context, keymap)?;
     }
     run_quit(&args, &context)?;
+    // the terminal is restored, so the report can be printed
+    if timing::is_enabled() {
+        eprint!("{}", timing::report());
+    }
     Ok(context.quit.exit_code())
 }
 
 fn run_quit(args: &Args, context: &AppContext) -> Result<(), AppError> {
+    if let Some(output_path) = &args.choosedir {
+        if context.quit != QuitAction::Force {
+            write_current_directory(output_path, context)?;
+        }
+    }
+    if let Some(output_path) = &args.choosefile {
+        if context.quit == QuitAction::OutputSelectedFiles {
+            write_selected_files(output_path, context)?;
+        }
+    }
+
     match &args.output_file {
         Some(output_path) => match context.quit {
-            QuitAction::OutputCurrentDirectory => {
-                let curr_path = context.tab_context_ref().curr_tab_ref().cwd();
-                let mut file = File::create(output_path)?;
-                file.write_all(curr_path.as_os_str().to_string_lossy().as_bytes())?;
-                file.write_all("\n".as_bytes())?;
-            }
-            QuitAction::OutputSelectedFiles => {
-                let curr_tab = context.tab_context_ref().curr_tab_ref();
-                let selected_files = curr_tab
-                    .curr_list_ref()
-                    .into_iter()
-                    .flat_map(|s| s.get_selected_paths());
-                let mut f = File::create(output_path)?;
-                for file in selected_files {
-                    writeln!(f, "{}", file.display())?;
-                }
-            }
+            QuitAction::OutputCurrentDirectory => write_current_directory(output_path, context)?,
+            QuitAction::OutputSelectedFiles => write_selected_files(output_path, context)?,
             _ => {}
         },
         None => match context.quit {
             QuitAction::OutputCurrentDirectory => {
-                let curr_path = std::env::current_dir()?;
-                eprintln!(
-                    "{}",
-                    curr_path.into_os_string().as_os_str().to_string_lossy()
-                );
+                let curr_path = context.tab_context_ref().curr_tab_ref().cwd();
+                eprintln!("{}", curr_path.as_os_str().to_string_lossy());
             }
-            QuitAction::OutputSelectedFiles => {
+            QuitAction::OutputSelectedFiles if args.choosefile.is_none() => {
                 let curr_tab = context.tab_context_ref().curr_tab_ref();
                 let selected_files = curr_tab
                     .curr_list_ref()
@@ -222,6 +351,27 @@ fn run_quit(args: &Args, context: &AppContext) -> Result<(), AppError> {
     Ok(())
 }
 
+fn write_current_directory(output_path: &Path, context: &AppContext) -> Result<(), AppError> {
+    let curr_path = context.tab_context_ref().curr_tab_ref().cwd();
+    let mut file = File::create(output_path)?;
+    file.write_all(curr_path.as_os_str().to_string_lossy().as_bytes())?;
+    file.write_all("\n".as_bytes())?;
+    Ok(())
+}
+
+fn write_selected_files(output_path: &Path, context: &AppContext) -> Result<(), AppError> {
+    let curr_tab = context.tab_context_ref().curr_tab_ref();
+    let selected_files = curr_tab
+        .curr_list_ref()
+        .into_iter()
+        .flat_map(|s| s.get_selected_paths());
+    let mut f = File::create(output_path)?;
+    for file in selected_files {
+        writeln!(f, "{}", file.display())?;
+    }
+    Ok(())
+}
+
 fn print_version() -> Result<i32, AppError> {
     writeln!(
         &mut std::io::stdout(),
@@ -235,8 +385,12 @@ fn main() {
     let args = Args::parse();
 
     match run_main(args) {
-        Ok(exit_code) => process::exit(exit_code),
+        Ok(exit_code) => {
+            log::info!("joshuto exited with code {}", exit_code);
+            process::exit(exit_code)
+        }
         Err(e) => {
+            log::error!("joshuto exited: {}", e);
             eprintln!("{}", e);
             process::exit(1);
         }
@@ -245,9 +399,61 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+
     use clap::Parser;
+    use lazy_static::lazy_static;
+
+    use crate::commands::quit::QuitAction;
+    use crate::config::clean::app::AppConfig;
+    use crate::config::clean::keymap::AppKeyMapping;
+    use crate::config::raw::app::display::preview::PreviewProtocol;
+    use crate::context::AppContext;
+    use crate::error::AppResult;
+    use crate::{run, run_quit, ui, Args, Commands};
+
+    lazy_static! {
+        // the commands change the working directory of the whole process
+        static ref CWD_LOCK: Mutex<()> = Mutex::new(());
+    }
 
-    use crate::{Args, Commands};
+    /// Creates a directory with a `sub` directory and the files `a.txt` and `b.txt`,
+    /// returned with its canonical path
+    fn create_test_dir() -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().canonicalize().unwrap();
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.txt"), "a").unwrap();
+        fs::write(dir.join("b.txt"), "b").unwrap();
+        (tmp, dir)
+    }
+
+    /// Runs the `--command` script of `args` in `dir` like `run_main` does
+    /// and writes the output files
+    fn run_script(dir: &Path, args: &[&str]) -> AppResult<QuitAction> {
+        let _lock = CWD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let cwd = std::env::current_dir()?;
+
+        let mut args = Args::parse_from(["program_name"].iter().chain(args));
+        args.rest = vec![dir.to_path_buf()];
+        let mut config = AppConfig::default();
+        config._preview_options_mut().preview_protocol = PreviewProtocol::Disabled;
+        let mut context = AppContext::new(config, args.clone());
+        let mut backend = ui::AppBackend::headless();
+        let script = args.command.clone().unwrap_or_default();
+        let res = run::run_headless(
+            &mut backend,
+            &mut context,
+            AppKeyMapping::default(),
+            &script,
+        )
+        .and_then(|_| run_quit(&args, &context));
+
+        std::env::set_current_dir(cwd)?;
+        res.map(|_| context.quit)
+    }
 
     #[test]
     fn test_command_new() {
@@ -262,6 +468,173 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_command_output_file() {
+        let args = Args::parse_from(["program_name", "--output-file", "/tmp/out"]);
+        assert_eq!(args.output_file, Some(PathBuf::from("/tmp/out")));
+    }
+
+    /// Runs `script` with `--output-file sub/out`, returning the quit action and the output
+    fn run_with_output_file(dir: &Path, script: &str) -> (QuitAction, Option<String>) {
+        let output = dir.join("sub").join("out");
+        let output_arg = output.to_str().unwrap();
+        let quit = run_script(dir, &["--output-file", output_arg, "--command", script]).unwrap();
+        (quit, fs::read_to_string(&output).ok())
+    }
+
+    #[test]
+    fn test_output_current_directory() {
+        let (_tmp, dir) = create_test_dir();
+        let (quit, output) = run_with_output_file(&dir, "cd sub; quit --output-current-directory");
+        assert_eq!(quit, QuitAction::OutputCurrentDirectory);
+        assert_eq!(output, Some(format!("{}\n", dir.join("sub").display())));
+    }
+
+    #[test]
+    fn test_output_selected_files() {
+        let (_tmp, dir) = create_test_dir();
+        let (quit, output) =
+            run_with_output_file(&dir, "select_glob *.txt; quit --output-selected-files");
+        assert_eq!(quit, QuitAction::OutputSelectedFiles);
+        assert_eq!(
+            output,
+            Some(format!(
+                "{}\n{}\n",
+                dir.join("a.txt").display(),
+                dir.join("b.txt").display()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_output_nothing_without_output_action() {
+        let (_tmp, dir) = create_test_dir();
+        let (quit, output) = run_with_output_file(&dir, "quit");
+        assert_eq!(quit, QuitAction::Noop);
+        assert_eq!(output, None);
+    }
+
+    #[test]
+    fn test_command_choosers() {
+        let args = Args::parse_from([
+            "program_name",
+            "--choosefile",
+            "/tmp/file",
+            "--choosedir",
+            "/tmp/dir",
+        ]);
+        assert_eq!(args.choosefile, Some(PathBuf::from("/tmp/file")));
+        assert_eq!(args.choosedir, Some(PathBuf::from("/tmp/dir")));
+    }
+
+    /// Runs `script` with `--choosefile sub/file` and `--choosedir sub/dir`,
+    /// returning the quit action and the contents of both files
+    fn run_with_choosers(dir: &Path, script: &str) -> (QuitAction, Option<String>, Option<String>) {
+        let choosefile = dir.join("sub").join("file");
+        let choosedir = dir.join("sub").join("dir");
+        let quit = run_script(
+            dir,
+            &[
+                "--choosefile",
+                choosefile.to_str().unwrap(),
+                "--choosedir",
+                choosedir.to_str().unwrap(),
+                "--command",
+                script,
+            ],
+        )
+        .unwrap();
+        (
+            quit,
+            fs::read_to_string(&choosefile).ok(),
+            fs::read_to_string(&choosedir).ok(),
+        )
+    }
+
+    #[test]
+    fn test_choosedir_written_on_quit() {
+        let (_tmp, dir) = create_test_dir();
+        let (quit, file, chosen_dir) = run_with_choosers(&dir, "cd sub; quit");
+        assert_eq!(quit, QuitAction::Noop);
+        assert_eq!(file, None);
+        assert_eq!(chosen_dir, Some(format!("{}\n", dir.join("sub").display())));
+    }
+
+    #[test]
+    fn test_choosefile_written_with_selected_files() {
+        let (_tmp, dir) = create_test_dir();
+        let (quit, file, chosen_dir) =
+            run_with_choosers(&dir, "select_glob b.txt; quit --output-selected-files");
+        assert_eq!(quit, QuitAction::OutputSelectedFiles);
+        assert_eq!(file, Some(format!("{}\n", dir.join("b.txt").display())));
+        assert_eq!(chosen_dir, Some(format!("{}\n", dir.display())));
+    }
+
+    #[test]
+    fn test_choosers_not_written_on_forced_quit() {
+        let (_tmp, dir) = create_test_dir();
+        let (quit, file, chosen_dir) = run_with_choosers(&dir, "quit --force");
+        assert_eq!(quit, QuitAction::Force);
+        assert_eq!(file, None);
+        assert_eq!(chosen_dir, None);
+    }
+
+    #[test]
+    fn test_command_headless() {
+        let args = Args::parse_from(["program_name", "--command", "cd /tmp; toggle_hidden"]);
+        assert_eq!(args.command.as_deref(), Some("cd /tmp; toggle_hidden"));
+    }
+
+    #[test]
+    fn test_headless_stops_at_quit() {
+        let (_tmp, dir) = create_test_dir();
+        let quit = run_script(
+            &dir,
+            &[
+                "--command",
+                "mkdir new; cd new; touch c.txt; quit; touch d.txt",
+            ],
+        )
+        .unwrap();
+        assert_eq!(quit, QuitAction::Noop);
+        assert!(dir.join("new").join("c.txt").is_file());
+        assert!(!dir.join("new").join("d.txt").exists());
+    }
+
+    #[test]
+    fn test_headless_without_quit() {
+        let (_tmp, dir) = create_test_dir();
+        let quit = run_script(&dir, &["--command", "touch d.txt;"]).unwrap();
+        assert_eq!(quit, QuitAction::DoNot);
+        assert!(dir.join("d.txt").is_file());
+    }
+
+    #[test]
+    fn test_headless_fails_on_unknown_command() {
+        let (_tmp, dir) = create_test_dir();
+        assert!(run_script(&dir, &["--command", "touch e.txt; no_such_command"]).is_err());
+        assert!(dir.join("e.txt").is_file());
+    }
+
+    #[test]
+    fn test_headless_refuses_interactive_commands() {
+        let (_tmp, dir) = create_test_dir();
+        assert!(run_script(&dir, &["--command", "show_tasks; touch f.txt"]).is_err());
+        assert!(run_script(&dir, &["--command", "open; touch f.txt"]).is_err());
+        assert!(!dir.join("f.txt").exists());
+    }
+
+    #[test]
+    fn test_command_remote() {
+        match Args::parse_from(["program_name", "remote", "cd /tmp"]).commands {
+            Some(Commands::Remote {
+                socket: None,
+                command,
+            }) => assert_eq!(command, "cd /tmp"),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn test_command_completions() {
         for shell in ["bash", "zsh", "fish", "elvish", "powershell"] {
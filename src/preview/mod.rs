@@ -1,3 +1,4 @@
+pub mod preview_cache;
 pub mod preview_default;
 pub mod preview_dir;
 pub mod preview_file;
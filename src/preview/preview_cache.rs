@@ -0,0 +1,221 @@
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::util::checksum::ChecksumAlgorithm;
+use crate::util::digest::Digest;
+
+/// Previews and image thumbnails stored on disk, keyed by the path and modification
+/// time of the previewed file and the program generating the preview. Entries older than `ttl` are ignored and the least
+/// recently written entries are removed once the cache grows past `max_size` bytes
+#[derive(Clone, Debug)]
+pub struct PreviewCache {
+    dir: PathBuf,
+    max_size: u64,
+    ttl: Option<Duration>,
+}
+
+impl PreviewCache {
+    /// Uses `$XDG_CACHE_HOME/joshuto/previews`, `None` if the cache is disabled
+    /// with a `max_size` of 0 or there is no cache directory
+    pub fn new(max_size: u64, ttl: Option<Duration>) -> Option<Self> {
+        if max_size == 0 {
+            return None;
+        }
+        let mut dir = dirs_next::cache_dir()?;
+        dir.push(env!("CARGO_PKG_NAME"));
+        dir.push("previews");
+        Some(Self::with_dir(dir, max_size, ttl))
+    }
+
+    pub fn with_dir(dir: PathBuf, max_size: u64, ttl: Option<Duration>) -> Self {
+        Self { dir, max_size, ttl }
+    }
+
+    /// Identifies the `kind` of preview of `path` as long as neither the file nor
+    /// the `generator` program making the preview are modified,
+    /// `variant` distinguishes previews of different sizes.
+    /// Keys stay the same across joshuto versions, as long as the inputs do
+    pub fn key(path: &Path, generator: Option<&Path>, kind: &str, variant: &str) -> Option<String> {
+        let mut digest = Digest::new(ChecksumAlgorithm::Sha256);
+        hash_file_version(&mut digest, path)?;
+        if let Some(generator) = generator {
+            hash_file_version(&mut digest, generator)?;
+        }
+        digest.update(variant.as_bytes());
+        Some(format!("{}-{}", kind, digest.finish_hex()))
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let path = self.dir.join(key);
+        if let Some(ttl) = self.ttl {
+            let written = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            let age = SystemTime::now()
+                .duration_since(written)
+                .unwrap_or_default();
+            if age > ttl {
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+        }
+        fs::read(path).ok()
+    }
+
+    pub fn put(&self, key: &str, data: &[u8]) -> io::Result<()> {
+        if data.len() as u64 > self.max_size {
+            return Ok(());
+        }
+        // previews show the content of files, which may be private
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&self.dir)?;
+        // write to a temporary file first, so readers never see partial entries
+        let tmp_path = self.dir.join(format!(".{}.tmp", key));
+        fs::write(&tmp_path, data)?;
+        fs::rename(&tmp_path, self.dir.join(key))?;
+        self.shrink()
+    }
+
+    /// Removes the oldest entries until the cache fits into `max_size`
+    fn shrink(&self) -> io::Result<()> {
+        let mut entries = Vec::new();
+        let mut total_size = 0;
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            total_size += metadata.len();
+            entries.push((metadata.modified()?, metadata.len(), entry.path()));
+        }
+        if total_size <= self.max_size {
+            return Ok(());
+        }
+        entries.sort();
+        for (_, size, path) in entries {
+            if total_size <= self.max_size {
+                break;
+            }
+            fs::remove_file(path)?;
+            total_size -= size;
+        }
+        Ok(())
+    }
+
+    /// Removes every entry, returns the number of bytes freed
+    pub fn clear(&self) -> io::Result<u64> {
+        let mut freed = 0;
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            freed += entry.metadata()?.len();
+            fs::remove_file(entry.path())?;
+        }
+        Ok(freed)
+    }
+}
+
+// the path, modification time and size of `path`, each followed by a separator
+fn hash_file_version(digest: &mut Digest, path: &Path) -> Option<()> {
+    let metadata = fs::metadata(path).ok()?;
+    let mtime = metadata
+        .modified()
+        .ok()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?;
+    digest.update(path.as_os_str().as_bytes());
+    digest.update(&[0]);
+    digest.update(&mtime.as_secs().to_le_bytes());
+    digest.update(&mtime.subsec_nanos().to_le_bytes());
+    digest.update(&metadata.len().to_le_bytes());
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(dir: &tempfile::TempDir, max_age: Option<Duration>) -> PreviewCache {
+        PreviewCache::with_dir(dir.path().join("cache"), 10, max_age)
+    }
+
+    fn make_old(path: &Path) {
+        let old = SystemTime::now() - Duration::from_secs(60);
+        filetime::set_file_mtime(path, filetime::FileTime::from_system_time(old)).unwrap();
+    }
+
+    #[test]
+    fn cached_previews_are_returned() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache(&dir, None);
+        cache.put("a", b"12345").unwrap();
+        assert_eq!(cache.get("a").unwrap(), b"12345");
+        assert_eq!(cache.get("b"), None);
+    }
+
+    #[test]
+    fn previews_larger_than_the_cache_are_not_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache(&dir, None);
+        cache.put("big", &[0; 11]).unwrap();
+        assert_eq!(cache.get("big"), None);
+    }
+
+    #[test]
+    fn oldest_previews_are_dropped_past_the_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache(&dir, None);
+        cache.put("a", b"12345").unwrap();
+        make_old(&dir.path().join("cache/a"));
+        cache.put("c", b"123456").unwrap();
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("c").unwrap(), b"123456");
+    }
+
+    #[test]
+    fn expired_previews_are_not_returned() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache(&dir, Some(Duration::from_secs(1)));
+        cache.put("c", b"123456").unwrap();
+        make_old(&dir.path().join("cache/c"));
+        assert_eq!(cache.get("c"), None);
+    }
+
+    #[test]
+    fn keys_change_with_the_file_or_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        let script = dir.path().join("script");
+        fs::write(&file, "a").unwrap();
+        fs::write(&script, "b").unwrap();
+        let key = PreviewCache::key(&file, Some(&script), "script", "80x24").unwrap();
+        assert_eq!(
+            PreviewCache::key(&file, Some(&script), "script", "80x24"),
+            Some(key.clone())
+        );
+        assert_ne!(
+            PreviewCache::key(&file, None, "script", "80x24"),
+            Some(key.clone())
+        );
+        make_old(&script);
+        assert_ne!(
+            PreviewCache::key(&file, Some(&script), "script", "80x24"),
+            Some(key)
+        );
+    }
+
+    #[test]
+    fn clear_removes_all_previews() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache(&dir, None);
+        cache.put("d", b"1").unwrap();
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert_eq!(cache.get("d"), None);
+    }
+}
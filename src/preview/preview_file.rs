@@ -1,5 +1,14 @@
 use std::fmt::Debug;
-use std::{process::Output, time};
+use std::fs;
+use std::io::{self, Read};
+use std::os::unix::process::ExitStatusExt;
+use std::path::Path;
+use std::{process::ExitStatus, process::Output, time};
+
+use crate::util::{format, syntax};
+
+// number of bytes shown by hexdump previews
+const HEX_PREVIEW_SIZE: u64 = 64 * 1024;
 
 pub enum PreviewFileState {
     Loading,
@@ -39,3 +48,50 @@ impl std::convert::From<Output> for FilePreview {
         }
     }
 }
+
+impl FilePreview {
+    /// Preview showing `output`, for previews not produced by running the preview script
+    pub fn from_string(output: String) -> Self {
+        Self {
+            status: ExitStatus::from_raw(0),
+            output,
+            index: 0,
+            modified: time::SystemTime::now(),
+        }
+    }
+
+    /// Source code in `path` with syntax highlighting, if it is recognized
+    pub fn highlighted(path: &Path, max_size: u64) -> io::Result<Option<Self>> {
+        let mut bytes = Vec::new();
+        fs::File::open(path)?
+            .take(max_size)
+            .read_to_end(&mut bytes)?;
+        if is_binary(&bytes) {
+            return Ok(None);
+        }
+        let text = String::from_utf8_lossy(&bytes).replace('\t', "        ");
+        Ok(syntax::highlight_ansi(path, &text).map(Self::from_string))
+    }
+
+    /// Hexdump of the start of `path`, if it is a binary file
+    pub fn hexdump(path: &Path) -> io::Result<Option<Self>> {
+        let mut bytes = Vec::new();
+        fs::File::open(path)?
+            .take(HEX_PREVIEW_SIZE)
+            .read_to_end(&mut bytes)?;
+        if !is_binary(&bytes) {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            status: ExitStatus::from_raw(0),
+            output: format::hexdump(&bytes, 0).join("\n"),
+            index: 0,
+            modified: time::SystemTime::now(),
+        }))
+    }
+}
+
+/// Same heuristic as most pagers: text files don't contain NUL bytes
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(8192).any(|&b| b == 0)
+}
@@ -1,78 +1,362 @@
 use crate::commands::quit::QuitAction;
+use crate::commands::{cursor_move, show_messages, suspend};
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
 use crate::config::clean::keymap::AppKeyMapping;
+use crate::config::problems;
 use crate::context::AppContext;
+use crate::error::{AppError, AppErrorKind, AppResult};
+use crate::event::ipc::IpcServer;
 use crate::event::process_event;
 use crate::event::AppEvent;
-use crate::key_command::{AppExecute, CommandKeybind};
+use crate::fs::metadata_loader;
+use crate::key_command::{AppCommand, AppExecute, Command, CommandKeybind};
 use crate::preview::preview_default;
 use crate::tab::JoshutoTab;
 use crate::traits::ToString;
 use crate::ui;
 use crate::ui::views;
 use crate::ui::views::TuiView;
+use crate::ui::widgets::TuiDirListLoading;
 use crate::ui::AppBackend;
+use crate::util::hooks::{self, Hook};
+use crate::util::{cwd, timing, title};
 
 use uuid::Uuid;
 
 use ratatui::layout::Rect;
+use signal_hook::consts::signal;
 use termion::event::Event;
 
+// redraws are limited to about 60 per second
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
 pub fn run_loop(
     backend: &mut ui::AppBackend,
     context: &mut AppContext,
-    keymap_t: AppKeyMapping,
+    mut keymap_t: AppKeyMapping,
 ) -> std::io::Result<()> {
     let curr_path = std::env::current_dir()?;
 
+    // removes the socket once we leave the main loop
+    let _ipc_server = match IpcServer::start(context.clone_event_tx()) {
+        Ok(server) => Some(server),
+        Err(e) => {
+            context
+                .message_queue_mut()
+                .push_warning(format!("Failed to start ipc server: {}", e));
+            None
+        }
+    };
+
     if let Ok(area) = backend.terminal_ref().size() {
         // pre-calculate some ui attributes
         calculate_ui_context(context, area);
     }
 
+    // something to look at while the first directories are read
+    backend.render(TuiDirListLoading::new());
+    timing::mark("loading screen");
+
     {
-        let id = Uuid::new_v4();
         // Initialize an initial tab
-        let tab = JoshutoTab::new(
-            curr_path,
-            context.ui_context_ref(),
-            context.config_ref().display_options_ref(),
-        )?;
-        context.tab_context_mut().insert_tab(id, tab);
+        timing::measure("first directory load", || init_tabs(context, curr_path))?;
 
         // trigger a preview of child
         preview_default::load_preview(context, backend);
     }
 
+    // mistakes in the config files are listed before anything else
+    let config_problems = problems::take_problems();
+    if !config_problems.is_empty() {
+        for problem in config_problems {
+            context
+                .message_queue_mut()
+                .push_warning(problem.to_string());
+        }
+        let _ = show_messages::show_messages(context, backend, &keymap_t);
+    }
+    hooks::call_hook(context, Hook::Startup);
+    let mut last_cwd = context.tab_context_ref().curr_tab_ref().cwd().to_path_buf();
+
+    let update_title = context.config_ref().update_title;
+    let tilde_in_titlebar = context
+        .config_ref()
+        .display_options_ref()
+        .tilde_in_titlebar();
+    if update_title {
+        title::push_title();
+        title::set_title(last_cwd.as_path(), tilde_in_titlebar);
+    }
+
+    let mut last_render: Option<Instant> = None;
     while context.quit == QuitAction::DoNot {
-        // do the ui
-        if let Ok(area) = backend.terminal_ref().size() {
-            // pre-calculate some ui attributes
-            calculate_ui_context(context, area);
+        // events arriving within a frame of the last render (e.g. a held down key)
+        // are processed before drawing again
+        let elapsed = last_render.map_or(FRAME_INTERVAL, |t| t.elapsed());
+        let queued_event = if elapsed < FRAME_INTERVAL {
+            context.poll_event_timeout(FRAME_INTERVAL - elapsed).ok()
+        } else {
+            None
+        };
+
+        let event = match queued_event {
+            Some(event) => event,
+            None => {
+                let dir_cache_size = context.config_ref().dir_cache_size;
+                for (_, tab) in context.tab_context_mut().iter_mut() {
+                    tab.shrink_history(dir_cache_size);
+                }
+                metadata_loader::load_pending(context);
 
-            // render the ui
-            backend.render(TuiView::new(context));
+                // do the ui
+                if let Ok(area) = backend.terminal_ref().size() {
+                    // pre-calculate some ui attributes
+                    calculate_ui_context(context, area);
 
-            // invoke preview hooks, if appropriate
-            context.update_external_preview();
-        }
+                    // render the ui
+                    backend.render(TuiView::new(context));
+                    if last_render.is_none() {
+                        timing::mark("first paint");
+                    }
+                    last_render = Some(Instant::now());
 
-        // wait for an event and pop it
-        let event = match context.poll_event() {
-            Ok(event) => event,
-            Err(_) => return Ok(()), // TODO
+                    // invoke preview hooks, if appropriate
+                    context.update_external_preview();
+                }
+
+                // wait for an event and pop it,
+                // dropping the current message once it has been shown long enough
+                let time_left = context
+                    .config_ref()
+                    .message_timeout
+                    .and_then(|timeout| context.message_queue_ref().time_left(timeout));
+                match time_left {
+                    Some(time_left) => match context.poll_event_timeout(time_left) {
+                        Ok(event) => event,
+                        Err(mpsc::RecvTimeoutError::Timeout) => {
+                            context.message_queue_mut().pop_front();
+                            continue;
+                        }
+                        Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+                    },
+                    None => match context.poll_event() {
+                        Ok(event) => event,
+                        Err(_) => return Ok(()), // TODO
+                    },
+                }
+            }
         };
 
         // update the file system supervisor that watches for changes in the FS
-        if context.config_ref().watch_files {
+        if context.config_ref().watch_files
+            || context.config_ref().watch_config
+            || context.config_ref().preview_options_ref().watch_preview
+        {
             context.update_watcher();
         }
 
         // process user input
         process_input(context, backend, &keymap_t, event);
+
+        // configs parsed on first use, like mimetype.toml, may have problems too
+        for problem in problems::take_problems() {
+            context
+                .message_queue_mut()
+                .push_warning(problem.to_string());
+        }
+
+        for edit in context.take_keymap_edits() {
+            match keymap_t.apply_edit(edit) {
+                Ok(removed) if removed > 1 => {
+                    context.message_queue_mut().push_warning(format!(
+                        "Unmapped {} key bindings starting with the key sequence",
+                        removed
+                    ));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    context
+                        .message_queue_mut()
+                        .push_error(format!("Keymap error: {}", e));
+                }
+            }
+        }
+
+        let curr_cwd = context.tab_context_ref().curr_tab_ref().cwd();
+        if curr_cwd != last_cwd {
+            let curr_cwd = curr_cwd.to_path_buf();
+            if update_title {
+                title::set_title(curr_cwd.as_path(), tilde_in_titlebar);
+            }
+            hooks::call_hook(
+                context,
+                Hook::DirChange {
+                    previous: last_cwd.as_path(),
+                },
+            );
+            last_cwd = curr_cwd;
+        }
     } // end of main loop
+    if let QuitAction::Terminate(_) = context.quit {
+        // nobody can be asked whether to stop the file operations, so the running ones
+        // are finished instead of leaving partial copies behind
+        context.worker_context_mut().clear_queue();
+        wait_for_workers(context);
+    }
+    if update_title {
+        title::pop_title();
+    }
+    hooks::call_hook(context, Hook::Exit);
+    Ok(())
+}
+
+/// Executes a `;` separated list of commands without entering the user interface.
+///
+/// Commands which need a terminal to interact with the user are rejected.
+/// Once all commands ran, this waits for pending background IO work to finish.
+pub fn run_headless(
+    backend: &mut ui::AppBackend,
+    context: &mut AppContext,
+    keymap_t: AppKeyMapping,
+    script: &str,
+) -> AppResult {
+    let curr_path = std::env::current_dir()?;
+
+    for problem in problems::take_problems() {
+        eprintln!("{}", problem);
+    }
+
+    // there is no terminal to query, so assume a common terminal size
+    calculate_ui_context(context, Rect::new(0, 0, 80, 24));
+    init_tabs(context, curr_path)?;
+
+    for line in script.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        let line = context.config_ref().expand_alias(line).into_owned();
+        let command = Command::from_str(&line)?;
+        if requires_terminal(&command) {
+            return Err(AppError::new(
+                AppErrorKind::InvalidParameters,
+                format!("{}: cannot be run without a terminal", command.command()),
+            ));
+        }
+        // there is nobody to ask for `quit --force`, so let pending work finish first
+        if let Command::Quit(action) | Command::QuitAll(action) = command {
+            wait_for_workers(context);
+            context.quit = action;
+            break;
+        }
+        command.execute(context, backend, &keymap_t)?;
+        if context.quit != QuitAction::DoNot {
+            break;
+        }
+    }
+    wait_for_workers(context);
+
+    // configs parsed on first use are only read by the commands needing them
+    for problem in problems::take_problems() {
+        eprintln!("{}", problem);
+    }
+    while let Some(message) = context.message_queue_mut().pop_front() {
+        eprintln!("{}", message.content);
+    }
+    Ok(())
+}
+
+fn wait_for_workers(context: &mut AppContext) {
+    while context.worker_context_ref().is_busy() || !context.worker_context_ref().is_empty() {
+        match context.poll_event() {
+            Ok(event) => process_event::process_noninteractive(event, context),
+            Err(_) => break,
+        }
+    }
+}
+
+/// Opens a tab for each path given on the command line, or `curr_path` if there are none.
+/// Tabs of files are opened in their directory with the cursor on the file
+fn init_tabs(context: &mut AppContext, curr_path: std::path::PathBuf) -> std::io::Result<()> {
+    let paths = context.args.rest.clone();
+    if paths.is_empty() {
+        return init_tab(context, curr_path);
+    }
+    for arg in paths {
+        let not_found = || {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{}: No such file or directory", arg.display()),
+            )
+        };
+        let path = curr_path.join(&arg);
+        if path.is_dir() {
+            init_tab(context, path.canonicalize()?)?;
+            continue;
+        }
+        // symlinks to files are shown in the directory of the link
+        let (dir, file_name) = match (path.parent(), path.file_name()) {
+            (Some(dir), Some(file_name)) if path.symlink_metadata().is_ok() => (dir, file_name),
+            _ => return Err(not_found()),
+        };
+        init_tab(context, dir.canonicalize()?)?;
+        context.tab_context_mut().index = context.tab_context_ref().len() - 1;
+        let _ = cursor_move::to_path(context, std::path::Path::new(file_name));
+    }
+    context.tab_context_mut().index = 0;
+    cwd::set_current_dir(context.tab_context_ref().curr_tab_ref().cwd())?;
+    Ok(())
+}
+
+fn init_tab(context: &mut AppContext, curr_path: std::path::PathBuf) -> std::io::Result<()> {
+    let id = Uuid::new_v4();
+    let tab = JoshutoTab::new(
+        curr_path,
+        context.ui_context_ref(),
+        context.config_ref().display_options_ref(),
+    )?;
+    context.tab_context_mut().insert_tab(id, tab);
     Ok(())
 }
 
+// commands which prompt the user or draw their own views
+fn requires_terminal(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::CommandLine { .. }
+            | Command::RenameFileAppend
+            | Command::RenameFileAppendBase
+            | Command::RenameFilePrepend
+            | Command::RenameFileKeepExt
+            | Command::RenameRegex { .. }
+            | Command::RenameTransform { .. }
+            | Command::DeleteFiles {
+                noconfirm: false,
+                ..
+            }
+            | Command::OpenFile
+            | Command::OpenFileWith { index: None }
+            | Command::OpenAll { .. }
+            | Command::SetMode
+            | Command::ShowTasks
+            | Command::Suspend
+            | Command::Messages
+            | Command::ViewFile { .. }
+            | Command::ShowProperties
+            | Command::ShowDuplicates
+            | Command::DirStats
+            | Command::CompareTabs
+            | Command::XattrList
+            | Command::AclEdit
+            | Command::OpenTerminal
+            | Command::JumpToChar
+            | Command::Help
+            | Command::CommandPalette
+            | Command::NumberedCommand { .. }
+            | Command::BookmarkAdd
+            | Command::BookmarkChangeDirectory
+    )
+}
+
 #[inline]
 fn process_input(
     context: &mut AppContext,
@@ -92,8 +376,9 @@ fn process_input(
             }
             match key {
                 // in the event where mouse input is not supported
-                // but we still want to register scroll
-                Event::Unsupported(s) => {
+                // but we still want to register scroll.
+                // Modified keys like ctrl+shift+p are unsupported events as well
+                Event::Unsupported(s) if !keymap_t.default_view.contains_key(&key) => {
                     process_event::process_unsupported(context, backend, keymap_t, s);
                 }
                 key => match keymap_t.default_view.get(&key) {
@@ -128,14 +413,53 @@ fn process_input(
             preview_default::load_preview(context, backend);
             context.flush_event();
         }
+        AppEvent::Remote(line) => {
+            process_event::process_remote(context, backend, keymap_t, line.as_str());
+            preview_default::load_preview(context, backend);
+        }
+        // the current entry might turn out to be a directory
+        event @ AppEvent::DirMetadata { .. } => {
+            process_event::process_noninteractive(event, context);
+            preview_default::load_preview(context, backend);
+        }
+        AppEvent::PreviewChanged { path } => {
+            context.preview_context_mut().finish_refresh();
+            // the cursor might have moved on in the meantime
+            if context.previewed_file() == Some(path.as_path()) {
+                context.reload_preview(backend, path);
+            }
+        }
+        AppEvent::Signal(signal) => process_signal(context, backend, signal),
         event => process_event::process_noninteractive(event, context),
     }
 }
 
+/// Handles a signal caught by the input thread, also while a view waits for input
+/// (see `process_event::process_in_modal`)
+pub fn process_signal(context: &mut AppContext, backend: &mut AppBackend, signal: i32) {
+    match signal {
+        signal::SIGWINCH => resize_handler(context, backend),
+        signal::SIGTSTP => {
+            if let Err(e) = suspend::suspend(context, backend) {
+                context.message_queue_mut().push_error(e.to_string());
+            }
+        }
+        signal => process_event::process_noninteractive(AppEvent::Signal(signal), context),
+    }
+}
+
+fn resize_handler(context: &mut AppContext, backend: &mut AppBackend) {
+    if let Ok(area) = backend.terminal_ref().size() {
+        calculate_ui_context(context, area);
+        // previews depend on the size of the preview area
+        preview_default::load_preview(context, backend);
+    }
+}
+
 fn calculate_ui_context(context: &mut AppContext, area: Rect) {
     let area = Rect {
         y: area.top() + 1,
-        height: area.height - 2,
+        height: area.height.saturating_sub(2),
         ..area
     };
     let config = context.config_ref();
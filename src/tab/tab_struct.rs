@@ -55,11 +55,24 @@ impl JoshutoTab {
     pub fn set_cwd(&mut self, cwd: &path::Path) {
         self._previous_dir = Some(self._cwd.to_path_buf());
         self._cwd = cwd.to_path_buf();
+        if let Some(dirlist) = self.history.get_mut(cwd) {
+            dirlist.touch();
+        }
 
         // OSC 7: Escape sequence to set the working directory
         // print!("\x1b]7;file://{}{}\x1b\\", HOSTNAME.as_str(), cwd.display());
     }
 
+    /// Limits the entries kept in the history, except for the directories currently shown
+    pub fn shrink_history(&mut self, max_entries: usize) {
+        let mut keep: Vec<path::PathBuf> =
+            self.cwd().ancestors().map(|p| p.to_path_buf()).collect();
+        if let Some(entry) = self.curr_list_ref().and_then(|list| list.curr_entry_ref()) {
+            keep.push(entry.file_path_buf());
+        }
+        self.history.evict_least_recently_used(max_entries, &keep);
+    }
+
     pub fn previous_dir(&self) -> Option<&path::Path> {
         // This converts PathBuf to Path
         match &self._previous_dir {
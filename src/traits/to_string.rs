@@ -1,5 +1,7 @@
 use termion::event::{Event, Key, MouseEvent};
 
+use crate::util::keyparse;
+
 pub trait ToString {
     fn to_string(&self) -> String;
 }
@@ -40,7 +42,9 @@ impl ToString for Event {
         match self {
             Event::Key(key) => key.to_string(),
             Event::Mouse(mouse) => mouse.to_string(),
-            Event::Unsupported(v) => format!("{:?}", v),
+            Event::Unsupported(v) => {
+                keyparse::unsupported_to_string(v).unwrap_or_else(|| format!("{:?}", v))
+            }
         }
     }
 }
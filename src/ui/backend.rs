@@ -1,6 +1,10 @@
 use std::io::{self, stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use ratatui::backend::TermionBackend;
+use ratatui::backend::{Backend, ClearType, TermionBackend, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
 use ratatui::widgets::Widget;
 use termion::raw::{IntoRawMode, RawTerminal};
 use termion::screen::AlternateScreen;
@@ -49,31 +53,110 @@ impl Write for Screen {
     }
 }
 
-// pub type TuiBackend = TermionBackend<Screen>;
-pub type TuiTerminal = ratatui::Terminal<TermionBackend<Screen>>;
+// whether the terminal is in raw mode and showing the alternate screen
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// whether colors are drawn, see `colors` in the `[display]` section
+static COLORS_ENABLED: AtomicBool = AtomicBool::new(true);
+// set when colors are toggled, only changed cells are drawn otherwise
+static COLORS_CHANGED: AtomicBool = AtomicBool::new(false);
+
+/// Draws to the terminal, without the colors of the drawn cells if colors are disabled.
+/// Text attributes like bold or reversed are kept, so everything stays distinguishable
+pub struct TuiBackend(TermionBackend<Screen>);
+
+impl Backend for TuiBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if COLORS_ENABLED.load(Ordering::Relaxed) {
+            return self.0.draw(content);
+        }
+        let cells: Vec<(u16, u16, Cell)> = content
+            .map(|(x, y, cell)| {
+                let mut cell = cell.clone();
+                cell.set_fg(Color::Reset).set_bg(Color::Reset);
+                (x, y, cell)
+            })
+            .collect();
+        self.0.draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.0.append_lines(n)
+    }
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.0.hide_cursor()
+    }
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.0.show_cursor()
+    }
+    fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+        self.0.get_cursor()
+    }
+    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.0.set_cursor(x, y)
+    }
+    fn clear(&mut self) -> io::Result<()> {
+        self.0.clear()
+    }
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.0.clear_region(clear_type)
+    }
+    fn size(&self) -> io::Result<Rect> {
+        self.0.size()
+    }
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.0.window_size()
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.0)
+    }
+}
+
+pub type TuiTerminal = ratatui::Terminal<TuiBackend>;
 
 pub struct AppBackend {
     pub terminal: Option<TuiTerminal>,
+    // whether commands are run without a terminal (see `--command`)
+    headless: bool,
 }
 
 impl AppBackend {
     pub fn new(mouse_support: bool) -> io::Result<Self> {
+        Ok(Self {
+            terminal: Some(Self::enter_terminal(mouse_support)?),
+            headless: false,
+        })
+    }
+
+    fn enter_terminal(mouse_support: bool) -> io::Result<TuiTerminal> {
         let mut alt_screen = Screen::new(mouse_support)?;
         // clears the screen of artifacts
         write!(alt_screen, "{}", termion::clear::All)?;
 
-        let backend = TermionBackend::new(alt_screen);
+        let backend = TuiBackend(TermionBackend::new(alt_screen));
         let mut terminal = ratatui::Terminal::new(backend)?;
         terminal.hide_cursor()?;
-        Ok(Self {
-            terminal: Some(terminal),
-        })
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        Ok(terminal)
+    }
+
+    /// Creates a backend without a terminal, for running commands non-interactively.
+    pub fn headless() -> Self {
+        Self {
+            terminal: None,
+            headless: true,
+        }
     }
 
     pub fn render<W>(&mut self, widget: W)
     where
         W: Widget,
     {
+        if COLORS_CHANGED.swap(false, Ordering::Relaxed) {
+            let _ = self.terminal_mut().clear();
+        }
         let _ = self.terminal_mut().draw(|frame| {
             let rect = frame.size();
             frame.render_widget(widget, rect);
@@ -89,13 +172,39 @@ impl AppBackend {
     }
 
     pub fn terminal_drop(&mut self) {
-        let _ = self.terminal.take();
+        // only the backend holding the terminal has set it up
+        if self.terminal.take().is_some() {
+            TERMINAL_ACTIVE.store(false, Ordering::SeqCst);
+        }
         let _ = stdout().flush();
     }
 
+    /// Draws colors or only text attributes from now on,
+    /// the whole screen is drawn again on the next render
+    pub fn set_colors(enabled: bool) {
+        if COLORS_ENABLED.swap(enabled, Ordering::Relaxed) != enabled {
+            COLORS_CHANGED.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether a terminal is set up for drawing the user interface
+    pub fn terminal_active() -> bool {
+        TERMINAL_ACTIVE.load(Ordering::SeqCst)
+    }
+
     pub fn terminal_restore(&mut self, mouse_support: bool) -> io::Result<()> {
-        let mut new_backend = Self::new(mouse_support)?;
-        std::mem::swap(&mut self.terminal, &mut new_backend.terminal);
+        if self.headless {
+            return Ok(());
+        }
+        // the screen of a terminal still held is left before entering it again
+        self.terminal_drop();
+        self.terminal = Some(Self::enter_terminal(mouse_support)?);
         Ok(())
     }
 }
+
+impl Drop for AppBackend {
+    fn drop(&mut self) {
+        self.terminal_drop();
+    }
+}
@@ -143,7 +143,7 @@ impl<'a> Widget for TuiFolderView<'a> {
             Constraint::Ratio(0, _) => {}
             _ => {
                 if let Some(list) = curr_tab.parent_list_ref().as_ref() {
-                    TuiDirList::new(list, true).render(layout_rect[0], buf);
+                    TuiDirList::new(list, display_options, true).render(layout_rect[0], buf);
                 }
             }
         }
@@ -185,7 +185,9 @@ impl<'a> Widget for TuiFolderView<'a> {
         }
 
         if let Some(list) = child_list.as_ref() {
-            TuiDirList::new(list, true).render(layout_rect[2], buf);
+            TuiDirList::new(list, display_options, true)
+                .summary(true)
+                .render(layout_rect[2], buf);
         } else if let Some(entry) = curr_entry {
             match curr_tab.history_metadata_ref().get(entry.file_path()) {
                 Some(PreviewDirState::Loading) => {
@@ -296,11 +298,11 @@ pub fn calculate_layout(area: Rect, constraints: &[Constraint; 3]) -> Vec<Rect>
         .to_vec();
 
     layout_rect[0] = Rect {
-        width: layout_rect[0].width - 1,
+        width: layout_rect[0].width.saturating_sub(1),
         ..layout_rect[0]
     };
     layout_rect[1] = Rect {
-        width: layout_rect[1].width - 1,
+        width: layout_rect[1].width.saturating_sub(1),
         ..layout_rect[1]
     };
     layout_rect
@@ -32,7 +32,7 @@ impl<'a> Widget for TuiHSplitView<'a> {
         let layout_rect = if display_options.show_borders() {
             let area = Rect {
                 y: area.top() + 1,
-                height: area.height - 2,
+                height: area.height.saturating_sub(2),
                 ..area
             };
 
@@ -57,7 +57,7 @@ impl<'a> Widget for TuiHSplitView<'a> {
         } else {
             let area = Rect {
                 y: area.top() + 1,
-                height: area.height - 2,
+                height: area.height.saturating_sub(2),
                 ..area
             };
             calculate_layout(area, constraints)
@@ -148,11 +148,11 @@ fn calculate_layout(area: Rect, constraints: &[Constraint; 2]) -> Vec<Rect> {
         .to_vec();
 
     layout_rect[0] = Rect {
-        width: layout_rect[0].width - 1,
+        width: layout_rect[0].width.saturating_sub(1),
         ..layout_rect[0]
     };
     layout_rect[1] = Rect {
-        width: layout_rect[1].width - 1,
+        width: layout_rect[1].width.saturating_sub(1),
         ..layout_rect[1]
     };
     layout_rect
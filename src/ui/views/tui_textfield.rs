@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use rustyline::completion::{Candidate, FilenameCompleter, Pair};
-use rustyline::history::SearchDirection;
+use rustyline::history::{History, SearchDirection};
 use rustyline::line_buffer::{self, ChangeListener, DeleteListener, Direction, LineBuffer};
 use rustyline::{At, Word};
 
@@ -10,7 +10,7 @@ use ratatui::widgets::Clear;
 use termion::event::{Event, Key};
 use unicode_width::UnicodeWidthStr;
 
-use crate::context::AppContext;
+use crate::context::{AppContext, PromptHistory};
 use crate::event::process_event;
 use crate::event::AppEvent;
 use crate::key_command::{complete_command, Command, InteractiveExecute};
@@ -52,6 +52,50 @@ impl CompletionTracker {
     }
 }
 
+/// State of a `Ctrl-R` search through the prompt history
+struct ReverseSearch {
+    query: String,
+    // history index of the current match
+    index: Option<usize>,
+    // input before the search started, restored when it is cancelled
+    original: String,
+    failed: bool,
+}
+
+impl ReverseSearch {
+    pub fn new(original: String) -> Self {
+        Self {
+            query: String::new(),
+            index: None,
+            original,
+            failed: false,
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        if self.failed {
+            format!("(failed reverse-i-search)`{}': ", self.query)
+        } else {
+            format!("(reverse-i-search)`{}': ", self.query)
+        }
+    }
+
+    /// Finds the newest entry of `history` at or before `start` containing the query
+    pub fn find(&mut self, history: &dyn History, start: Option<usize>) -> Option<String> {
+        let found = match start {
+            Some(start) => history
+                .search(&self.query, start, SearchDirection::Reverse)
+                .ok()
+                .flatten(),
+            None => None,
+        };
+        self.failed = found.is_none();
+        let found = found?;
+        self.index = Some(found.idx);
+        Some(found.entry.into_owned())
+    }
+}
+
 pub struct CursorInfo {
     pub x: usize,
     pub y: usize,
@@ -63,6 +107,7 @@ pub struct TuiTextField<'a> {
     _prefix: &'a str,
     _suffix: &'a str,
     _menu_items: Vec<&'a str>,
+    _history: PromptHistory,
 }
 
 impl<'a> TuiTextField<'a> {
@@ -89,6 +134,13 @@ impl<'a> TuiTextField<'a> {
         self
     }
 
+    /// Browses, searches and adds to the history of `kind` instead of the one
+    /// shared by the prompts without their own
+    pub fn history(&mut self, kind: PromptHistory) -> &mut Self {
+        self._history = kind;
+        self
+    }
+
     pub fn get_input(
         &mut self,
         backend: &mut AppBackend,
@@ -106,13 +158,21 @@ impl<'a> TuiTextField<'a> {
         line_buffer.insert_str(line_buffer.len(), self._suffix, listener);
         line_buffer.set_pos(char_idx);
 
-        let terminal = backend.terminal_mut();
-        let _ = terminal.show_cursor();
+        let _ = backend.terminal_mut().show_cursor();
 
-        let mut curr_history_index = context.commandline_context_ref().history_ref().len();
+        let mut curr_history_index = context
+            .commandline_context_ref()
+            .history_ref(self._history)
+            .len();
+        let mut reverse_search: Option<ReverseSearch> = None;
 
         loop {
-            terminal
+            let prompt = match reverse_search.as_ref() {
+                Some(search) => search.prompt(),
+                None => self._prompt.to_string(),
+            };
+            backend
+                .terminal_mut()
                 .draw(|frame| {
                     let area: Rect = frame.size();
                     if area.height == 0 {
@@ -129,7 +189,7 @@ impl<'a> TuiTextField<'a> {
                     let buffer_str = line_buffer.as_str();
                     let cursor_xpos = line_buffer.pos();
 
-                    let line_str = format!("{}{}", self._prompt, buffer_str);
+                    let line_str = format!("{}{}", prompt, buffer_str);
                     let multiline = TuiMultilineText::new(line_str.as_str(), area_width);
                     let multiline_height = multiline.height();
 
@@ -165,8 +225,7 @@ impl<'a> TuiTextField<'a> {
                     };
 
                     // get cursor render position
-                    let cursor_prefix_width =
-                        buffer_str[0..cursor_xpos].width() + self._prompt.len();
+                    let cursor_prefix_width = buffer_str[0..cursor_xpos].width() + prompt.width();
                     let y_offset = cursor_prefix_width / area_width;
                     cursor_info.y = area.height as usize - multiline_height + y_offset;
                     cursor_info.x = cursor_prefix_width % area_width + y_offset;
@@ -182,6 +241,42 @@ impl<'a> TuiTextField<'a> {
 
             if let Ok(event) = context.poll_event() {
                 match event {
+                    AppEvent::Termion(Event::Key(key)) if reverse_search.is_some() => {
+                        let search = reverse_search.as_mut().unwrap();
+                        let history = context.commandline_context_ref().history_ref(self._history);
+                        let newest = history.len().checked_sub(1);
+                        let found = match key {
+                            Key::Ctrl('r') => {
+                                let start = search.index.and_then(|i| i.checked_sub(1));
+                                search.find(history, start)
+                            }
+                            Key::Backspace => {
+                                search.query.pop();
+                                search.find(history, newest)
+                            }
+                            Key::Char('\n') => break,
+                            Key::Char(c) => {
+                                search.query.push(c);
+                                // the current match might still contain the longer query
+                                search.find(history, search.index.or(newest))
+                            }
+                            Key::Esc | Key::Ctrl('g') => {
+                                let original = search.original.clone();
+                                line_buffer.update(&original, original.len(), listener);
+                                reverse_search = None;
+                                None
+                            }
+                            // keep the match for editing
+                            _ => {
+                                reverse_search = None;
+                                None
+                            }
+                        };
+                        if let Some(entry) = found {
+                            line_buffer.update(&entry, entry.len(), listener);
+                        }
+                        context.flush_event();
+                    }
                     AppEvent::Termion(Event::Key(key)) => {
                         let dirty = match key {
                             Key::Backspace => {
@@ -196,39 +291,39 @@ impl<'a> TuiTextField<'a> {
                             Key::Home => line_buffer.move_home(),
                             Key::End => line_buffer.move_end(),
                             Key::Up => {
+                                let history =
+                                    context.commandline_context_ref().history_ref(self._history);
                                 curr_history_index = curr_history_index.saturating_sub(1);
                                 line_buffer.move_home();
                                 line_buffer.kill_line(listener);
-                                if let Ok(Some(s)) = context
-                                    .commandline_context_ref()
-                                    .history_ref()
-                                    .get(curr_history_index, SearchDirection::Forward)
+                                if let Ok(Some(s)) =
+                                    history.get(curr_history_index, SearchDirection::Forward)
                                 {
                                     line_buffer.insert_str(0, &s.entry, listener);
                                 }
                                 true
                             }
                             Key::Down => {
-                                curr_history_index = if curr_history_index
-                                    < context.commandline_context_ref().history_ref().len()
-                                {
-                                    curr_history_index + 1
-                                } else {
-                                    curr_history_index
-                                };
+                                let history =
+                                    context.commandline_context_ref().history_ref(self._history);
+                                if curr_history_index < history.len() {
+                                    curr_history_index += 1;
+                                }
                                 line_buffer.move_home();
                                 line_buffer.kill_line(listener);
-                                if let Ok(Some(s)) = context
-                                    .commandline_context_ref()
-                                    .history_ref()
-                                    .get(curr_history_index, SearchDirection::Reverse)
+                                if let Ok(Some(s)) =
+                                    history.get(curr_history_index, SearchDirection::Reverse)
                                 {
                                     line_buffer.insert_str(0, &s.entry, listener);
                                 }
                                 true
                             }
+                            Key::Ctrl('r') => {
+                                reverse_search = Some(ReverseSearch::new(line_buffer.to_string()));
+                                false
+                            }
                             Key::Esc => {
-                                let _ = terminal.hide_cursor();
+                                let _ = backend.terminal_mut().hide_cursor();
                                 return None;
                             }
                             Key::Char('\t') => autocomplete_forward(
@@ -301,16 +396,29 @@ impl<'a> TuiTextField<'a> {
                     AppEvent::Termion(_) => {
                         context.flush_event();
                     }
-                    event => process_event::process_noninteractive(event, context),
+                    event => {
+                        if process_event::process_in_modal(event, context, backend) {
+                            let _ = backend.terminal_mut().hide_cursor();
+                            return None;
+                        }
+                        // resuming after SIGTSTP hides the cursor
+                        let _ = backend.terminal_mut().show_cursor();
+                    }
                 };
             }
         }
-        let _ = terminal.hide_cursor();
+        let _ = backend.terminal_mut().hide_cursor();
 
         if line_buffer.as_str().is_empty() {
             None
         } else {
             let input_string = line_buffer.to_string();
+            let history = context.commandline_context_mut();
+            if let Err(e) = history.add(self._history, input_string.trim_start()) {
+                context
+                    .message_queue_mut()
+                    .push_error(format!("Failed to save prompt history: {}", e));
+            }
             Some(input_string)
         }
     }
@@ -1,11 +1,16 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
+use ratatui::style::Style;
 use ratatui::widgets::Widget;
 
 use crate::config::clean::app::display::DisplayMode;
 use crate::context::AppContext;
 use crate::ui::views::{TuiFolderView, TuiHSplitView};
 
+// below this size the views can't lay out their panels
+const MIN_WIDTH: u16 = 8;
+const MIN_HEIGHT: u16 = 4;
+
 pub struct TuiView<'a> {
     pub context: &'a AppContext,
     pub show_bottom_status: bool,
@@ -22,6 +27,16 @@ impl<'a> TuiView<'a> {
 
 impl<'a> Widget for TuiView<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            buf.set_stringn(
+                area.x,
+                area.y,
+                "too small",
+                area.width as usize,
+                Style::default(),
+            );
+            return;
+        }
         let config = self.context.config_ref();
         let display_options = config.display_options_ref();
         match display_options.mode() {
@@ -30,6 +30,10 @@ impl<'a> Widget for TuiWorkerView<'a> {
             width: area.width,
             height: area.height - 1,
         };
-        TuiWorker::new(self.context.worker_context_ref()).render(rect, buf);
+        TuiWorker::new(
+            self.context.worker_context_ref(),
+            self.context.config_ref().max_concurrent_tasks,
+        )
+        .render(rect, buf);
     }
 }
@@ -1,25 +1,42 @@
+mod tui_command_palette;
+mod tui_dir_compare;
+mod tui_dir_stats;
 mod tui_dirlist;
 mod tui_dirlist_detailed;
 mod tui_dirlist_loading;
+mod tui_duplicates;
 mod tui_file_preview;
 mod tui_footer;
 mod tui_help;
 mod tui_menu;
 mod tui_message;
+mod tui_message_log;
+mod tui_pager;
 mod tui_prompt;
+mod tui_properties;
 mod tui_text;
 mod tui_topbar;
 mod tui_worker;
 
+pub use self::tui_command_palette::TuiCommandPalette;
+pub use self::tui_dir_compare::TuiDirCompare;
+pub use self::tui_dir_stats::TuiDirStats;
 pub use self::tui_dirlist::TuiDirList;
-pub use self::tui_dirlist_detailed::{trim_file_label, TuiDirListDetailed};
+pub use self::tui_dirlist_detailed::{trim_file_label, trim_file_label_middle, TuiDirListDetailed};
 pub use self::tui_dirlist_loading::TuiDirListLoading;
+pub use self::tui_duplicates::TuiDuplicates;
 pub use self::tui_file_preview::TuiFilePreview;
 pub use self::tui_footer::TuiFooter;
-pub use self::tui_help::{get_keymap_table, TuiHelp};
+pub use self::tui_help::{
+    get_keybind_commands, get_keymap_table, get_raw_keymap_table, get_unbound_commands_table,
+    TuiHelp,
+};
 pub use self::tui_menu::TuiMenu;
 pub use self::tui_message::TuiMessage;
+pub use self::tui_message_log::TuiMessageLog;
+pub use self::tui_pager::TuiPager;
 pub use self::tui_prompt::TuiPrompt;
+pub use self::tui_properties::TuiProperties;
 pub use self::tui_text::TuiMultilineText;
 pub use self::tui_topbar::TuiTopBar;
 pub use self::tui_worker::TuiWorker;
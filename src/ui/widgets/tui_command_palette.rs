@@ -0,0 +1,115 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Clear, Widget};
+
+const TITLE: &str = "Commands";
+const FOOTER: &str = "<Enter>: run  <Up>/<Down>: select  <ESC>: close";
+const KEY_WIDTH: usize = 12;
+const COMMAND_WIDTH: usize = 32;
+
+/// Popup of `command_palette`, listing the commands matching `query`.
+/// Rows are made of the key, command and description like in `get_raw_keymap_table`
+pub struct TuiCommandPalette<'a> {
+    query: &'a str,
+    rows: &'a [&'a [String; 4]],
+    index: usize,
+}
+
+impl<'a> TuiCommandPalette<'a> {
+    pub fn new(query: &'a str, rows: &'a [&'a [String; 4]], index: usize) -> Self {
+        Self { query, rows, index }
+    }
+}
+
+impl<'a> Widget for TuiCommandPalette<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = (area.width * 4 / 5).max(area.width.min(40));
+        let height = (area.height * 3 / 5).max(area.height.min(8));
+        // borders, the query, a blank line before the footer and the footer
+        if width < 4 || height < 6 {
+            return;
+        }
+        let rect = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        Clear.render(rect, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(TITLE)
+            .render(rect, buf);
+
+        let inner_width = (rect.width - 4) as usize;
+        let x = rect.x + 2;
+        buf.set_stringn(
+            x,
+            rect.y + 1,
+            format!("> {}", self.query),
+            inner_width,
+            Style::default().add_modifier(Modifier::BOLD),
+        );
+
+        let visible = (rect.height - 5) as usize;
+        let offset = (self.index + 1).saturating_sub(visible);
+        if self.rows.is_empty() {
+            buf.set_stringn(
+                x,
+                rect.y + 2,
+                "No matching commands",
+                inner_width,
+                Style::default().fg(Color::DarkGray),
+            );
+        }
+        for (i, row) in self.rows.iter().enumerate().skip(offset).take(visible) {
+            let y = rect.y + 2 + (i - offset) as u16;
+            let reversed = if i == self.index {
+                Modifier::REVERSED
+            } else {
+                Modifier::empty()
+            };
+            let line = format!(
+                "{:<kw$} {:<cw$} {}",
+                row[0],
+                row[1],
+                row[2],
+                kw = KEY_WIDTH,
+                cw = COMMAND_WIDTH
+            );
+            buf.set_stringn(
+                x,
+                y,
+                format!("{:<w$}", line, w = inner_width),
+                inner_width,
+                Style::default().add_modifier(reversed),
+            );
+            buf.set_stringn(
+                x,
+                y,
+                &row[0],
+                inner_width.min(KEY_WIDTH),
+                Style::default().fg(Color::Green).add_modifier(reversed),
+            );
+            if KEY_WIDTH + 1 < inner_width {
+                buf.set_stringn(
+                    x + KEY_WIDTH as u16 + 1,
+                    y,
+                    &row[1],
+                    (inner_width - KEY_WIDTH - 1).min(COMMAND_WIDTH),
+                    Style::default().fg(Color::Blue).add_modifier(reversed),
+                );
+            }
+        }
+
+        buf.set_stringn(
+            x,
+            rect.bottom() - 2,
+            FOOTER,
+            inner_width,
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
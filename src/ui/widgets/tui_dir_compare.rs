@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Widget;
+
+use crate::util::dir_compare::{DiffKind, DirDifference};
+
+const FOOTER: &str = "<: only in left  >: only in right  !: different  <ESC>: return";
+
+/// Differences between two directory trees, see `compare_tabs`
+pub struct TuiDirCompare<'a> {
+    left: &'a Path,
+    right: &'a Path,
+    // `None` while they are computed
+    differences: Option<&'a [DirDifference]>,
+    offset: &'a mut usize,
+}
+
+impl<'a> TuiDirCompare<'a> {
+    pub fn new(
+        left: &'a Path,
+        right: &'a Path,
+        differences: Option<&'a [DirDifference]>,
+        offset: &'a mut usize,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            differences,
+            offset,
+        }
+    }
+}
+
+impl<'a> Widget for TuiDirCompare<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 3 {
+            return;
+        }
+        let bar_style = Style::default().add_modifier(Modifier::REVERSED);
+        let width = area.width as usize;
+        let height = area.height as usize - 2;
+
+        let differences = self.differences.unwrap_or_default();
+        let max_offset = differences.len().saturating_sub(height);
+        if *self.offset > max_offset {
+            *self.offset = max_offset;
+        }
+
+        let title = format!("{}  <>  {}", self.left.display(), self.right.display());
+        buf.set_stringn(
+            area.x,
+            area.y,
+            format!("{:^w$}", title, w = width),
+            width,
+            bar_style,
+        );
+
+        if differences.is_empty() {
+            let msg = match self.differences {
+                Some(_) => "The directories are identical",
+                None => "Comparing directories...",
+            };
+            buf.set_stringn(area.x, area.y + 1, msg, width, Style::default());
+        }
+        for (i, difference) in differences
+            .iter()
+            .skip(*self.offset)
+            .take(height)
+            .enumerate()
+        {
+            let (symbol, style) = match difference.kind {
+                DiffKind::OnlyLeft => ('<', Style::default().fg(Color::Red)),
+                DiffKind::OnlyRight => ('>', Style::default().fg(Color::Green)),
+                DiffKind::Different => ('!', Style::default().fg(Color::Yellow)),
+            };
+            let line = format!(
+                "{} {}{}",
+                symbol,
+                difference.path.display(),
+                if difference.is_dir { "/" } else { "" }
+            );
+            buf.set_stringn(area.x, area.y + 1 + i as u16, line, width, style);
+        }
+
+        buf.set_stringn(
+            area.x,
+            area.bottom() - 1,
+            format!("{:<w$}", FOOTER, w = width),
+            width,
+            bar_style,
+        );
+    }
+}
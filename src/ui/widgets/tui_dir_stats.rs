@@ -0,0 +1,183 @@
+use std::path::Path;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Widget;
+
+use crate::util::dir_stats::DirStats;
+use crate::util::format;
+
+const FOOTER: &str = "<ENTER>: jump to file  <ESC>: return";
+// extensions listed before the rest are summed up as "others"
+const LISTED_EXTENSIONS: usize = 10;
+
+/// Statistics of a directory tree, see `stats`
+pub struct TuiDirStats<'a> {
+    root: &'a Path,
+    // `None` while they are computed
+    stats: Option<&'a DirStats>,
+    progress: Option<&'a str>,
+    // index of the selected file, counting the files of all lists
+    cursor: usize,
+}
+
+impl<'a> TuiDirStats<'a> {
+    pub fn new(
+        root: &'a Path,
+        stats: Option<&'a DirStats>,
+        progress: Option<&'a str>,
+        cursor: usize,
+    ) -> Self {
+        Self {
+            root,
+            stats,
+            progress,
+            cursor,
+        }
+    }
+
+    // (text, style, is the cursor on this line)
+    fn lines(&self, stats: &'a DirStats) -> Vec<(String, Style, bool)> {
+        let header_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let size = |size: u64| format::file_size_to_string(size).trim().to_string();
+        let name = |path: &'a Path| path.strip_prefix(self.root).unwrap_or(path).display();
+
+        let mut lines = vec![
+            (
+                format!(
+                    "{} in {} files and {} directories",
+                    size(stats.total_size),
+                    stats.file_count,
+                    stats.dir_count
+                ),
+                Style::default(),
+                false,
+            ),
+            (String::new(), Style::default(), false),
+            ("By extension".to_string(), header_style, false),
+        ];
+        for ext in stats.extensions.iter().take(LISTED_EXTENSIONS) {
+            let extension = if ext.extension.is_empty() {
+                "(none)".to_string()
+            } else {
+                format!(".{}", ext.extension)
+            };
+            lines.push((
+                format!(
+                    "  {:<12} {:>8} files {:>10}",
+                    extension,
+                    ext.count,
+                    size(ext.size)
+                ),
+                Style::default(),
+                false,
+            ));
+        }
+        let others = &stats.extensions[stats.extensions.len().min(LISTED_EXTENSIONS)..];
+        if !others.is_empty() {
+            lines.push((
+                format!(
+                    "  {:<12} {:>8} files {:>10}",
+                    "(others)",
+                    others.iter().map(|e| e.count).sum::<usize>(),
+                    size(others.iter().map(|e| e.size).sum())
+                ),
+                Style::default(),
+                false,
+            ));
+        }
+
+        let mut file_index = 0;
+        let mut push_files = |lines: &mut Vec<_>, title: &str, files: Vec<(String, &'a Path)>| {
+            lines.push((String::new(), Style::default(), false));
+            lines.push((title.to_string(), header_style, false));
+            for (info, path) in files {
+                lines.push((
+                    format!("  {:>16}  {}", info, name(path)),
+                    Style::default(),
+                    file_index == self.cursor,
+                ));
+                file_index += 1;
+            }
+        };
+        push_files(
+            &mut lines,
+            "Largest files",
+            stats
+                .largest
+                .iter()
+                .map(|(p, s)| (size(*s), p.as_path()))
+                .collect(),
+        );
+        push_files(
+            &mut lines,
+            "Oldest files",
+            stats
+                .oldest
+                .iter()
+                .map(|(p, t)| (format::mtime_to_string(*t), p.as_path()))
+                .collect(),
+        );
+        push_files(
+            &mut lines,
+            "Newest files",
+            stats
+                .newest
+                .iter()
+                .map(|(p, t)| (format::mtime_to_string(*t), p.as_path()))
+                .collect(),
+        );
+        lines
+    }
+}
+
+impl<'a> Widget for TuiDirStats<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 3 {
+            return;
+        }
+        let bar_style = Style::default().add_modifier(Modifier::REVERSED);
+        let width = area.width as usize;
+        let height = area.height as usize - 2;
+
+        let title = format!("Statistics of {}", self.root.display());
+        buf.set_stringn(
+            area.x,
+            area.y,
+            format!("{:^w$}", title, w = width),
+            width,
+            bar_style,
+        );
+
+        match self.stats {
+            Some(stats) => {
+                let lines = self.lines(stats);
+                let cursor_line = lines.iter().position(|(_, _, c)| *c).unwrap_or(0);
+                let offset = (cursor_line + 1).saturating_sub(height);
+                for (i, (text, style, cursor)) in lines.iter().skip(offset).take(height).enumerate()
+                {
+                    let style = if *cursor {
+                        style.add_modifier(Modifier::REVERSED)
+                    } else {
+                        *style
+                    };
+                    buf.set_stringn(area.x, area.y + 1 + i as u16, text, width, style);
+                }
+            }
+            None => {
+                let msg = self.progress.unwrap_or("computing statistics");
+                buf.set_stringn(area.x, area.y + 1, msg, width, Style::default());
+            }
+        }
+        buf.set_stringn(
+            area.x,
+            area.bottom() - 1,
+            format!("{:<w$}", FOOTER, w = width),
+            width,
+            bar_style,
+        );
+    }
+}
@@ -4,28 +4,67 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::Widget;
 use unicode_width::UnicodeWidthStr;
 
+use crate::config::clean::app::display::DisplayOption;
 use crate::fs::{JoshutoDirEntry, JoshutoDirList};
-use crate::ui::widgets::trim_file_label;
-use crate::util::style;
+use crate::ui::widgets::{trim_file_label, trim_file_label_middle};
+use crate::util::{format, style};
 
 pub struct TuiDirList<'a> {
     dirlist: &'a JoshutoDirList,
+    display_options: &'a DisplayOption,
     pub focused: bool,
+    // first line shows the number of entries and their size
+    summary: bool,
 }
 
 impl<'a> TuiDirList<'a> {
-    pub fn new(dirlist: &'a JoshutoDirList, focused: bool) -> Self {
-        Self { dirlist, focused }
+    pub fn new(
+        dirlist: &'a JoshutoDirList,
+        display_options: &'a DisplayOption,
+        focused: bool,
+    ) -> Self {
+        Self {
+            dirlist,
+            display_options,
+            focused,
+            summary: false,
+        }
+    }
+
+    pub fn summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
     }
 }
 
+/// e.g. "3 dirs, 12 files, 1.2 MB", sizes of entries whose metadata is not loaded yet are
+/// not included
+pub fn dirlist_summary(dirlist: &JoshutoDirList) -> String {
+    let dirs = dirlist.iter().filter(|e| e.metadata.is_dir()).count();
+    let files = dirlist.len() - dirs;
+    let size: u64 = dirlist
+        .iter()
+        .filter(|e| !e.metadata.is_dir())
+        .map(|e| e.metadata.len())
+        .sum();
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    format!(
+        "{} dir{}, {} file{}, {}",
+        dirs,
+        plural(dirs),
+        files,
+        plural(files),
+        format::file_size_to_string(size)
+    )
+}
+
 impl<'a> Widget for TuiDirList<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if area.width < 4 || area.height < 1 {
             return;
         }
         let x = area.left();
-        let y = area.top();
+        let mut y = area.top();
 
         if self.dirlist.contents.is_empty() {
             let style = Style::default().bg(Color::Red).fg(Color::White);
@@ -33,8 +72,21 @@ impl<'a> Widget for TuiDirList<'a> {
             return;
         }
 
+        let mut height = area.height as usize;
+        if self.summary && height > 1 {
+            let style = Style::default().fg(Color::DarkGray);
+            let summary = dirlist_summary(self.dirlist);
+            buf.set_stringn(x + 1, y, summary, area.width as usize - 1, style);
+            y += 1;
+            height -= 1;
+        }
+
         let curr_index = self.dirlist.get_index().unwrap();
-        let skip_dist = self.dirlist.first_index_for_viewport();
+        // the viewport is calculated for the whole area, keep the cursor visible
+        let skip_dist = self
+            .dirlist
+            .first_index_for_viewport()
+            .max((curr_index + 1).saturating_sub(height));
 
         let drawing_width = area.width as usize;
 
@@ -44,7 +96,7 @@ impl<'a> Widget for TuiDirList<'a> {
             .iter()
             .skip(skip_dist)
             .enumerate()
-            .take(area.height as usize)
+            .take(height)
             .for_each(|(i, entry)| {
                 let ix = skip_dist + i;
 
@@ -58,7 +110,16 @@ impl<'a> Widget for TuiDirList<'a> {
 
                 buf.set_string(x, y + i as u16, space_fill.as_str(), style);
 
-                print_entry(buf, entry, style, (x + 1, y + i as u16), drawing_width - 1);
+                let prefix = style::selection_prefix(entry).unwrap_or_default();
+                print_entry(
+                    buf,
+                    entry,
+                    style,
+                    (x + 1, y + i as u16),
+                    drawing_width - 1,
+                    self.display_options.middle_ellipsis(),
+                    &prefix,
+                );
             });
     }
 }
@@ -69,13 +130,27 @@ fn print_entry(
     style: Style,
     (x, y): (u16, u16),
     drawing_width: usize,
+    middle_ellipsis: bool,
+    prefix: &str,
 ) {
+    let prefix_width = prefix.width().min(drawing_width);
+    buf.set_stringn(x, y, prefix, prefix_width, style);
+    let (x, drawing_width) = (x + prefix_width as u16, drawing_width - prefix_width);
+
     let name = entry.label();
-    let name_width = name.width();
-    let label = if name_width > drawing_width {
-        trim_file_label(name, drawing_width)
+    let marker = entry.marker();
+    let drawing_width = drawing_width.saturating_sub(marker.width());
+    let mut label = if name.width() > drawing_width {
+        if middle_ellipsis {
+            trim_file_label_middle(name, drawing_width)
+        } else {
+            trim_file_label(name, drawing_width)
+        }
     } else {
         name.to_string()
     };
+    if !label.is_empty() {
+        label.push_str(marker);
+    }
     buf.set_string(x, y, label, style);
 }
@@ -89,10 +89,10 @@ impl<'a> Widget for TuiDirListDetailed<'a> {
 
                 buf.set_string(x, y + i as u16, space_fill.as_str(), style);
 
-                let mut prefix = if entry.is_selected() {
-                    " ".to_string()
-                } else {
-                    "".to_string()
+                let mut prefix = match style::selection_prefix(entry) {
+                    Some(prefix) => prefix,
+                    None if entry.is_selected() => " ".to_string(),
+                    None => String::new(),
                 };
                 let line_number_prefix = match line_num_style {
                     LineNumberStyle::None => "".to_string(),
@@ -111,7 +111,7 @@ impl<'a> Widget for TuiDirListDetailed<'a> {
                     entry,
                     style,
                     (x + 1, y + i as u16),
-                    self.tab_display_options.linemode,
+                    (self.tab_display_options.linemode, self.display_options),
                     drawing_width - 1,
                     &prefix,
                 );
@@ -135,7 +135,7 @@ fn print_entry(
     entry: &JoshutoDirEntry,
     style: Style,
     (x, y): (u16, u16),
-    linemode: LineMode,
+    (linemode, display_options): (LineMode, &DisplayOption),
     drawing_width: usize,
     prefix: &str,
 ) {
@@ -150,8 +150,14 @@ fn print_entry(
         linemode
             .iter_names()
             .map(|f| match f.0 {
+                // placeholder until the metadata is loaded, see `lazy_metadata`
+                _ if !entry.metadata.is_loaded() => "-".to_string(),
+                _ if entry.metadata.load_failed() => "?".to_string(),
                 "size" => get_entry_size_string(entry),
-                "mtime" => format::mtime_to_string(entry.metadata.modified()),
+                "mtime" => format::time_to_string(
+                    entry.metadata.modified(),
+                    display_options.date_format(),
+                ),
                 "user" => unix::uid_to_string(entry.metadata.uid).unwrap_or("unknown".into()),
                 "group" => unix::gid_to_string(entry.metadata.gid).unwrap_or("unknown".into()),
                 "perm" => unix::mode_to_string(entry.metadata.mode),
@@ -166,13 +172,18 @@ fn print_entry(
     buf.set_stringn(x, y, prefix, prefix_width, Style::default());
     let x = x + prefix_width as u16;
 
-    // factor left_label and right_label
+    // factor left_label and right_label, leaving room for the marker
     let drawing_width = drawing_width - prefix_width;
-    let (left_label, right_label) = factor_labels_for_entry(
+    let marker = entry.marker();
+    let (mut left_label, right_label) = factor_labels_for_entry(
         left_label_original,
         right_label_original.as_str(),
-        drawing_width,
+        drawing_width.saturating_sub(marker.width()),
+        display_options.middle_ellipsis(),
     );
+    if !left_label.is_empty() {
+        left_label.push_str(marker);
+    }
 
     // Draw labels
     buf.set_stringn(x, y, left_label, drawing_width, style);
@@ -189,7 +200,13 @@ fn factor_labels_for_entry<'a>(
     left_label_original: &'a str,
     right_label_original: &'a str,
     drawing_width: usize,
+    middle_ellipsis: bool,
 ) -> (String, &'a str) {
+    let trim = if middle_ellipsis {
+        trim_file_label_middle
+    } else {
+        trim_file_label
+    };
     let left_label_original_width = left_label_original.width();
     let right_label_original_width = right_label_original.width();
 
@@ -203,7 +220,7 @@ fn factor_labels_for_entry<'a>(
     } else if left_width_remainder < MIN_LEFT_LABEL_WIDTH {
         (
             if left_label_original.width() as i32 <= left_width_remainder {
-                trim_file_label(left_label_original, drawing_width)
+                trim(left_label_original, drawing_width)
             } else {
                 left_label_original.to_string()
             },
@@ -211,7 +228,7 @@ fn factor_labels_for_entry<'a>(
         )
     } else {
         (
-            trim_file_label(left_label_original, left_width_remainder as usize),
+            trim(left_label_original, left_width_remainder as usize),
             right_label_original,
         )
     }
@@ -239,6 +256,11 @@ pub fn trim_file_label(name: &str, drawing_width: usize) -> String {
                 format!("{}{}{}", truncated_stem, ELLIPSIS, extension)
             }
             Ordering::Equal => extension.replacen('.', ELLIPSIS, 1),
+            Ordering::Greater if drawing_width < 3 => {
+                let mut truncated = name.trunc(drawing_width - 1);
+                truncated.push_str(ELLIPSIS);
+                truncated
+            }
             Ordering::Greater => {
                 // file ext does not fit
                 let stem_width = drawing_width;
@@ -249,6 +271,27 @@ pub fn trim_file_label(name: &str, drawing_width: usize) -> String {
     }
 }
 
+/// Like `trim_file_label`, but keeps the beginning and the end of the stem
+pub fn trim_file_label_middle(name: &str, drawing_width: usize) -> String {
+    // pre-condition: string name is longer than width
+    let (stem, extension) = match name.rfind('.') {
+        Some(i) if i > 0 => name.split_at(i),
+        _ => (name, ""),
+    };
+    let ext_width = extension.width();
+    if drawing_width < 1 {
+        "".to_string()
+    } else if ext_width + 3 > drawing_width {
+        // no room for both ends of the stem
+        trim_file_label(name, drawing_width)
+    } else {
+        let stem_width = drawing_width - ext_width - 1;
+        let head = stem.trunc((stem_width + 1) / 2);
+        let tail = stem.trunc_start(stem_width - head.width());
+        format!("{}{}{}{}", head, ELLIPSIS, tail, extension)
+    }
+}
+
 #[cfg(test)]
 mod test_factor_labels {
     use super::{factor_labels_for_entry, MIN_LEFT_LABEL_WIDTH};
@@ -259,7 +302,7 @@ mod test_factor_labels {
         let right = "right";
         assert_eq!(
             ("".to_string(), ""),
-            factor_labels_for_entry(left, right, 0)
+            factor_labels_for_entry(left, right, 0, false)
         );
     }
 
@@ -269,7 +312,7 @@ mod test_factor_labels {
         let right = "right";
         assert_eq!(
             (left.to_string(), right),
-            factor_labels_for_entry(left, right, 20)
+            factor_labels_for_entry(left, right, 20, false)
         );
     }
 
@@ -279,7 +322,7 @@ mod test_factor_labels {
         let right = "right";
         assert_eq!(
             (left.to_string(), right),
-            factor_labels_for_entry(left, right, 12)
+            factor_labels_for_entry(left, right, 12, false)
         );
     }
 
@@ -290,7 +333,7 @@ mod test_factor_labels {
         assert!(left.chars().count() as i32 == MIN_LEFT_LABEL_WIDTH);
         assert_eq!(
             ("foobarbazfo.ext".to_string(), ""),
-            factor_labels_for_entry(left, right, MIN_LEFT_LABEL_WIDTH as usize)
+            factor_labels_for_entry(left, right, MIN_LEFT_LABEL_WIDTH as usize, false)
         );
     }
 
@@ -304,7 +347,8 @@ mod test_factor_labels {
             factor_labels_for_entry(
                 left,
                 right,
-                MIN_LEFT_LABEL_WIDTH as usize + right.chars().count()
+                MIN_LEFT_LABEL_WIDTH as usize + right.chars().count(),
+                false
             )
         );
     }
@@ -318,14 +362,14 @@ mod test_factor_labels {
         assert!(left.chars().count() as i32 > MIN_LEFT_LABEL_WIDTH);
         assert_eq!(
             ("foooooobaaaaaaarbaaaa…".to_string(), right),
-            factor_labels_for_entry(left, right, left.chars().count())
+            factor_labels_for_entry(left, right, left.chars().count(), false)
         );
     }
 }
 
 #[cfg(test)]
 mod test_trim_file_label {
-    use super::trim_file_label;
+    use super::{trim_file_label, trim_file_label_middle};
 
     #[test]
     fn dotfiles_get_an_ellipsis_at_the_end_if_they_dont_fit() {
@@ -374,4 +418,31 @@ mod test_trim_file_label {
         let left = "🌕🌕🌕";
         assert_eq!("🌕🌕…".to_string(), trim_file_label(left, 5));
     }
+
+    #[test]
+    fn if_the_extension_is_wider_than_the_drawing_width_nothing_overflows() {
+        let left = "a.bcdef";
+        assert_eq!("a…".to_string(), trim_file_label(left, 2));
+    }
+
+    #[test]
+    fn middle_ellipsis_keeps_both_ends_of_the_stem_and_the_extension() {
+        let left = "holiday_photos_2021.tar";
+        assert_eq!(
+            "holi…2021.tar".to_string(),
+            trim_file_label_middle(left, 13)
+        );
+    }
+
+    #[test]
+    fn middle_ellipsis_accounts_for_full_width_characters() {
+        let left = "🌕🌕🌕🌕.txt";
+        assert_eq!("🌕…🌕.txt".to_string(), trim_file_label_middle(left, 9));
+    }
+
+    #[test]
+    fn middle_ellipsis_falls_back_if_the_extension_barely_fits() {
+        let left = "foo.ext";
+        assert_eq!("f….ext".to_string(), trim_file_label_middle(left, 6));
+    }
 }
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Widget;
+
+use crate::util::duplicates::DuplicateGroup;
+use crate::util::format;
+
+const FOOTER: &str =
+    "<space>: mark  a: mark all but first  u: unmark all  d: delete marked  <ESC>: return";
+
+/// Groups of duplicate files, see `show_duplicates`
+pub struct TuiDuplicates<'a> {
+    root: &'a Path,
+    groups: &'a [DuplicateGroup],
+    // index of the selected file, counting the files of all groups
+    cursor: usize,
+    marked: &'a HashSet<PathBuf>,
+}
+
+impl<'a> TuiDuplicates<'a> {
+    pub fn new(
+        root: &'a Path,
+        groups: &'a [DuplicateGroup],
+        cursor: usize,
+        marked: &'a HashSet<PathBuf>,
+    ) -> Self {
+        Self {
+            root,
+            groups,
+            cursor,
+            marked,
+        }
+    }
+}
+
+impl<'a> Widget for TuiDuplicates<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 3 {
+            return;
+        }
+        let bar_style = Style::default().add_modifier(Modifier::REVERSED);
+        let header_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let marked_style = Style::default().fg(Color::Red);
+        let width = area.width as usize;
+        let height = area.height as usize - 2;
+
+        // (text, style, is the cursor on this line)
+        let mut lines = Vec::new();
+        let mut file_index = 0;
+        for group in self.groups {
+            lines.push((
+                format!(
+                    "{} copies of {}",
+                    group.paths.len(),
+                    format::file_size_to_string(group.size).trim()
+                ),
+                header_style,
+                false,
+            ));
+            for path in group.paths.iter() {
+                let marked = self.marked.contains(path);
+                let name = path.strip_prefix(self.root).unwrap_or(path);
+                let style = if marked {
+                    marked_style
+                } else {
+                    Style::default()
+                };
+                lines.push((
+                    format!("  [{}] {}", if marked { "x" } else { " " }, name.display()),
+                    style,
+                    file_index == self.cursor,
+                ));
+                file_index += 1;
+            }
+        }
+
+        let cursor_line = lines.iter().position(|(_, _, c)| *c).unwrap_or(0);
+        let offset = (cursor_line + 1).saturating_sub(height);
+
+        let title = format!("Duplicates in {}", self.root.display());
+        buf.set_stringn(
+            area.x,
+            area.y,
+            format!("{:^w$}", title, w = width),
+            width,
+            bar_style,
+        );
+        for (i, (text, style, cursor)) in lines.iter().skip(offset).take(height).enumerate() {
+            let style = if *cursor {
+                style.add_modifier(Modifier::REVERSED)
+            } else {
+                *style
+            };
+            buf.set_stringn(area.x, area.y + 1 + i as u16, text, width, style);
+        }
+        if lines.is_empty() {
+            buf.set_stringn(
+                area.x,
+                area.y + 1,
+                "No duplicates found",
+                width,
+                Style::default(),
+            );
+        }
+        buf.set_stringn(
+            area.x,
+            area.bottom() - 1,
+            format!("{:<w$}", FOOTER, w = width),
+            width,
+            bar_style,
+        );
+    }
+}
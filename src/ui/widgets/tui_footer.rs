@@ -6,6 +6,7 @@ use ratatui::widgets::{Paragraph, Widget};
 
 use crate::config::clean::app::display::tab::TabDisplayOption;
 use crate::fs::{JoshutoDirList, LinkType};
+use crate::history::folder_sort_options;
 use crate::util::format;
 use crate::util::unix;
 use crate::{THEME_T, TIMEZONE_STR};
@@ -29,19 +30,27 @@ impl<'a> Widget for TuiFooter<'a> {
         #[cfg(unix)]
         use std::os::unix::fs::PermissionsExt;
 
+        let theme = THEME_T.read().unwrap();
         let visual_mode_style = Style::default().fg(Color::Black).bg(Color::LightRed);
         let mode_style = Style::default().fg(Color::Cyan);
 
         // flat and filter commands indicator style
         let indicator_style = Style::default()
             .fg(Color::LightBlue)
-            .add_modifier(THEME_T.selection.modifier);
+            .add_modifier(theme.selection.modifier);
 
         let selection_style = Style::default()
-            .fg(THEME_T.selection.fg)
-            .bg(THEME_T.selection.bg)
-            .add_modifier(THEME_T.selection.modifier);
+            .fg(theme.selection.fg)
+            .bg(theme.selection.bg)
+            .add_modifier(theme.selection.modifier);
         let selected_count = self.dirlist.selected_count();
+        // the size of directories is the number of their entries, if known
+        let selected_size: u64 = self
+            .dirlist
+            .iter_selected()
+            .filter(|e| !e.metadata.file_type().is_dir())
+            .map(|e| e.metadata.len())
+            .sum();
 
         match self.dirlist.get_index() {
             Some(i) if i < self.dirlist.len() => {
@@ -56,6 +65,17 @@ impl<'a> Widget for TuiFooter<'a> {
                 let size_str = format::file_size_to_string(entry.metadata.len());
 
                 let path = self.dirlist.file_path();
+                let sort_options = folder_sort_options(path, self.tab_options);
+                let sort_str = format!(
+                    "sort:{}{} ",
+                    sort_options
+                        .sort_methods
+                        .list
+                        .front()
+                        .map(|s| s.as_str())
+                        .unwrap_or_default(),
+                    if sort_options.reverse { "(rev)" } else { "" }
+                );
 
                 let mut text = vec![
                     Span::styled(
@@ -99,9 +119,14 @@ impl<'a> Widget for TuiFooter<'a> {
                         },
                         indicator_style,
                     ),
+                    Span::styled(sort_str, indicator_style),
                     Span::styled(
                         if selected_count > 0 {
-                            format!("{} selected", selected_count)
+                            format!(
+                                "{} selected ({})",
+                                selected_count,
+                                format::file_size_to_string(selected_size)
+                            )
                         } else {
                             " ".to_string()
                         },
@@ -112,14 +137,14 @@ impl<'a> Widget for TuiFooter<'a> {
                 if let LinkType::Symlink { target, valid } = entry.metadata.link_type() {
                     let link_style = if *valid {
                         Style::default()
-                            .fg(THEME_T.link.fg)
-                            .bg(THEME_T.link.bg)
-                            .add_modifier(THEME_T.link.modifier)
+                            .fg(theme.link.fg)
+                            .bg(theme.link.bg)
+                            .add_modifier(theme.link.modifier)
                     } else {
                         Style::default()
-                            .fg(THEME_T.link_invalid.fg)
-                            .bg(THEME_T.link_invalid.bg)
-                            .add_modifier(THEME_T.link_invalid.modifier)
+                            .fg(theme.link_invalid.fg)
+                            .bg(theme.link_invalid.bg)
+                            .add_modifier(theme.link_invalid.modifier)
                     };
                     text.push(Span::styled(" -> ", link_style));
                     text.push(Span::styled(target, link_style));
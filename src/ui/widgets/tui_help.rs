@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use lazy_static::lazy_static;
 
 use termion::event::{Event, Key};
@@ -9,12 +11,19 @@ use ratatui::widgets::{Cell, Row, Table, Widget};
 
 use crate::config::clean::keymap::KeyMapping;
 use crate::key_command::traits::CommandComment;
-use crate::key_command::CommandKeybind;
+use crate::key_command::{
+    command_category, command_comment, commands, AppCommand, Command, CommandKeybind,
+    COMMAND_CATEGORIES,
+};
+use crate::util::keyparse;
 
 lazy_static! {
     static ref COMMENT_STYLE: Style = Style::default().add_modifier(Modifier::REVERSED);
     static ref DEFAULT_STYLE: Style = Style::default();
     static ref HEADER_STYLE: Style = Style::default().fg(Color::Yellow);
+    static ref CATEGORY_STYLE: Style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
     static ref KEY_STYLE: Style = Style::default().fg(Color::Green);
     static ref COMMAND_STYLE: Style = Style::default().fg(Color::Blue);
 }
@@ -25,12 +34,12 @@ const FOOTER: &str = "Press <ESC> to return, / to search, 1,2,3 to sort";
 pub struct TuiHelp<'a> {
     // This keymap is constructed with get_keymap_table function
     keymap: &'a [Row<'a>],
-    offset: &'a mut u8,
+    offset: &'a mut usize,
     search_query: &'a str,
 }
 
 impl<'a> TuiHelp<'a> {
-    pub fn new(keymap: &'a [Row], offset: &'a mut u8, search_query: &'a str) -> TuiHelp<'a> {
+    pub fn new(keymap: &'a [Row], offset: &'a mut usize, search_query: &'a str) -> TuiHelp<'a> {
         TuiHelp {
             keymap,
             offset,
@@ -43,15 +52,15 @@ impl<'a> Widget for TuiHelp<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Subtracting 2 because we'll draw a title at the top and some
         // additional information at the bottom of the page
-        let height = (area.bottom() - area.top() - 2) as i16;
+        let height = (area.bottom() - area.top()).saturating_sub(2);
         let width = area.right() - area.left();
-        let max_offset = Ord::max(self.keymap.len() as i16 - height + 2, 0) as u8;
+        let max_offset = (self.keymap.len() + 2).saturating_sub(height as usize);
         if *self.offset > max_offset {
             *self.offset = max_offset;
         }
-        let keymap = Vec::from(&self.keymap[(*self.offset as usize)..]);
+        let keymap = Vec::from(&self.keymap[*self.offset..]);
 
-        let keybindings_area = Rect::new(0, 1, width, height as u16);
+        let keybindings_area = Rect::new(0, 1, width, height);
         let mut keybindings_buffer = Buffer::default();
         keybindings_buffer.resize(keybindings_area);
         let widths = [
@@ -82,13 +91,7 @@ impl<'a> Widget for TuiHelp<'a> {
         } else {
             format!("{:<w$}", self.search_query, w = width as usize)
         };
-        buf.set_stringn(
-            0,
-            (height + 1) as u16,
-            &footer,
-            footer.len(),
-            *COMMENT_STYLE,
-        );
+        buf.set_stringn(0, height + 1, &footer, footer.len(), *COMMENT_STYLE);
     }
 }
 
@@ -99,9 +102,18 @@ pub fn get_keymap_table<'a>(
     search_query: &'a str,
     sort_by: usize,
 ) -> Vec<Row<'a>> {
-    let raw_rows = get_raw_keymap_table(keymap, search_query, sort_by);
+    let mut raw_rows = get_raw_keymap_table(keymap, search_query, sort_by);
+    raw_rows.extend(get_unbound_commands_table(keymap, search_query));
+    // group rows by category, keeping the requested order inside each group
+    raw_rows.sort_by_cached_key(|x| (category_index(&x[3]), x[sort_by].clone()));
+
     let mut rows = Vec::new();
+    let mut curr_category = "";
     for row in raw_rows {
+        if row[3] != curr_category {
+            rows.push(Row::new(vec![Cell::from(row[3].clone())]).style(*CATEGORY_STYLE));
+            curr_category = COMMAND_CATEGORIES[category_index(&row[3])];
+        }
         rows.push(Row::new(vec![
             Cell::from(row[0].clone()).style(*KEY_STYLE),
             Cell::from(row[1].clone()).style(*COMMAND_STYLE),
@@ -113,50 +125,119 @@ pub fn get_keymap_table<'a>(
 
 // This function is needed because we cannot access Row items, which
 // means that we won't be able to sort binds if we create Rows directly
+//
+// Each row is made of the key, command, description and category
 pub fn get_raw_keymap_table<'a>(
     keymap: &'a KeyMapping,
     search_query: &'a str,
     sort_by: usize,
-) -> Vec<[String; 3]> {
+) -> Vec<[String; 4]> {
     let search_query_lowercase = &String::from(search_query).to_lowercase();
     let mut rows = Vec::new();
     for (event, bind) in keymap.iter() {
         let key = key_event_to_string(event);
-        let (command, comment) = match bind {
+        let (command, comment, category) = match bind {
             CommandKeybind::SimpleKeybind {
                 commands,
                 description: Some(desc),
-            } => (format!("{}", commands[0]), desc.as_str()),
+            } => (
+                format!("{}", commands[0]),
+                desc.as_str(),
+                command_category(commands[0].command()),
+            ),
             CommandKeybind::SimpleKeybind {
                 commands,
                 description: None,
-            } => (format!("{}", commands[0]), commands[0].comment()),
+            } => (
+                format!("{}", commands[0]),
+                commands[0].comment(),
+                command_category(commands[0].command()),
+            ),
             CommandKeybind::CompositeKeybind(sub_keymap) => {
                 let mut sub_rows = get_raw_keymap_table(sub_keymap, "", sort_by);
                 for _ in 0..sub_rows.len() {
                     let mut sub_row = sub_rows.pop().unwrap();
-                    sub_row[0] = key.clone() + &sub_row[0];
-                    if sub_row[0].contains(search_query)
-                        || sub_row[1].contains(search_query)
-                        || sub_row[2].to_lowercase().contains(search_query_lowercase)
-                    {
+                    // `&String` does not coerce here, `smartstring` (used by rhai)
+                    // implements `Add<&String>` for its own types as well
+                    sub_row[0] = key.clone() + sub_row[0].as_str();
+                    if row_matches(&sub_row, search_query, search_query_lowercase) {
                         rows.push(sub_row)
                     }
                 }
                 continue;
             }
         };
-        if key.contains(search_query)
-            || command.contains(search_query)
-            || comment.to_lowercase().contains(search_query_lowercase)
-        {
-            rows.push([key, command, comment.to_string()]);
+        let row = [key, command, comment.to_string(), category.to_string()];
+        if row_matches(&row, search_query, search_query_lowercase) {
+            rows.push(row);
         }
     }
     rows.sort_by_cached_key(|x| x[sort_by].clone());
     rows
 }
 
+// Commands that are not bound to any key, so they can still be
+// discovered from the help page and run from the command line
+pub fn get_unbound_commands_table(keymap: &KeyMapping, search_query: &str) -> Vec<[String; 4]> {
+    let search_query_lowercase = &String::from(search_query).to_lowercase();
+    let mut bound = HashSet::new();
+    get_bound_commands(keymap, &mut bound);
+
+    commands()
+        .into_iter()
+        .filter(|command| !bound.contains(command))
+        .map(|command| {
+            [
+                String::new(),
+                command.to_string(),
+                command_comment(command).to_string(),
+                command_category(command).to_string(),
+            ]
+        })
+        .filter(|row| row_matches(row, search_query, search_query_lowercase))
+        .collect()
+}
+
+/// The commands bound to `key` as it is shown in the keymap table, e.g. `gh`
+pub fn get_keybind_commands<'a>(keymap: &'a KeyMapping, key: &str) -> Option<&'a [Command]> {
+    keymap.iter().find_map(|(event, bind)| {
+        let rest = key.strip_prefix(key_event_to_string(event).as_str())?;
+        match bind {
+            CommandKeybind::SimpleKeybind { commands, .. } if rest.is_empty() => {
+                Some(commands.as_slice())
+            }
+            CommandKeybind::CompositeKeybind(sub_keymap) if !rest.is_empty() => {
+                get_keybind_commands(sub_keymap, rest)
+            }
+            _ => None,
+        }
+    })
+}
+
+fn get_bound_commands(keymap: &KeyMapping, bound: &mut HashSet<&'static str>) {
+    for bind in keymap.values() {
+        match bind {
+            CommandKeybind::SimpleKeybind { commands, .. } => {
+                bound.extend(commands.iter().map(|c| c.command()));
+            }
+            CommandKeybind::CompositeKeybind(sub_keymap) => get_bound_commands(sub_keymap, bound),
+        }
+    }
+}
+
+fn row_matches(row: &[String; 4], search_query: &str, search_query_lowercase: &str) -> bool {
+    row[0].contains(search_query)
+        || row[1].contains(search_query)
+        || row[2].to_lowercase().contains(search_query_lowercase)
+}
+
+fn category_index(category: &str) -> usize {
+    COMMAND_CATEGORIES
+        .iter()
+        .position(|c| *c == category)
+        .unwrap_or(COMMAND_CATEGORIES.len() - 1)
+}
+
 fn key_event_to_string(event: &Event) -> String {
     match event {
         Event::Key(key) => match key {
@@ -184,6 +265,7 @@ fn key_event_to_string(event: &Event) -> String {
             Key::Ctrl(chr) => format!("Ctrl+{}", chr),
             _ => "".to_string(),
         },
+        Event::Unsupported(v) => keyparse::unsupported_to_string(v).unwrap_or_default(),
         _ => "".to_string(),
     }
 }
@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::Widget;
+
+use crate::context::Message;
+
+const TITLE: &str = "Messages";
+const FOOTER: &str = "Press <ESC> to return";
+
+pub struct TuiMessageLog<'a> {
+    log: &'a VecDeque<Message>,
+    offset: &'a mut usize,
+}
+
+impl<'a> TuiMessageLog<'a> {
+    pub fn new(log: &'a VecDeque<Message>, offset: &'a mut usize) -> Self {
+        Self { log, offset }
+    }
+}
+
+impl<'a> Widget for TuiMessageLog<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 3 {
+            return;
+        }
+        let title_style = Style::default().add_modifier(Modifier::REVERSED);
+        let width = area.width as usize;
+        // the title and footer take up one line each
+        let height = area.height as usize - 2;
+
+        let max_offset = self.log.len().saturating_sub(height);
+        if *self.offset > max_offset {
+            *self.offset = max_offset;
+        }
+
+        buf.set_stringn(
+            area.x,
+            area.y,
+            format!("{:^w$}", TITLE, w = width),
+            width,
+            title_style,
+        );
+
+        if self.log.is_empty() {
+            buf.set_stringn(area.x, area.y + 1, "No messages", width, Style::default());
+        }
+        for (i, message) in self.log.iter().skip(*self.offset).take(height).enumerate() {
+            let line = format!(
+                "{} {:<7} {}",
+                message.timestamp.format("%H:%M:%S"),
+                message.level.as_str(),
+                message.content
+            );
+            buf.set_stringn(area.x, area.y + 1 + i as u16, line, width, message.style);
+        }
+
+        buf.set_stringn(
+            area.x,
+            area.bottom() - 1,
+            format!("{:^w$}", FOOTER, w = width),
+            width,
+            title_style,
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+
+/// Read-only view of some lines with line numbers, used by the `view` command
+pub struct TuiPager<'a> {
+    title: &'a str,
+    lines: &'a [String],
+    offset: &'a mut usize,
+    footer: &'a str,
+    // lines containing this are highlighted
+    pattern: &'a str,
+    // `lines` with syntax highlighting
+    styled_lines: Option<&'a [Line<'a>]>,
+}
+
+impl<'a> TuiPager<'a> {
+    pub fn new(
+        title: &'a str,
+        lines: &'a [String],
+        offset: &'a mut usize,
+        footer: &'a str,
+        pattern: &'a str,
+    ) -> Self {
+        Self {
+            title,
+            lines,
+            offset,
+            footer,
+            pattern,
+            styled_lines: None,
+        }
+    }
+
+    pub fn styled_lines(mut self, styled_lines: Option<&'a [Line<'a>]>) -> Self {
+        self.styled_lines = styled_lines;
+        self
+    }
+}
+
+impl<'a> Widget for TuiPager<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 3 {
+            return;
+        }
+        let bar_style = Style::default().add_modifier(Modifier::REVERSED);
+        let number_style = Style::default().fg(Color::DarkGray);
+        let match_style = Style::default().fg(Color::Yellow);
+        let width = area.width as usize;
+        // the title and footer take up one line each
+        let height = area.height as usize - 2;
+
+        let max_offset = self.lines.len().saturating_sub(height);
+        if *self.offset > max_offset {
+            *self.offset = max_offset;
+        }
+
+        buf.set_stringn(
+            area.x,
+            area.y,
+            format!("{:^w$}", self.title, w = width),
+            width,
+            bar_style,
+        );
+
+        let number_width = self.lines.len().to_string().len();
+        for (i, line) in self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(*self.offset)
+            .take(height)
+        {
+            let y = area.y + 1 + (i - *self.offset) as u16;
+            let number = format!("{:>w$} ", i + 1, w = number_width);
+            let (x, _) = buf.set_stringn(area.x, y, &number, width, number_style);
+
+            let remaining = (area.right() - x) as usize;
+            if !self.pattern.is_empty() && line.contains(self.pattern) {
+                buf.set_stringn(x, y, line, remaining, match_style);
+            } else if let Some(styled_line) = self.styled_lines.and_then(|lines| lines.get(i)) {
+                buf.set_line(x, y, styled_line, remaining as u16);
+            } else {
+                buf.set_stringn(x, y, line, remaining, Style::default());
+            }
+        }
+
+        let position = format!("{}/{}", *self.offset + 1, self.lines.len());
+        buf.set_stringn(
+            area.x,
+            area.bottom() - 1,
+            format!("{:<w$}", self.footer, w = width),
+            width,
+            bar_style,
+        );
+        if position.len() + self.footer.len() < width {
+            buf.set_stringn(
+                area.right() - position.len() as u16,
+                area.bottom() - 1,
+                &position,
+                position.len(),
+                bar_style,
+            );
+        }
+    }
+}
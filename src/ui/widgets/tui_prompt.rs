@@ -20,11 +20,9 @@ impl<'a> TuiPrompt<'a> {
     }
 
     pub fn get_key(&mut self, backend: &mut AppBackend, context: &mut AppContext) -> Key {
-        let terminal = backend.terminal_mut();
-
         context.flush_event();
         loop {
-            let _ = terminal.draw(|frame| {
+            let _ = backend.terminal_mut().draw(|frame| {
                 let f_size: Rect = frame.size();
                 if f_size.height == 0 {
                     return;
@@ -62,7 +60,11 @@ impl<'a> TuiPrompt<'a> {
                     AppEvent::Termion(_) => {
                         context.flush_event();
                     }
-                    event => process_event::process_noninteractive(event, context),
+                    event => {
+                        if process_event::process_in_modal(event, context, backend) {
+                            return Key::Esc;
+                        }
+                    }
                 };
             }
         }
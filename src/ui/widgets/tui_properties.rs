@@ -0,0 +1,97 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Clear, Widget};
+
+const FOOTER: &str = "1: md5  2: sha1  3: sha256  <ESC>: close";
+
+/// Popup listing `(name, value)` pairs, used by `show_properties` and `xattr_list`
+pub struct TuiProperties<'a> {
+    title: &'a str,
+    rows: &'a [(String, String)],
+    footer: &'a str,
+}
+
+impl<'a> TuiProperties<'a> {
+    pub fn new(title: &'a str, rows: &'a [(String, String)]) -> Self {
+        Self {
+            title,
+            rows,
+            footer: FOOTER,
+        }
+    }
+
+    pub fn footer(mut self, footer: &'a str) -> Self {
+        self.footer = footer;
+        self
+    }
+}
+
+impl<'a> Widget for TuiProperties<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let name_width = self
+            .rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        let content_width = self
+            .rows
+            .iter()
+            .map(|(_, value)| name_width + 2 + value.len())
+            .max()
+            .unwrap_or(0)
+            .max(self.title.len())
+            .max(self.footer.len());
+
+        // borders and a blank line before the footer
+        let width = (content_width as u16 + 4).min(area.width);
+        let height = (self.rows.len() as u16 + 4).min(area.height);
+        if width < 4 || height < 4 {
+            return;
+        }
+        let rect = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        Clear.render(rect, buf);
+        Block::default()
+            .borders(Borders::ALL)
+            .title(self.title)
+            .render(rect, buf);
+
+        let name_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let inner_width = (rect.width - 4) as usize;
+        let x = rect.x + 2;
+        for (i, (name, value)) in self
+            .rows
+            .iter()
+            .take((rect.height - 4) as usize)
+            .enumerate()
+        {
+            let y = rect.y + 1 + i as u16;
+            buf.set_stringn(x, y, name, inner_width, name_style);
+            if name_width + 2 < inner_width {
+                buf.set_stringn(
+                    x + name_width as u16 + 2,
+                    y,
+                    value,
+                    inner_width - name_width - 2,
+                    Style::default(),
+                );
+            }
+        }
+        buf.set_stringn(
+            x,
+            rect.bottom() - 2,
+            self.footer,
+            inner_width,
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
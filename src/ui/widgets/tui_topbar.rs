@@ -50,7 +50,7 @@ impl<'a> Widget for TuiTopBar<'a> {
             available_tab_width,
             &paths,
             self.context.tab_context_ref().index,
-            &THEME_T.tabs,
+            &THEME_T.read().unwrap().tabs,
         );
         top_bar_spans.extend(tab_bar_spans);
         Paragraph::new(Line::from(top_bar_spans)).render(area, buf);
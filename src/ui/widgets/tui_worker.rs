@@ -7,13 +7,17 @@ use crate::context::WorkerContext;
 use crate::io::{FileOperationProgress, IoWorkerObserver};
 use crate::util::format;
 
+// rows taken by each running worker, including the blank row after it
+const WORKER_HEIGHT: u16 = 4;
+
 pub struct TuiWorker<'a> {
     pub context: &'a WorkerContext,
+    pub max_tasks: usize,
 }
 
 impl<'a> TuiWorker<'a> {
-    pub fn new(context: &'a WorkerContext) -> Self {
-        Self { context }
+    pub fn new(context: &'a WorkerContext, max_tasks: usize) -> Self {
+        Self { context, max_tasks }
     }
 }
 
@@ -22,37 +26,49 @@ impl<'a> Widget for TuiWorker<'a> {
         if area.height < 7 {
             return;
         }
-        match self.context.worker_ref() {
-            Some(io_obs) => {
+        let mut y = area.y + 1;
+        if self.context.is_busy() {
+            for io_obs in self.context.workers_iter() {
+                // keep room for the queue header
+                if y + WORKER_HEIGHT > area.bottom().saturating_sub(2) {
+                    break;
+                }
                 if let Some(progress) = io_obs.progress.as_ref() {
-                    let current_area = Rect {
-                        y: area.y + 1,
-                        ..area
-                    };
+                    let current_area = Rect { y, ..area };
                     TuiCurrentWorker::new(io_obs, progress).render(current_area, buf);
                 }
-
-                // draw queued up work
-                let style = Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD);
-                buf.set_stringn(0, 6, "Queue:", area.width as usize, style);
-
-                let queue_area = Rect {
-                    y: area.y + 7,
-                    ..area
-                };
-                TuiWorkerQueue::new(self.context).render(queue_area, buf);
-            }
-            _ => {
-                let style = Style::default();
-                buf.set_stringn(0, 2, "No operations running", area.width as usize, style);
-                let style = Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD);
-                buf.set_stringn(0, 4, "Queue:", area.width as usize, style);
+                y += WORKER_HEIGHT;
             }
+        } else {
+            let style = Style::default();
+            buf.set_stringn(
+                area.x,
+                y,
+                "No operations running",
+                area.width as usize,
+                style,
+            );
+            y += 2;
         }
+
+        // draw queued up work
+        let style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let header = format!(
+            "Queue: {} waiting, {}/{} running",
+            self.context.iter().count(),
+            self.context.running_count(),
+            self.max_tasks
+        );
+        buf.set_stringn(area.x, y, header, area.width as usize, style);
+
+        let queue_area = Rect {
+            y: y + 1,
+            height: area.bottom().saturating_sub(y + 1),
+            ..area
+        };
+        TuiWorkerQueue::new(self.context).render(queue_area, buf);
     }
 }
 
@@ -139,7 +155,7 @@ impl<'a> Widget for TuiWorkerQueue<'a> {
 
         let style = Style::default();
 
-        for (i, worker) in self.context.iter().enumerate() {
+        for (i, worker) in self.context.iter().take(area.height as usize).enumerate() {
             let msg = format!(
                 "{:02} {} {} items {:?}",
                 i + 1,
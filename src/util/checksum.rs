@@ -0,0 +1,174 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppResult;
+
+use super::digest::Digest;
+
+/// Hash algorithms, with the same results as the coreutils `*sum` programs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "md5" => Some(Self::Md5),
+            "sha1" => Some(Self::Sha1),
+            "sha256" => Some(Self::Sha256),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Md5 => "md5",
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+        }
+    }
+}
+
+/// Computes the checksum of `path` as a lowercase hex string
+pub fn checksum(path: &Path, algorithm: ChecksumAlgorithm) -> AppResult<String> {
+    let mut file = fs::File::open(path)?;
+    let mut digest = Digest::new(algorithm);
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        digest.update(&buf[..n]);
+    }
+    Ok(digest.finish_hex())
+}
+
+/// Where the results of a checksum thread are shown
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumOutput {
+    Messages,
+    /// also copied to the clipboard, see `checksum --copy`
+    Clipboard,
+    /// the popup of `show_properties`, as messages once it is closed
+    Properties,
+}
+
+/// Outcome of checking one file, see `verify_checksums`
+#[derive(Clone, Debug)]
+pub struct ChecksumEntry {
+    pub path: PathBuf,
+    // the checksum the file is supposed to have, when verifying
+    pub expected: Option<String>,
+    pub result: Result<String, String>,
+}
+
+impl ChecksumEntry {
+    pub fn mismatch(&self) -> bool {
+        match (&self.expected, &self.result) {
+            (Some(expected), Ok(sum)) => !expected.eq_ignore_ascii_case(sum),
+            _ => false,
+        }
+    }
+}
+
+/// Reads the checksums listed in a `sha256sum` style file.
+///
+/// Returns the files with their expected checksum, relative paths are relative to `list`
+pub fn read_checksum_list(list: &Path) -> AppResult<Vec<(PathBuf, String)>> {
+    let content = fs::read_to_string(list)?;
+    let dir = list.parent().unwrap_or_else(|| Path::new(""));
+    let entries = content
+        .lines()
+        .filter_map(parse_checksum_line)
+        .map(|(sum, file)| (dir.join(file), sum.to_string()))
+        .collect();
+    Ok(entries)
+}
+
+/// A line of a `sha256sum` style file, names with a backslash or newline are escaped
+pub fn format_checksum_line(sum: &str, name: &str) -> String {
+    if name.contains(['\\', '\n', '\r']) {
+        let name = name
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        format!("\\{}  {}", sum, name)
+    } else {
+        format!("{}  {}", sum, name)
+    }
+}
+
+// lines look like "<sum>  <file>", or "<sum> *<file>" for binary mode.
+// A leading '\' means backslashes and newlines in the file name are escaped
+fn parse_checksum_line(line: &str) -> Option<(&str, String)> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    let (sum, file) = line.split_once(' ')?;
+    let file = file
+        .strip_prefix(' ')
+        .or_else(|| file.strip_prefix('*'))
+        .unwrap_or(file);
+    if sum.is_empty() || file.is_empty() || !sum.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    if escaped {
+        Some((sum, unescape_name(file)))
+    } else {
+        Some((sum, file.to_string()))
+    }
+}
+
+fn unescape_name(name: &str) -> String {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_checksum_line() {
+        assert_eq!(
+            parse_checksum_line("d41d8cd98f00b204e9800998ecf8427e  empty file.txt"),
+            Some((
+                "d41d8cd98f00b204e9800998ecf8427e",
+                "empty file.txt".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_checksum_line("d41d8cd98f00b204e9800998ecf8427e *data.bin"),
+            Some(("d41d8cd98f00b204e9800998ecf8427e", "data.bin".to_string()))
+        );
+        assert_eq!(
+            parse_checksum_line("\\d41d8cd98f00b204e9800998ecf8427e  a\\nb\\\\c"),
+            Some(("d41d8cd98f00b204e9800998ecf8427e", "a\nb\\c".to_string()))
+        );
+        assert_eq!(
+            parse_checksum_line(&format_checksum_line("0a", "a\nb\\c")),
+            Some(("0a", "a\nb\\c".to_string()))
+        );
+        assert_eq!(parse_checksum_line("# comment"), None);
+        assert_eq!(parse_checksum_line(""), None);
+    }
+}
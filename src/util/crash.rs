@@ -0,0 +1,75 @@
+use std::fs;
+use std::io::{self, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use crate::ui::AppBackend;
+
+// termion only resets these when its terminal types are dropped
+const DISABLE_MOUSE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+/// Makes panics readable while the user interface is shown.
+///
+/// Panics on the main thread leave the alternate screen before the message is printed,
+/// the rest of the terminal state is restored by `AppBackend` while unwinding.
+/// Panics on other threads are not printed while the user interface is shown.
+/// If `crash_log` is set, every panic message is appended to it.
+pub fn install_panic_hook(crash_log: Option<PathBuf>) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let thread = thread::current();
+        let thread_name = thread.name().unwrap_or("<unnamed>");
+        let message = format!("joshuto: thread '{}' {}", thread_name, info);
+        log::error!("thread '{}' {}", thread_name, info);
+
+        let log_res = crash_log
+            .as_deref()
+            .map(|path| (path, write_crash_log(path, &message)));
+
+        if !AppBackend::terminal_active() {
+            default_hook(info);
+        } else if thread_name != "main" {
+            // printing would draw over the user interface, which keeps running
+            return;
+        } else {
+            let mut stdout = io::stdout();
+            let _ = write!(
+                stdout,
+                "{}{}{}",
+                DISABLE_MOUSE,
+                termion::screen::ToMainScreen,
+                termion::cursor::Show
+            );
+            let _ = stdout.flush();
+            // the terminal is still in raw mode, so lines need a carriage return
+            eprint!("{}\r\n", message.replace('\n', "\r\n"));
+        }
+
+        match log_res {
+            Some((path, Ok(()))) => eprint!("crash log written to {}\r\n", path.display()),
+            Some((path, Err(e))) => {
+                eprint!("failed to write crash log {}: {}\r\n", path.display(), e)
+            }
+            None => {}
+        }
+    }));
+}
+
+fn write_crash_log(path: &Path, message: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(
+        file,
+        "[{}] joshuto {}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        env!("CARGO_PKG_VERSION")
+    )?;
+    writeln!(file, "{}", message)?;
+    Ok(())
+}
@@ -4,6 +4,9 @@ use crate::HOSTNAME;
 
 pub fn set_current_dir(path: &path::Path) -> std::io::Result<()> {
     std::env::set_current_dir(path)?;
+    if !termion::is_tty(&std::io::stdout()) {
+        return Ok(());
+    }
     // OSC 7:
     // Escape sequences to advise the terminal of the working directory
     print!(
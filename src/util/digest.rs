@@ -0,0 +1,113 @@
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest as _, Sha256};
+
+use super::checksum::ChecksumAlgorithm;
+
+/// Incrementally computes the hash of one of the `ChecksumAlgorithm`s
+pub enum Digest {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha256(Sha256),
+}
+
+impl Digest {
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Md5 => Self::Md5(Md5::new()),
+            ChecksumAlgorithm::Sha1 => Self::Sha1(Sha1::new()),
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Md5(hasher) => hasher.update(data),
+            Self::Sha1(hasher) => hasher.update(data),
+            Self::Sha256(hasher) => hasher.update(data),
+        }
+    }
+
+    /// The hash as a lowercase hex string
+    pub fn finish_hex(self) -> String {
+        let bytes = match self {
+            Self::Md5(hasher) => hasher.finalize().to_vec(),
+            Self::Sha1(hasher) => hasher.finalize().to_vec(),
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+        };
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_digest(algorithm: ChecksumAlgorithm, data: &[u8]) -> String {
+        let mut digest = Digest::new(algorithm);
+        digest.update(data);
+        digest.finish_hex()
+    }
+
+    #[test]
+    fn digest_test() {
+        let long = vec![b'a'; 1000];
+        let cases: [(ChecksumAlgorithm, &[u8], &str); 9] = [
+            (
+                ChecksumAlgorithm::Md5,
+                b"",
+                "d41d8cd98f00b204e9800998ecf8427e",
+            ),
+            (
+                ChecksumAlgorithm::Md5,
+                b"The quick brown fox jumps over the lazy dog",
+                "9e107d9d372bb6826bd81d3542a419d6",
+            ),
+            (
+                ChecksumAlgorithm::Md5,
+                &long,
+                "cabe45dcc9ae5b66ba86600cca6b8ba8",
+            ),
+            (
+                ChecksumAlgorithm::Sha1,
+                b"",
+                "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            ),
+            (
+                ChecksumAlgorithm::Sha1,
+                b"The quick brown fox jumps over the lazy dog",
+                "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12",
+            ),
+            (
+                ChecksumAlgorithm::Sha1,
+                &long,
+                "291e9a6c66994949b57ba5e650361e98fc36b1ba",
+            ),
+            (
+                ChecksumAlgorithm::Sha256,
+                b"",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                ChecksumAlgorithm::Sha256,
+                b"The quick brown fox jumps over the lazy dog",
+                "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592",
+            ),
+            (
+                ChecksumAlgorithm::Sha256,
+                &long,
+                "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3",
+            ),
+        ];
+        for (algorithm, data, expected) in cases {
+            assert_eq!(hex_digest(algorithm, data), expected, "{:?}", algorithm);
+        }
+
+        // fed in pieces that don't line up with the blocks
+        let mut digest = Digest::new(ChecksumAlgorithm::Sha256);
+        for chunk in long.chunks(7) {
+            digest.update(chunk);
+        }
+        assert_eq!(digest.finish_hex(), cases[8].2);
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::util::duplicates::same_content;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    OnlyLeft,
+    OnlyRight,
+    Different,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirDifference {
+    // relative to the compared directories
+    pub path: PathBuf,
+    pub kind: DiffKind,
+    pub is_dir: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct EntryInfo {
+    is_dir: bool,
+    len: u64,
+}
+
+/// Compares the directory trees of `left` and `right`.
+///
+/// Directories existing on one side only are reported without their contents.
+/// Files with the same size are compared byte by byte. Symlinks are not followed.
+/// Fails with `ErrorKind::Interrupted` once `cancel` is set.
+pub fn compare_dirs(
+    left: &Path,
+    right: &Path,
+    cancel: &AtomicBool,
+) -> io::Result<Vec<DirDifference>> {
+    let left_entries = read_tree(left, cancel)?;
+    let right_entries = read_tree(right, cancel)?;
+
+    let all_paths: BTreeSet<&PathBuf> = left_entries.keys().chain(right_entries.keys()).collect();
+    let mut differences: Vec<DirDifference> = Vec::new();
+    // directories only existing on one side, their contents are skipped
+    let mut skipped: Option<PathBuf> = None;
+    for path in all_paths {
+        check_cancel(cancel)?;
+        if let Some(dir) = skipped.as_ref() {
+            if path.starts_with(dir) {
+                continue;
+            }
+            skipped = None;
+        }
+        let (kind, is_dir) = match (left_entries.get(path), right_entries.get(path)) {
+            (Some(l), None) => (DiffKind::OnlyLeft, l.is_dir),
+            (None, Some(r)) => (DiffKind::OnlyRight, r.is_dir),
+            (Some(l), Some(r)) => {
+                if l.is_dir && r.is_dir {
+                    continue;
+                }
+                let same = l.is_dir == r.is_dir
+                    && l.len == r.len
+                    && same_content(&left.join(path), &right.join(path)).unwrap_or(false);
+                if same {
+                    continue;
+                }
+                // a file on one side and a directory on the other is a difference as well
+                (DiffKind::Different, false)
+            }
+            (None, None) => continue,
+        };
+        if is_dir && kind != DiffKind::Different {
+            skipped = Some(path.clone());
+        }
+        differences.push(DirDifference {
+            path: path.clone(),
+            kind,
+            is_dir,
+        });
+    }
+    Ok(differences)
+}
+
+fn check_cancel(cancel: &AtomicBool) -> io::Result<()> {
+    if cancel.load(Ordering::Relaxed) {
+        Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled"))
+    } else {
+        Ok(())
+    }
+}
+
+fn read_tree(root: &Path, cancel: &AtomicBool) -> io::Result<BTreeMap<PathBuf, EntryInfo>> {
+    // fail early for missing directories
+    fs::read_dir(root)?;
+
+    let mut entries = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        check_cancel(cancel)?;
+        let path = match entry.path().strip_prefix(root) {
+            Ok(path) => path.to_path_buf(),
+            Err(_) => continue,
+        };
+        let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        entries.insert(
+            path,
+            EntryInfo {
+                is_dir: entry.file_type().is_dir(),
+                len,
+            },
+        );
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use std::sync::atomic::AtomicBool;
+
+    use super::{compare_dirs, DiffKind, DirDifference};
+
+    fn compare(left: &Path, right: &Path) -> Vec<DirDifference> {
+        compare_dirs(left, right, &AtomicBool::new(false)).unwrap()
+    }
+
+    fn difference(path: &str, kind: DiffKind, is_dir: bool) -> DirDifference {
+        DirDifference {
+            path: PathBuf::from(path),
+            kind,
+            is_dir,
+        }
+    }
+
+    #[test]
+    fn identical_directories_have_no_differences() {
+        let (left, right) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        for dir in [left.path(), right.path()] {
+            fs::create_dir(dir.join("sub")).unwrap();
+            fs::write(dir.join("sub/same"), "same").unwrap();
+        }
+        assert!(compare(left.path(), right.path()).is_empty());
+    }
+
+    #[test]
+    fn files_on_one_side_are_reported() {
+        let (left, right) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        fs::write(left.path().join("old"), "old").unwrap();
+        fs::write(right.path().join("new"), "new").unwrap();
+        assert_eq!(
+            compare(left.path(), right.path()),
+            vec![
+                difference("new", DiffKind::OnlyRight, false),
+                difference("old", DiffKind::OnlyLeft, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn directories_on_one_side_are_reported_without_their_contents() {
+        let (left, right) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        fs::create_dir(left.path().join("dir")).unwrap();
+        fs::write(left.path().join("dir/file"), "a").unwrap();
+        assert_eq!(
+            compare(left.path(), right.path()),
+            vec![difference("dir", DiffKind::OnlyLeft, true)]
+        );
+    }
+
+    #[test]
+    fn files_with_the_same_size_are_compared_by_content() {
+        let (left, right) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        fs::write(left.path().join("changed"), "abc").unwrap();
+        fs::write(right.path().join("changed"), "abd").unwrap();
+        assert_eq!(
+            compare(left.path(), right.path()),
+            vec![difference("changed", DiffKind::Different, false)]
+        );
+    }
+
+    #[test]
+    fn cancelled_comparisons_are_interrupted() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("file"), "a").unwrap();
+        let err = compare_dirs(dir.path(), dir.path(), &AtomicBool::new(true)).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+    }
+}
@@ -0,0 +1,243 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+
+// number of files listed as largest, oldest and newest
+const LISTED_FILES: usize = 10;
+// progress is reported every this many files
+const PROGRESS_STEP: usize = 1000;
+
+/// Number and total size of the files with one extension
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionStats {
+    /// extension without the dot, empty for files without one
+    pub extension: String,
+    pub count: usize,
+    pub size: u64,
+}
+
+/// Breakdown of a directory and its subdirectories, see `stats`
+#[derive(Clone, Debug, Default)]
+pub struct DirStats {
+    pub total_size: u64,
+    pub file_count: usize,
+    pub dir_count: usize,
+    /// biggest share of the total size first
+    pub extensions: Vec<ExtensionStats>,
+    pub largest: Vec<(PathBuf, u64)>,
+    pub oldest: Vec<(PathBuf, SystemTime)>,
+    pub newest: Vec<(PathBuf, SystemTime)>,
+}
+
+impl DirStats {
+    /// The listed files in the order they are shown: largest, oldest, newest
+    pub fn listed_files(&self) -> impl Iterator<Item = &Path> {
+        self.largest
+            .iter()
+            .map(|(p, _)| p.as_path())
+            .chain(self.oldest.iter().map(|(p, _)| p.as_path()))
+            .chain(self.newest.iter().map(|(p, _)| p.as_path()))
+    }
+}
+
+/// The `LISTED_FILES` greatest items pushed, so that only as many are kept in memory
+struct TopFiles<T: Ord>(BinaryHeap<Reverse<T>>);
+
+impl<T: Ord> TopFiles<T> {
+    fn new() -> Self {
+        Self(BinaryHeap::with_capacity(LISTED_FILES + 1))
+    }
+
+    fn push(&mut self, item: T) {
+        self.0.push(Reverse(item));
+        if self.0.len() > LISTED_FILES {
+            self.0.pop();
+        }
+    }
+
+    /// Greatest first
+    fn into_sorted_vec(self) -> Vec<T> {
+        self.0
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+}
+
+/// Walks `root` and collects its statistics.
+///
+/// `progress` is called with the number of files seen so far.
+/// Symlinks are not followed. Fails with `ErrorKind::Interrupted` once `cancel` is set.
+pub fn dir_stats<F>(root: &Path, cancel: &AtomicBool, mut progress: F) -> io::Result<DirStats>
+where
+    F: FnMut(usize),
+{
+    // fail early instead of reporting an empty directory
+    fs::read_dir(root)?;
+
+    let mut stats = DirStats::default();
+    let mut extensions: HashMap<String, ExtensionStats> = HashMap::new();
+    // the same size or time are listed by path
+    let mut largest: TopFiles<(u64, Reverse<PathBuf>)> = TopFiles::new();
+    let mut oldest: TopFiles<Reverse<(SystemTime, PathBuf)>> = TopFiles::new();
+    let mut newest: TopFiles<(SystemTime, PathBuf)> = TopFiles::new();
+    for entry in walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled"));
+        }
+        if entry.file_type().is_dir() {
+            stats.dir_count += 1;
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        let size = metadata.len();
+        stats.file_count += 1;
+        stats.total_size += size;
+
+        let extension = entry
+            .path()
+            .extension()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let ext_stats = extensions
+            .entry(extension.clone())
+            .or_insert_with(|| ExtensionStats {
+                extension,
+                count: 0,
+                size: 0,
+            });
+        ext_stats.count += 1;
+        ext_stats.size += size;
+
+        let path = entry.into_path();
+        if let Ok(modified) = metadata.modified() {
+            oldest.push(Reverse((modified, path.clone())));
+            newest.push((modified, path.clone()));
+        }
+        largest.push((size, Reverse(path)));
+        if stats.file_count % PROGRESS_STEP == 0 {
+            progress(stats.file_count);
+        }
+    }
+
+    stats.extensions = extensions.into_values().collect();
+    stats.extensions.sort_by(|a, b| {
+        b.size
+            .cmp(&a.size)
+            .then_with(|| a.extension.cmp(&b.extension))
+    });
+
+    stats.largest = largest
+        .into_sorted_vec()
+        .into_iter()
+        .map(|(size, Reverse(path))| (path, size))
+        .collect();
+    stats.oldest = oldest
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((modified, path))| (path, modified))
+        .collect();
+    stats.newest = newest
+        .into_sorted_vec()
+        .into_iter()
+        .map(|(modified, path)| (path, modified))
+        .collect();
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let sub = root.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        fs::write(root.path().join("a.txt"), "12345").unwrap();
+        fs::write(sub.join("b.TXT"), "123").unwrap();
+        fs::write(sub.join("c.rs"), "1234567").unwrap();
+        fs::write(sub.join("Makefile"), "").unwrap();
+        root
+    }
+
+    fn stats(root: &Path) -> DirStats {
+        dir_stats(root, &AtomicBool::new(false), |_| {}).unwrap()
+    }
+
+    #[test]
+    fn files_and_directories_are_counted() {
+        let root = tree();
+        let stats = stats(root.path());
+        assert_eq!(stats.total_size, 15);
+        assert_eq!(stats.file_count, 4);
+        assert_eq!(stats.dir_count, 1);
+    }
+
+    #[test]
+    fn extensions_are_grouped_case_insensitively_by_size() {
+        let root = tree();
+        let extensions: Vec<(String, usize, u64)> = stats(root.path())
+            .extensions
+            .into_iter()
+            .map(|e| (e.extension, e.count, e.size))
+            .collect();
+        assert_eq!(
+            extensions,
+            vec![
+                ("txt".to_string(), 2, 8),
+                ("rs".to_string(), 1, 7),
+                ("".to_string(), 1, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn largest_files_come_first() {
+        let root = tree();
+        let stats = stats(root.path());
+        assert_eq!(stats.largest[0], (root.path().join("sub/c.rs"), 7));
+        assert_eq!(stats.listed_files().count(), 12);
+    }
+
+    #[test]
+    fn top_files_keeps_the_greatest_items() {
+        let mut top = TopFiles::new();
+        for i in 0..100 {
+            top.push(i % 37);
+        }
+        assert_eq!(top.0.len(), LISTED_FILES);
+        assert_eq!(
+            top.into_sorted_vec(),
+            vec![36, 36, 35, 35, 34, 34, 33, 33, 32, 32]
+        );
+    }
+
+    #[test]
+    fn missing_directories_fail() {
+        let root = tree();
+        let missing = root.path().join("missing");
+        assert!(dir_stats(&missing, &AtomicBool::new(false), |_| {}).is_err());
+    }
+
+    #[test]
+    fn cancelled_walks_are_interrupted() {
+        let root = tree();
+        let err = dir_stats(root.path(), &AtomicBool::new(true), |_| {}).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Files with the same content
+#[derive(Clone, Debug)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub paths: Vec<PathBuf>,
+}
+
+/// Finds files under `root` with the same content.
+///
+/// Files are grouped by size first, only files sharing their size are read.
+/// Files with the same hash are compared byte by byte before they are reported.
+/// `progress` is called with the number of files read so far and the number of files to read.
+/// Empty files and symlinks are ignored.
+pub fn find_duplicates<F>(root: &Path, mut progress: F) -> io::Result<Vec<DuplicateGroup>>
+where
+    F: FnMut(usize, usize),
+{
+    // fail early instead of reporting no duplicates
+    fs::read_dir(root)?;
+
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for entry in walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let size = match entry.metadata() {
+            Ok(metadata) => metadata.len(),
+            Err(_) => continue,
+        };
+        if size > 0 {
+            by_size.entry(size).or_default().push(entry.into_path());
+        }
+    }
+
+    let candidates: Vec<(u64, Vec<PathBuf>)> = by_size
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .collect();
+    let total = candidates.iter().map(|(_, paths)| paths.len()).sum();
+    let mut processed = 0;
+    progress(processed, total);
+
+    let mut groups = Vec::new();
+    for (size, paths) in candidates {
+        let mut by_hash: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for path in paths {
+            // files can disappear while scanning
+            if let Ok(hash) = content_hash(&path) {
+                by_hash.entry(hash).or_default().push(path);
+            }
+            processed += 1;
+            progress(processed, total);
+        }
+        groups.extend(
+            by_hash
+                .into_values()
+                .filter(|paths| paths.len() > 1)
+                .flat_map(split_by_content)
+                .filter(|paths| paths.len() > 1)
+                .map(|mut paths| {
+                    paths.sort();
+                    DuplicateGroup { size, paths }
+                }),
+        );
+    }
+    // biggest savings first
+    groups.sort_by(|a, b| {
+        let a_size = a.size * (a.paths.len() as u64 - 1);
+        let b_size = b.size * (b.paths.len() as u64 - 1);
+        b_size.cmp(&a_size).then_with(|| a.paths.cmp(&b.paths))
+    });
+    Ok(groups)
+}
+
+// splits files with the same hash into groups of files with the same bytes,
+// in case of a hash collision
+fn split_by_content(paths: Vec<PathBuf>) -> Vec<Vec<PathBuf>> {
+    let mut groups: Vec<Vec<PathBuf>> = Vec::new();
+    for path in paths {
+        match groups
+            .iter_mut()
+            .find(|group| same_content(&group[0], &path).unwrap_or(false))
+        {
+            Some(group) => group.push(path),
+            None => groups.push(vec![path]),
+        }
+    }
+    groups
+}
+
+/// Whether the files have the same content, compared byte by byte
+pub fn same_content(a: &Path, b: &Path) -> io::Result<bool> {
+    let mut a = fs::File::open(a)?;
+    let mut b = fs::File::open(b)?;
+    let mut buf_a = vec![0; 64 * 1024];
+    let mut buf_b = vec![0; 64 * 1024];
+    loop {
+        let n = read_full(&mut a, &mut buf_a)?;
+        if n != read_full(&mut b, &mut buf_b)? || buf_a[..n] != buf_b[..n] {
+            return Ok(false);
+        }
+        if n == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+// reads until `buf` is full or the end of the file, so chunks of both files line up
+fn read_full(file: &mut fs::File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+fn content_hash(path: &Path) -> io::Result<u64> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buf[..n]);
+    }
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let sub = root.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        fs::write(root.path().join("a"), "same").unwrap();
+        fs::write(sub.join("b"), "same").unwrap();
+        fs::write(sub.join("c"), "diff").unwrap();
+        fs::write(sub.join("d"), "").unwrap();
+        fs::write(sub.join("e"), "").unwrap();
+        root
+    }
+
+    #[test]
+    fn files_with_the_same_content_are_grouped() {
+        let root = tree();
+        let groups = find_duplicates(root.path(), |_, _| {}).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].size, 4);
+        assert_eq!(
+            groups[0].paths,
+            vec![root.path().join("a"), root.path().join("sub/b")]
+        );
+    }
+
+    #[test]
+    fn empty_files_are_not_duplicates() {
+        let root = tree();
+        let groups = find_duplicates(root.path(), |_, _| {}).unwrap();
+        assert!(groups.iter().all(|g| g.size > 0));
+    }
+
+    #[test]
+    fn same_size_files_are_split_by_content() {
+        let root = tree();
+        let (a, b, c) = (
+            root.path().join("a"),
+            root.path().join("sub/b"),
+            root.path().join("sub/c"),
+        );
+        let split = split_by_content(vec![a.clone(), c.clone(), b.clone()]);
+        assert_eq!(split, vec![vec![a, b], vec![c]]);
+    }
+
+    #[test]
+    fn same_content_compares_bytes() {
+        let root = tree();
+        assert!(same_content(&root.path().join("a"), &root.path().join("sub/b")).unwrap());
+        assert!(!same_content(&root.path().join("a"), &root.path().join("sub/c")).unwrap());
+    }
+}
@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::iter::Peekable;
+use std::path::PathBuf;
+use std::str::Chars;
+
+use crate::error::{AppError, AppErrorKind, AppResult};
+
+/// Home directory of `user`, or of the current user if `user` is empty
+fn home_dir(user: &str) -> AppResult<PathBuf> {
+    let home = if user.is_empty() {
+        dirs_next::home_dir()
+    } else {
+        nix::unistd::User::from_name(user)
+            .ok()
+            .flatten()
+            .map(|user| user.dir)
+    };
+    home.ok_or_else(|| {
+        let msg = if user.is_empty() {
+            "Cannot find home directory".to_string()
+        } else {
+            format!("~{}: no such user", user)
+        };
+        AppError::new(AppErrorKind::EnvVarNotPresent, msg)
+    })
+}
+
+/// Expands `$VAR`, `${VAR}` and `${VAR:-default}`,
+/// fails on unset variables without a default
+pub fn expand_vars(s: &str) -> AppResult<Cow<'_, str>> {
+    shellexpand::env(s).map_err(|e| {
+        AppError::new(
+            AppErrorKind::EnvVarNotPresent,
+            format!("${}: variable is not set", e.var_name),
+        )
+    })
+}
+
+/// Expands a leading `~` or `~user` and environment variables in a path given by the user
+pub fn expand_path(s: &str) -> AppResult<PathBuf> {
+    let rest = match s.strip_prefix('~') {
+        Some(rest) => rest,
+        None => return Ok(PathBuf::from(expand_vars(s)?.as_ref())),
+    };
+    let (user, rest) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let mut path = OsString::from(home_dir(user)?);
+    path.push(expand_vars(rest)?.as_ref());
+    Ok(PathBuf::from(path))
+}
+
+/// Reads a variable reference following a `$` and expands it.
+/// A `$` not followed by a variable name and references to unset variables stay as they are,
+/// for the shell to expand, like the positional parameters in `sh -c 'echo $1'`
+fn expand_var_ref(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut var_ref = String::from("$");
+    if chars.peek() == Some(&'{') {
+        for c in chars.by_ref() {
+            var_ref.push(c);
+            if c == '}' {
+                break;
+            }
+        }
+    } else {
+        while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+            var_ref.push(c);
+        }
+    }
+    shellexpand::env_with_context_no_errors(&var_ref, |name| std::env::var(name).ok()).into_owned()
+}
+
+/// Splits `s` into words like a shell would, expanding `~`, `~user` and
+/// environment variables unless they are escaped or in single quotes.
+/// Unset variables without a default are kept, see `expand_var_ref`
+pub fn split_shell_words(s: &str) -> AppResult<Vec<String>> {
+    let unterminated = || {
+        AppError::new(
+            AppErrorKind::InvalidParameters,
+            format!("{}: missing closing quote", s),
+        )
+    };
+
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            words.extend(word.take());
+            continue;
+        }
+        let at_word_start = word.is_none();
+        let curr = word.get_or_insert_with(String::new);
+        match c {
+            '\\' => curr.extend(chars.next()),
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(c) => curr.push(c),
+                    None => return Err(unterminated()),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c @ ('"' | '\\' | '$')) => curr.push(c),
+                        Some(c) => {
+                            curr.push('\\');
+                            curr.push(c);
+                        }
+                        None => return Err(unterminated()),
+                    },
+                    Some('$') => curr.push_str(&expand_var_ref(&mut chars)),
+                    Some(c) => curr.push(c),
+                    None => return Err(unterminated()),
+                }
+            },
+            '$' => curr.push_str(&expand_var_ref(&mut chars)),
+            '~' if at_word_start => {
+                let mut user = String::new();
+                while let Some(c) = chars.next_if(|c| *c != '/' && !c.is_whitespace()) {
+                    user.push(c);
+                }
+                curr.push_str(&home_dir(&user)?.to_string_lossy());
+            }
+            c => curr.push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_path_test() {
+        std::env::set_var("JOSHUTO_EXPAND_TEST", "/projects");
+        std::env::remove_var("JOSHUTO_EXPAND_UNSET");
+        let home = dirs_next::home_dir().unwrap();
+
+        assert_eq!(
+            expand_path("$JOSHUTO_EXPAND_TEST/foo").unwrap(),
+            PathBuf::from("/projects/foo")
+        );
+        assert_eq!(
+            expand_path("${JOSHUTO_EXPAND_UNSET:-/tmp}/foo").unwrap(),
+            PathBuf::from("/tmp/foo")
+        );
+        assert_eq!(expand_path("~/foo").unwrap(), home.join("foo"));
+        assert_eq!(expand_path("a~b").unwrap(), PathBuf::from("a~b"));
+        assert!(expand_path("$JOSHUTO_EXPAND_UNSET/foo").is_err());
+        assert!(expand_path("~no_such_user_hopefully/foo").is_err());
+    }
+
+    #[test]
+    fn split_shell_words_test() {
+        std::env::set_var("JOSHUTO_SPLIT_TEST", "a b");
+        let home = dirs_next::home_dir().unwrap();
+        let home = home.to_string_lossy();
+
+        assert_eq!(
+            split_shell_words("echo $JOSHUTO_SPLIT_TEST '$JOSHUTO_SPLIT_TEST' \\$x").unwrap(),
+            vec!["echo", "a b", "$JOSHUTO_SPLIT_TEST", "$x"]
+        );
+        assert_eq!(
+            split_shell_words("\"${JOSHUTO_SPLIT_TEST}/c\" ~/d '' %s").unwrap(),
+            vec![
+                "a b/c".to_string(),
+                format!("{}/d", home),
+                "".to_string(),
+                "%s".to_string()
+            ]
+        );
+        assert_eq!(
+            split_shell_words("sed 's/$/x/' a$").unwrap(),
+            vec!["sed", "s/$/x/", "a$"]
+        );
+        std::env::remove_var("JOSHUTO_SPLIT_UNSET");
+        assert_eq!(
+            split_shell_words("sh -c \"echo $1 $JOSHUTO_SPLIT_UNSET\" _ %s").unwrap(),
+            vec!["sh", "-c", "echo $1 $JOSHUTO_SPLIT_UNSET", "_", "%s"]
+        );
+        assert_eq!(
+            split_shell_words("${JOSHUTO_SPLIT_UNSET:-x}").unwrap(),
+            vec!["x"]
+        );
+        assert!(split_shell_words("echo 'unterminated").is_err());
+    }
+}
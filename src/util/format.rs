@@ -1,22 +1,55 @@
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::time;
 
+use crate::config::clean::app::display::size_format::SizeFormat;
+
+// sizes are formatted in places without access to the config, see `set_size_format`
+static SIZE_FORMAT: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the format used by `file_size_to_string`
+pub fn set_size_format(size_format: SizeFormat) {
+    let value = match size_format {
+        SizeFormat::Binary => 0,
+        SizeFormat::Si => 1,
+        SizeFormat::Bytes => 2,
+    };
+    SIZE_FORMAT.store(value, Ordering::Relaxed);
+}
+
+fn size_format() -> SizeFormat {
+    match SIZE_FORMAT.load(Ordering::Relaxed) {
+        1 => SizeFormat::Si,
+        2 => SizeFormat::Bytes,
+        _ => SizeFormat::Binary,
+    }
+}
+
 pub fn file_size_to_string(file_size: u64) -> String {
-    const FILE_UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
-    const CONV_RATE: f64 = 1024.0;
+    file_size_to_string_with(file_size, size_format())
+}
+
+pub fn file_size_to_string_with(file_size: u64, size_format: SizeFormat) -> String {
+    const BINARY_UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+    const SI_UNITS: [&str; 6] = ["B", "kB", "MB", "GB", "TB", "PB"];
+    let (units, conv_rate) = match size_format {
+        SizeFormat::Binary => (BINARY_UNITS, 1024.0),
+        SizeFormat::Si => (SI_UNITS, 1000.0),
+        SizeFormat::Bytes => return format!("{} B", file_size),
+    };
     let mut file_size: f64 = file_size as f64;
 
     let mut index = 0;
-    while file_size > CONV_RATE {
-        file_size /= CONV_RATE;
+    while file_size > conv_rate && index < units.len() - 1 {
+        file_size /= conv_rate;
         index += 1;
     }
 
     if file_size >= 100.0 {
-        format!("{:>4.0} {}", file_size, FILE_UNITS[index])
+        format!("{:>4.0} {}", file_size, units[index])
     } else if file_size >= 10.0 {
-        format!("{:>4.1} {}", file_size, FILE_UNITS[index])
+        format!("{:>4.1} {}", file_size, units[index])
     } else {
-        format!("{:>4.2} {}", file_size, FILE_UNITS[index])
+        format!("{:>4.2} {}", file_size, units[index])
     }
 }
 
@@ -26,3 +59,129 @@ pub fn mtime_to_string(mtime: time::SystemTime) -> String {
     let datetime: chrono::DateTime<chrono::offset::Local> = mtime.into();
     datetime.format(MTIME_FORMATTING).to_string()
 }
+
+/// Formats `time` with a strftime-like `date_format`, or relative to now for "relative"
+pub fn time_to_string(time: time::SystemTime, date_format: &str) -> String {
+    if date_format == "relative" {
+        if let Some(s) = relative_time_to_string(time, time::SystemTime::now()) {
+            return s;
+        }
+        return mtime_to_string(time);
+    }
+    let datetime: chrono::DateTime<chrono::offset::Local> = time.into();
+    // chrono only reports invalid format strings while formatting, `to_string` would panic
+    let mut s = String::new();
+    match std::fmt::Write::write_fmt(&mut s, format_args!("{}", datetime.format(date_format))) {
+        Ok(_) => s,
+        Err(_) => mtime_to_string(time),
+    }
+}
+
+/// Formats the time passed between `time` and `now` like "3h ago",
+/// `None` if `time` is in the future
+pub fn relative_time_to_string(time: time::SystemTime, now: time::SystemTime) -> Option<String> {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const MONTH: u64 = 30 * DAY;
+    const YEAR: u64 = 365 * DAY;
+
+    let secs = now.duration_since(time).ok()?.as_secs();
+    let s = if secs < MINUTE {
+        "just now".to_string()
+    } else if secs < HOUR {
+        format!("{}m ago", secs / MINUTE)
+    } else if secs < DAY {
+        format!("{}h ago", secs / HOUR)
+    } else if secs < MONTH {
+        format!("{}d ago", secs / DAY)
+    } else if secs < YEAR {
+        format!("{}mo ago", secs / MONTH)
+    } else {
+        format!("{}y ago", secs / YEAR)
+    };
+    Some(s)
+}
+
+/// Formats `bytes` like `hexdump -C`, 16 bytes per line starting at `offset`
+pub fn hexdump(bytes: &[u8], offset: usize) -> Vec<String> {
+    const BYTES_PER_LINE: usize = 16;
+
+    bytes
+        .chunks(BYTES_PER_LINE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut hex = String::with_capacity(BYTES_PER_LINE * 3 + 1);
+            for (j, byte) in chunk.iter().enumerate() {
+                if j == BYTES_PER_LINE / 2 {
+                    hex.push(' ');
+                }
+                hex.push_str(&format!("{:02x} ", byte));
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<w$} |{}|",
+                offset + i * BYTES_PER_LINE,
+                hex,
+                ascii,
+                w = BYTES_PER_LINE * 3 + 1
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{file_size_to_string_with, hexdump, relative_time_to_string};
+    use crate::config::clean::app::display::size_format::SizeFormat;
+
+    #[test]
+    fn test_file_size_to_string() {
+        assert_eq!(file_size_to_string_with(512, SizeFormat::Binary), " 512 B");
+        assert_eq!(file_size_to_string_with(1536, SizeFormat::Binary), "1.50 K");
+        assert_eq!(file_size_to_string_with(1500, SizeFormat::Si), "1.50 kB");
+        assert_eq!(
+            file_size_to_string_with(25_000_000, SizeFormat::Si),
+            "25.0 MB"
+        );
+        assert_eq!(file_size_to_string_with(1536, SizeFormat::Bytes), "1536 B");
+    }
+
+    #[test]
+    fn test_relative_time_to_string() {
+        let now = SystemTime::now();
+        let ago = |secs| relative_time_to_string(now - Duration::from_secs(secs), now);
+        assert_eq!(ago(5), Some("just now".to_string()));
+        assert_eq!(ago(3 * 3600 + 100), Some("3h ago".to_string()));
+        assert_eq!(ago(2 * 86400), Some("2d ago".to_string()));
+        assert_eq!(ago(400 * 86400), Some("1y ago".to_string()));
+        assert_eq!(
+            relative_time_to_string(now + Duration::from_secs(60), now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_hexdump() {
+        let lines = hexdump(b"0123456789abcdef\x00\xff\n", 0);
+        assert_eq!(
+            lines,
+            vec![
+                "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|"
+                    .to_string(),
+                "00000010  00 ff 0a                                          |...|".to_string(),
+            ]
+        );
+    }
+}
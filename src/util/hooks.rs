@@ -0,0 +1,75 @@
+use std::path;
+use std::process;
+use std::thread;
+
+use crate::context::AppContext;
+
+pub enum Hook<'a> {
+    Startup,
+    Exit,
+    DirChange { previous: &'a path::Path },
+    FileOpen { files: Vec<path::PathBuf> },
+}
+
+impl Hook<'_> {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Startup => "on_startup",
+            Self::Exit => "on_exit",
+            Self::DirChange { .. } => "on_dir_change",
+            Self::FileOpen { .. } => "on_file_open",
+        }
+    }
+}
+
+/// Calls the hook script configured for `hook`, if any.
+///
+/// The script is given the context through environment variables:
+/// `JOSHUTO_HOOK`, `JOSHUTO_CWD`, and depending on the hook
+/// `JOSHUTO_PREVIOUS_CWD` or `JOSHUTO_FILES` (newline separated).
+/// All hooks run in the background, except `on_exit` which is waited for.
+pub fn call_hook(context: &AppContext, hook: Hook) {
+    let hook_options = context.config_ref().hook_options_ref();
+    let script = match hook {
+        Hook::Startup => hook_options.on_startup.as_ref(),
+        Hook::Exit => hook_options.on_exit.as_ref(),
+        Hook::DirChange { .. } => hook_options.on_dir_change.as_ref(),
+        Hook::FileOpen { .. } => hook_options.on_file_open.as_ref(),
+    };
+    let script = match script {
+        Some(script) => script,
+        None => return,
+    };
+
+    let mut command = process::Command::new(script);
+    command
+        .env("JOSHUTO_HOOK", hook.name())
+        .env(
+            "JOSHUTO_CWD",
+            context.tab_context_ref().curr_tab_ref().cwd(),
+        )
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null());
+    match &hook {
+        Hook::DirChange { previous } => {
+            command.env("JOSHUTO_PREVIOUS_CWD", previous);
+        }
+        Hook::FileOpen { files } => {
+            let files: Vec<String> = files
+                .iter()
+                .map(|f| f.to_string_lossy().to_string())
+                .collect();
+            command.env("JOSHUTO_FILES", files.join("\n"));
+        }
+        _ => {}
+    }
+
+    if let Hook::Exit = hook {
+        let _ = command.status();
+    } else {
+        let _ = thread::spawn(move || {
+            let _ = command.status();
+        });
+    }
+}
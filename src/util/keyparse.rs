@@ -1,65 +1,111 @@
 use termion::event::{Event, Key, MouseButton, MouseEvent};
 
-pub fn str_to_event(s: &str) -> Option<Event> {
-    if let Some(k) = str_to_key(s) {
-        Some(Event::Key(k))
-    } else {
-        str_to_mouse(s).map(Event::Mouse)
-    }
-}
+use crate::error::{AppError, AppErrorKind, AppResult};
 
-pub fn str_to_key(s: &str) -> Option<Key> {
+// modifier bits, as used by xterm in its escape sequences (plus 1)
+const SHIFT: u8 = 1;
+const ALT: u8 = 2;
+const CTRL: u8 = 4;
+
+const MODIFIER_NAMES: [(&str, u8); 4] = [
+    ("ctrl+", CTRL),
+    ("alt+", ALT),
+    ("meta+", ALT),
+    ("shift+", SHIFT),
+];
+
+// vim style modifiers, only allowed inside `<...>`
+const SHORT_MODIFIER_NAMES: [(&str, u8); 4] =
+    [("c-", CTRL), ("a-", ALT), ("m-", ALT), ("s-", SHIFT)];
+
+/// Parses a key as written in keymap.toml.
+///
+/// Keys can be single characters, named keys like `enter` or `f5`,
+/// either of those with modifiers like `ctrl+shift+p` or `alt+enter`,
+/// or wrapped in angle brackets like `<space>`, `<tab>`, `<bs>` or `<c-x>`.
+///
+/// Modifier combinations that termion has no `Key` for are represented
+/// by the escape sequence terminals send for them, see `normalize_event`.
+pub fn str_to_event(s: &str) -> AppResult<Event> {
     if s.is_empty() {
-        return None;
+        return Err(parse_error("key cannot be empty".to_string()));
+    }
+    if let Some(mouse) = str_to_mouse(s) {
+        return Ok(Event::Mouse(mouse));
     }
 
-    let key = match s {
-        "backspace" => Some(Key::Backspace),
-        "backtab" => Some(Key::BackTab),
-        "arrow_left" => Some(Key::Left),
-        "arrow_right" => Some(Key::Right),
-        "arrow_up" => Some(Key::Up),
-        "arrow_down" => Some(Key::Down),
-        "home" => Some(Key::Home),
-        "end" => Some(Key::End),
-        "page_up" => Some(Key::PageUp),
-        "page_down" => Some(Key::PageDown),
-        "delete" => Some(Key::Delete),
-        "insert" => Some(Key::Insert),
-        "escape" => Some(Key::Esc),
-        "f1" => Some(Key::F(1)),
-        "f2" => Some(Key::F(2)),
-        "f3" => Some(Key::F(3)),
-        "f4" => Some(Key::F(4)),
-        "f5" => Some(Key::F(5)),
-        "f6" => Some(Key::F(6)),
-        "f7" => Some(Key::F(7)),
-        "f8" => Some(Key::F(8)),
-        "f9" => Some(Key::F(9)),
-        "f10" => Some(Key::F(10)),
-        "f11" => Some(Key::F(11)),
-        "f12" => Some(Key::F(12)),
-        _ => None,
+    let (inner, bracketed) = match s.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+        Some(inner) if !inner.is_empty() => (inner, true),
+        _ => (s, false),
     };
 
-    if key.is_some() {
-        return key;
+    let mut modifiers = 0;
+    let mut name = inner;
+    'outer: loop {
+        let short_names: &[(&str, u8)] = if bracketed {
+            &SHORT_MODIFIER_NAMES
+        } else {
+            &[]
+        };
+        for (prefix, modifier) in MODIFIER_NAMES.iter().chain(short_names) {
+            // the rest still needs to contain a key, so `ctrl++` is ctrl and `+`
+            if name.len() > prefix.len()
+                && name.is_char_boundary(prefix.len())
+                && name[..prefix.len()].eq_ignore_ascii_case(prefix)
+            {
+                modifiers |= modifier;
+                name = &name[prefix.len()..];
+                continue 'outer;
+            }
+        }
+        break;
     }
 
-    if s.starts_with("ctrl+") {
-        let ch = s.chars().nth("ctrl+".len());
-        let key = ch.map(Key::Ctrl);
-        return key;
-    } else if s.starts_with("alt+") {
-        let ch = s.chars().nth("alt+".len());
-        let key = ch.map(Key::Alt);
-        return key;
-    } else if s.len() == 1 {
-        let ch = s.chars().next();
-        let key = ch.map(Key::Char);
-        return key;
+    let key = str_to_key(name).ok_or_else(|| {
+        if name.len() != inner.len() {
+            parse_error(format!("'{}': unknown key '{}' after modifiers", s, name))
+        } else {
+            parse_error(format!("'{}': unknown key", s))
+        }
+    })?;
+    apply_modifiers(key, modifiers)
+        .ok_or_else(|| parse_error(format!("'{}': key cannot be combined with modifiers", s)))
+}
+
+pub fn str_to_key(s: &str) -> Option<Key> {
+    let mut chars = s.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(c));
     }
-    None
+
+    let key = match s.to_lowercase().as_str() {
+        "backspace" | "bs" => Key::Backspace,
+        "backtab" => Key::BackTab,
+        "arrow_left" | "left" => Key::Left,
+        "arrow_right" | "right" => Key::Right,
+        "arrow_up" | "up" => Key::Up,
+        "arrow_down" | "down" => Key::Down,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "page_up" | "pageup" => Key::PageUp,
+        "page_down" | "pagedown" => Key::PageDown,
+        "delete" | "del" => Key::Delete,
+        "insert" | "ins" => Key::Insert,
+        "escape" | "esc" => Key::Esc,
+        "space" => Key::Char(' '),
+        "tab" => Key::Char('\t'),
+        "enter" | "return" | "cr" => Key::Char('\n'),
+        "lt" => Key::Char('<'),
+        "gt" => Key::Char('>'),
+        name => {
+            let n: u8 = name.strip_prefix('f')?.parse().ok()?;
+            if !(1..=12).contains(&n) {
+                return None;
+            }
+            Key::F(n)
+        }
+    };
+    Some(key)
 }
 
 pub fn str_to_mouse(s: &str) -> Option<MouseEvent> {
@@ -69,3 +115,241 @@ pub fn str_to_mouse(s: &str) -> Option<MouseEvent> {
         _ => None,
     }
 }
+
+/// Turns the escape sequences of modified keys into the events `str_to_event` creates.
+///
+/// Terminals report keys like ctrl+shift+p either in the `CSI u` format
+/// (`ESC [ 112 ; 6 u`) or with xterm's modifyOtherKeys (`ESC [ 27 ; 6 ; 112 ~`),
+/// termion reports both as unsupported events.
+pub fn normalize_event(event: Event) -> Event {
+    let normalized = match &event {
+        Event::Unsupported(bytes) => match parse_csi(bytes) {
+            Some((params, b'u')) if !params.is_empty() => {
+                csi_u_to_event(params[0], params.get(1).copied().unwrap_or(1))
+            }
+            Some((params, b'~')) if params.len() == 3 && params[0] == 27 => {
+                csi_u_to_event(params[2], params[1])
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    normalized.unwrap_or(event)
+}
+
+/// Human readable name of keys that are represented by escape sequences
+pub fn unsupported_to_string(bytes: &[u8]) -> Option<String> {
+    let (params, final_byte) = parse_csi(bytes)?;
+    let (key, modifiers) = match (params.as_slice(), final_byte) {
+        ([codepoint, modifiers], b'u') => {
+            let name = match *codepoint {
+                9 => "tab".to_string(),
+                13 => "enter".to_string(),
+                27 => "escape".to_string(),
+                32 => "space".to_string(),
+                127 => "backspace".to_string(),
+                c => char::from_u32(c)?.to_string(),
+            };
+            (name, *modifiers)
+        }
+        ([1, modifiers], c) => {
+            let name = match c {
+                b'A' => "arrow_up".to_string(),
+                b'B' => "arrow_down".to_string(),
+                b'C' => "arrow_right".to_string(),
+                b'D' => "arrow_left".to_string(),
+                b'F' => "end".to_string(),
+                b'H' => "home".to_string(),
+                b'P'..=b'S' => format!("f{}", c - b'P' + 1),
+                _ => return None,
+            };
+            (name, *modifiers)
+        }
+        ([code, modifiers], b'~') => {
+            let name = match code {
+                2 => "insert".to_string(),
+                3 => "delete".to_string(),
+                5 => "page_up".to_string(),
+                6 => "page_down".to_string(),
+                _ => format!("f{}", function_key_number(*code)?),
+            };
+            (name, *modifiers)
+        }
+        _ => return None,
+    };
+
+    let modifiers = modifiers.checked_sub(1)? as u8;
+    let mut s = String::new();
+    for (prefix, modifier) in MODIFIER_NAMES.iter() {
+        // `meta+` is an alias of `alt+`
+        if modifiers & modifier != 0 && *prefix != "meta+" {
+            s.push_str(prefix);
+        }
+    }
+    s.push_str(&key);
+    Some(s)
+}
+
+fn apply_modifiers(key: Key, modifiers: u8) -> Option<Event> {
+    if modifiers == 0 {
+        return Some(Event::Key(key));
+    }
+    let param = modifiers as u32 + 1;
+    let event = match key {
+        Key::Char(c) => match modifiers {
+            SHIFT if c.is_alphabetic() => Key::Char(c.to_uppercase().next()?),
+            SHIFT if c == '\t' => Key::BackTab,
+            CTRL if c == ' ' => Key::Null,
+            CTRL if c.is_ascii_graphic() => Key::Ctrl(c),
+            ALT if c == '\n' => Key::Alt('\r'),
+            ALT => Key::Alt(c),
+            m if m == ALT | SHIFT && c.is_alphabetic() => Key::Alt(c.to_uppercase().next()?),
+            m if m == ALT | CTRL && c.is_ascii_alphabetic() => {
+                Key::Alt((c.to_ascii_lowercase() as u8 - b'a' + 1) as char)
+            }
+            _ => {
+                let codepoint = match c {
+                    '\n' => 13,
+                    c => c.to_lowercase().next()? as u32,
+                };
+                return Some(csi(format!("{};{}u", codepoint, param)));
+            }
+        },
+        Key::Backspace if modifiers == ALT => Key::Alt('\x7f'),
+        Key::Backspace => return Some(csi(format!("127;{}u", param))),
+        Key::Esc => return Some(csi(format!("27;{}u", param))),
+        Key::Up => return Some(csi(format!("1;{}A", param))),
+        Key::Down => return Some(csi(format!("1;{}B", param))),
+        Key::Right => return Some(csi(format!("1;{}C", param))),
+        Key::Left => return Some(csi(format!("1;{}D", param))),
+        Key::End => return Some(csi(format!("1;{}F", param))),
+        Key::Home => return Some(csi(format!("1;{}H", param))),
+        Key::Insert => return Some(csi(format!("2;{}~", param))),
+        Key::Delete => return Some(csi(format!("3;{}~", param))),
+        Key::PageUp => return Some(csi(format!("5;{}~", param))),
+        Key::PageDown => return Some(csi(format!("6;{}~", param))),
+        Key::F(n @ 1..=4) => {
+            return Some(csi(format!("1;{}{}", param, (b'P' + n - 1) as char)));
+        }
+        Key::F(n) => {
+            let code = (11..=24).find(|code| function_key_number(*code) == Some(n))?;
+            return Some(csi(format!("{};{}~", code, param)));
+        }
+        _ => return None,
+    };
+    Some(Event::Key(event))
+}
+
+fn csi_u_to_event(codepoint: u32, param: u32) -> Option<Event> {
+    let key = match codepoint {
+        9 => Key::Char('\t'),
+        13 => Key::Char('\n'),
+        27 => Key::Esc,
+        127 => Key::Backspace,
+        c => Key::Char(char::from_u32(c)?),
+    };
+    // ignore caps lock and num lock
+    let modifiers = (param.checked_sub(1)? & 0b111) as u8;
+    apply_modifiers(key, modifiers)
+}
+
+// function keys are numbered with gaps in escape sequences
+fn function_key_number(code: u32) -> Option<u8> {
+    match code {
+        11..=15 => Some(code as u8 - 10),
+        17..=21 => Some(code as u8 - 11),
+        23..=24 => Some(code as u8 - 12),
+        _ => None,
+    }
+}
+
+fn csi(s: String) -> Event {
+    let mut bytes = b"\x1b[".to_vec();
+    bytes.extend(s.into_bytes());
+    Event::Unsupported(bytes)
+}
+
+// splits `ESC [ 1 ; 5 A` into its parameters and the final byte,
+// sub-parameters like kitty's alternate keys (`112:80`) are dropped
+fn parse_csi(bytes: &[u8]) -> Option<(Vec<u32>, u8)> {
+    let (final_byte, body) = bytes.strip_prefix(b"\x1b[")?.split_last()?;
+    let params = std::str::from_utf8(body)
+        .ok()?
+        .split(';')
+        .map(|p| p.split(':').next().unwrap_or_default().parse().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    Some((params, *final_byte))
+}
+
+fn parse_error(cause: String) -> AppError {
+    AppError::new(AppErrorKind::ParseError, cause)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_to_event() {
+        let key = |s| match str_to_event(s) {
+            Ok(Event::Key(key)) => Some(key),
+            _ => None,
+        };
+        assert_eq!(key("a"), Some(Key::Char('a')));
+        assert_eq!(key("<space>"), Some(Key::Char(' ')));
+        assert_eq!(key("<tab>"), Some(Key::Char('\t')));
+        assert_eq!(key("<bs>"), Some(Key::Backspace));
+        assert_eq!(key("<lt>"), Some(Key::Char('<')));
+        assert_eq!(key("<"), Some(Key::Char('<')));
+        assert_eq!(key("ctrl+a"), Some(Key::Ctrl('a')));
+        assert_eq!(key("<c-a>"), Some(Key::Ctrl('a')));
+        assert_eq!(key("ctrl++"), Some(Key::Ctrl('+')));
+        assert_eq!(key("alt+enter"), Some(Key::Alt('\r')));
+        assert_eq!(key("shift+tab"), Some(Key::BackTab));
+        assert_eq!(key("shift+a"), Some(Key::Char('A')));
+        assert_eq!(key("f12"), Some(Key::F(12)));
+
+        assert!(str_to_event("").is_err());
+        assert!(str_to_event("f13").is_err());
+        assert!(str_to_event("ctrl+foo").is_err());
+        assert!(str_to_event("c-a").is_err());
+        assert!(str_to_event("ctrl+backtab").is_err());
+    }
+
+    #[test]
+    fn test_modified_keys() {
+        let ctrl_shift_p = str_to_event("ctrl+shift+p").unwrap();
+        assert_eq!(ctrl_shift_p, Event::Unsupported(b"\x1b[112;6u".to_vec()));
+        // xterm modifyOtherKeys
+        assert_eq!(
+            normalize_event(Event::Unsupported(b"\x1b[27;6;112~".to_vec())),
+            ctrl_shift_p
+        );
+        // kitty keyboard protocol with caps lock
+        assert_eq!(
+            normalize_event(Event::Unsupported(b"\x1b[112;70u".to_vec())),
+            ctrl_shift_p
+        );
+        assert_eq!(
+            normalize_event(Event::Unsupported(b"\x1b[97;5u".to_vec())),
+            Event::Key(Key::Ctrl('a'))
+        );
+        assert_eq!(
+            str_to_event("ctrl+arrow_up").unwrap(),
+            Event::Unsupported(b"\x1b[1;5A".to_vec())
+        );
+        assert_eq!(
+            str_to_event("shift+f5").unwrap(),
+            Event::Unsupported(b"\x1b[15;2~".to_vec())
+        );
+
+        assert_eq!(
+            unsupported_to_string(b"\x1b[112;6u").as_deref(),
+            Some("ctrl+shift+p")
+        );
+        assert_eq!(
+            unsupported_to_string(b"\x1b[1;3D").as_deref(),
+            Some("alt+arrow_left")
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use lazy_static::lazy_static;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Where the log is written unless `log_file` is set:
+/// `$XDG_STATE_HOME/joshuto/joshuto.log`, `~/.local/state/joshuto/joshuto.log` by default
+pub fn default_log_file() -> Option<PathBuf> {
+    xdg::BaseDirectories::with_prefix(env!("CARGO_PKG_NAME"))
+        .ok()
+        .map(|dirs| dirs.get_state_home().join("joshuto.log"))
+}
+
+/// Appends log records to a file, which is only created once something is logged.
+/// Until the file is known, records are kept in memory
+struct FileLogger {
+    state: Mutex<LoggerState>,
+}
+
+struct LoggerState {
+    path: Option<PathBuf>,
+    file: Option<fs::File>,
+    // records made before `set_file`, `None` afterwards
+    pending: Option<Vec<(Level, String)>>,
+}
+
+impl FileLogger {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(LoggerState {
+                path: None,
+                file: None,
+                pending: Some(Vec::new()),
+            }),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, LoggerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Writes the records kept so far up to `level` to `path` and the following ones as well,
+    /// `None` drops them
+    fn set_file(&self, path: Option<PathBuf>, level: LevelFilter) -> io::Result<()> {
+        let mut state = self.state();
+        let pending = state.pending.take().unwrap_or_default();
+        state.path = path;
+        for (record_level, line) in pending {
+            if record_level <= level {
+                state.write_line(record_level, line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LoggerState {
+    fn write_line(&mut self, level: Level, line: String) -> io::Result<()> {
+        if let Some(pending) = self.pending.as_mut() {
+            pending.push((level, line));
+            return Ok(());
+        }
+        let path = match self.path.as_ref() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if self.file.is_none() {
+            self.file = Some(open_log_file(path)?);
+        }
+        match self.file.as_mut() {
+            Some(file) => writeln!(file, "{}", line),
+            None => Ok(()),
+        }
+    }
+}
+
+fn format_record(record: &Record) -> String {
+    format!(
+        "{} {:<5} {}: {}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        record.level(),
+        record.target(),
+        record.args()
+    )
+}
+
+impl Log for FileLogger {
+    // the `log` macros already skip records above the maximum level
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        // there is nowhere to report failing to log
+        let _ = self
+            .state()
+            .write_line(record.level(), format_record(record));
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.state().file.as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+fn open_log_file(path: &Path) -> io::Result<fs::File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::OpenOptions::new().create(true).append(true).open(path)
+}
+
+lazy_static! {
+    static ref LOGGER: FileLogger = FileLogger::new();
+}
+
+/// Starts collecting the records of the `log` macros up to `level`, before the configs
+/// saying where they go are read. They are kept in memory until `set_file`
+pub fn init(level: LevelFilter) {
+    // fails if a logger is set already, which then keeps receiving the records
+    let _ = log::set_logger(&*LOGGER);
+    set_level(level);
+}
+
+/// Sends the records to `path` for the rest of the session, including the ones collected
+/// since `init` up to `level`. The level can still be changed with `set_level`
+pub fn set_file(path: Option<PathBuf>, level: LevelFilter) {
+    let _ = LOGGER.set_file(path, level);
+    set_level(level);
+}
+
+pub fn set_level(level: LevelFilter) {
+    log::set_max_level(level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(logger: &FileLogger, level: log::Level, msg: &str) {
+        logger.log(
+            &Record::builder()
+                .args(format_args!("{}", msg))
+                .level(level)
+                .target("joshuto::test")
+                .build(),
+        )
+    }
+
+    #[test]
+    fn log_file_and_its_directories_are_created_once_something_is_logged() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("joshuto.log");
+        let logger = FileLogger::new();
+        logger
+            .set_file(Some(path.clone()), LevelFilter::Warn)
+            .unwrap();
+        assert!(!path.exists());
+        log(&logger, log::Level::Warn, "first");
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn messages_before_the_file_is_set_are_kept_by_level() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("joshuto.log");
+        let logger = FileLogger::new();
+        log(&logger, log::Level::Debug, "config");
+        log(&logger, log::Level::Warn, "first");
+        logger
+            .set_file(Some(path.clone()), LevelFilter::Warn)
+            .unwrap();
+        log(&logger, log::Level::Warn, "second");
+        logger.flush();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("WARN  joshuto::test: first"));
+        assert!(lines[1].ends_with("WARN  joshuto::test: second"));
+    }
+}
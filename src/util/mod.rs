@@ -1,10 +1,25 @@
+pub mod checksum;
+pub mod crash;
 pub mod cwd;
+pub mod digest;
+pub mod dir_compare;
+pub mod dir_stats;
+pub mod duplicates;
+pub mod expand;
 pub mod format;
 pub mod fs;
+pub mod hooks;
 pub mod keyparse;
+pub mod logging;
 pub mod mimetype;
 pub mod name_resolution;
+pub mod name_transform;
+pub mod notification;
 pub mod process;
 pub mod string;
 pub mod style;
+pub mod syntax;
+pub mod timing;
+pub mod title;
 pub mod unix;
+pub mod xattr;
@@ -1,15 +1,143 @@
+use std::ffi::{OsStr, OsString};
 use std::path;
 
-pub fn rename_filename_conflict(path: &mut path::PathBuf) {
-    let file_name = path.file_name().unwrap().to_os_string();
-    for i in 0.. {
-        if !path.exists() {
-            break;
+pub const DEFAULT_RENAME_PATTERN: &str = "{name} ({n}){ext}";
+// names tried before giving up on finding one that doesn't exist
+const MAX_RENAME_ATTEMPTS: usize = 10_000;
+
+/// Whether `pattern` gives a different name for each try in the same directory
+pub fn is_valid_rename_pattern(pattern: &str) -> bool {
+    pattern.contains("{name}") && !pattern.contains('/')
+}
+
+/// Builds the `n`th alternative for `file_name` from `pattern`, where `{name}` is
+/// the file name without extension, `{ext}` the extension including its dot and
+/// `{n}` a counter starting at 1. Directories are treated as having no extension.
+/// Patterns without `{n}` get `_n` appended to the name from the second try on.
+/// The parts of `file_name` are kept as they are, even if they aren't valid UTF-8
+pub fn unique_file_name(file_name: &OsStr, is_dir: bool, pattern: &str, n: usize) -> OsString {
+    let pattern = if pattern.is_empty() {
+        DEFAULT_RENAME_PATTERN
+    } else {
+        pattern
+    };
+    let file_path = path::Path::new(file_name);
+    let (stem, extension) = match (file_path.file_stem(), file_path.extension()) {
+        (Some(stem), Some(extension)) if !is_dir => (stem, Some(extension)),
+        _ => (file_name, None),
+    };
+    let mut name = stem.to_os_string();
+    if n > 1 && !pattern.contains("{n}") {
+        name.push(format!("_{}", n));
+    }
+    let mut ext = OsString::new();
+    if let Some(extension) = extension {
+        ext.push(".");
+        ext.push(extension);
+    }
+    let counter = OsString::from(n.to_string());
+
+    let placeholders = [("{name}", &name), ("{ext}", &ext), ("{n}", &counter)];
+    let mut result = OsString::new();
+    let mut rest = pattern;
+    // the earliest placeholder is replaced first, so replacements are never searched
+    while let Some((i, placeholder, value)) = placeholders
+        .iter()
+        .filter_map(|(placeholder, value)| rest.find(placeholder).map(|i| (i, placeholder, value)))
+        .min_by_key(|(i, _, _)| *i)
+    {
+        result.push(&rest[..i]);
+        result.push(value);
+        rest = &rest[i + placeholder.len()..];
+    }
+    result.push(rest);
+    result
+}
+
+/// Changes the file name of `path` following `pattern` until it doesn't exist anymore,
+/// returns false if no such name was found
+pub fn rename_filename_conflict(path: &mut path::PathBuf, pattern: &str) -> bool {
+    if path.symlink_metadata().is_err() {
+        return true;
+    }
+    let file_name = match path.file_name() {
+        Some(s) => s.to_os_string(),
+        None => return false,
+    };
+    let pattern = if is_valid_rename_pattern(pattern) {
+        pattern
+    } else {
+        DEFAULT_RENAME_PATTERN
+    };
+    let is_dir = path.is_dir();
+    for n in 1..=MAX_RENAME_ATTEMPTS {
+        path.set_file_name(unique_file_name(&file_name, is_dir, pattern, n));
+        if path.symlink_metadata().is_err() {
+            return true;
         }
-        path.pop();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_file_name(file_name: &str, is_dir: bool, pattern: &str, n: usize) -> OsString {
+        super::unique_file_name(OsStr::new(file_name), is_dir, pattern, n)
+    }
+
+    #[test]
+    fn unique_file_name_test() {
+        assert_eq!(
+            unique_file_name("report.pdf", false, DEFAULT_RENAME_PATTERN, 1),
+            "report (1).pdf"
+        );
+        assert_eq!(
+            unique_file_name("archive.tar.gz", false, "", 2),
+            "archive.tar (2).gz"
+        );
+        assert_eq!(
+            unique_file_name("photos.2020", true, DEFAULT_RENAME_PATTERN, 1),
+            "photos.2020 (1)"
+        );
+        assert_eq!(
+            unique_file_name(".bashrc", false, DEFAULT_RENAME_PATTERN, 1),
+            ".bashrc (1)"
+        );
+        assert_eq!(
+            unique_file_name("report.pdf", false, "{name}_copy{ext}", 1),
+            "report_copy.pdf"
+        );
+        assert_eq!(
+            unique_file_name("report.pdf", false, "{name}_copy{ext}", 2),
+            "report_2_copy.pdf"
+        );
+        assert_eq!(
+            unique_file_name("{ext}.txt", false, DEFAULT_RENAME_PATTERN, 1),
+            "{ext} (1).txt"
+        );
+    }
+
+    #[test]
+    fn rename_pattern_validity_test() {
+        assert!(is_valid_rename_pattern(DEFAULT_RENAME_PATTERN));
+        assert!(is_valid_rename_pattern("{name}_copy{ext}"));
+        assert!(!is_valid_rename_pattern("copy{ext}"));
+        assert!(!is_valid_rename_pattern("copy ({n}){ext}"));
+        assert!(!is_valid_rename_pattern("../{name}{ext}"));
+        assert!(!is_valid_rename_pattern(""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_file_name_test() {
+        use std::os::unix::ffi::OsStrExt;
 
-        let mut file_name = file_name.clone();
-        file_name.push(&format!("_{}", i));
-        path.push(file_name);
+        let name = OsStr::from_bytes(b"caf\xe9.txt");
+        assert_eq!(
+            super::unique_file_name(name, false, DEFAULT_RENAME_PATTERN, 1),
+            OsStr::from_bytes(b"caf\xe9 (1).txt")
+        );
     }
 }
@@ -0,0 +1,117 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+// characters FAT and Windows filesystems don't allow in file names
+const FAT_ILLEGAL_CHARS: &[char] = &['"', '*', '/', ':', '<', '>', '?', '\\', '|'];
+
+/// Transforms applied to file names by `rename_transform`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameTransform {
+    Lowercase,
+    Uppercase,
+    TitleCase,
+    Underscores,
+    StripDiacritics,
+    FatSafe,
+}
+
+impl NameTransform {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "lowercase" => Some(Self::Lowercase),
+            "uppercase" => Some(Self::Uppercase),
+            "titlecase" => Some(Self::TitleCase),
+            "underscores" => Some(Self::Underscores),
+            "strip_diacritics" => Some(Self::StripDiacritics),
+            "fat_safe" => Some(Self::FatSafe),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lowercase => "lowercase",
+            Self::Uppercase => "uppercase",
+            Self::TitleCase => "titlecase",
+            Self::Underscores => "underscores",
+            Self::StripDiacritics => "strip_diacritics",
+            Self::FatSafe => "fat_safe",
+        }
+    }
+
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            Self::Lowercase => name.to_lowercase(),
+            Self::Uppercase => name.to_uppercase(),
+            Self::TitleCase => title_case(name),
+            Self::Underscores => name
+                .chars()
+                .map(|c| if c.is_whitespace() { '_' } else { c })
+                .collect(),
+            Self::StripDiacritics => name
+                .nfd()
+                .filter(|c| !is_combining_mark(*c))
+                .nfc()
+                .collect(),
+            Self::FatSafe => {
+                let name: String = name
+                    .chars()
+                    .filter(|c| !c.is_control() && !FAT_ILLEGAL_CHARS.contains(c))
+                    .collect();
+                // trailing dots and spaces are dropped by FAT, so names would collide
+                name.trim_end_matches(|c| c == '.' || c == ' ').to_string()
+            }
+        }
+    }
+}
+
+/// Capitalizes the first letter of every word and lowercases the rest,
+/// words are separated by whitespace, `_` and `-`
+fn title_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut word_start = true;
+    for c in name.chars() {
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        word_start = c.is_whitespace() || c == '_' || c == '-';
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_transform_test() {
+        let name = "Été à la plage_IMG-01.JPG";
+        assert_eq!(
+            NameTransform::Lowercase.apply(name),
+            "été à la plage_img-01.jpg"
+        );
+        assert_eq!(
+            NameTransform::Uppercase.apply(name),
+            "ÉTÉ À LA PLAGE_IMG-01.JPG"
+        );
+        assert_eq!(
+            NameTransform::TitleCase.apply(name),
+            "Été À La Plage_Img-01.jpg"
+        );
+        assert_eq!(
+            NameTransform::Underscores.apply(name),
+            "Été_à_la_plage_IMG-01.JPG"
+        );
+        assert_eq!(
+            NameTransform::StripDiacritics.apply(name),
+            "Ete a la plage_IMG-01.JPG"
+        );
+        assert_eq!(
+            NameTransform::FatSafe.apply("what? a:b|c\"d*. ."),
+            "what abcd"
+        );
+        assert_eq!(NameTransform::FatSafe.apply("tab\there"), "tabhere");
+    }
+}
@@ -0,0 +1,27 @@
+use std::process;
+use std::thread;
+
+use crate::context::MessageLevel;
+
+/// Shows `msg` as a desktop notification through `notify-send`.
+///
+/// Runs in the background, failures (e.g. `notify-send` not being installed) are ignored.
+pub fn send_notification(msg: &str, level: MessageLevel) {
+    let urgency = match level {
+        MessageLevel::Error => "critical",
+        _ => "normal",
+    };
+    let mut command = process::Command::new("notify-send");
+    command
+        .arg("--app-name=joshuto")
+        .arg(format!("--urgency={}", urgency))
+        .arg("joshuto")
+        .arg(msg)
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null());
+
+    let _ = thread::spawn(move || {
+        let _ = command.status();
+    });
+}
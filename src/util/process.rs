@@ -1,3 +1,4 @@
+use std::ffi::{OsStr, OsString};
 use std::io::{self, Write};
 use std::process;
 use std::sync::mpsc;
@@ -6,27 +7,76 @@ use std::thread;
 use crate::config::clean::mimetype::ProgramEntry;
 use crate::event::AppEvent;
 
-pub fn fork_execute<I, S>(
-    entry: &ProgramEntry,
-    paths: I,
-    event_tx: mpsc::Sender<AppEvent>,
-) -> std::io::Result<(u32, thread::JoinHandle<()>)>
+// argument replaced by the files to open
+const FILES_PLACEHOLDER: &str = "%f";
+
+/// The command line of `entry` for `paths`. `%f` arguments are replaced by the paths,
+/// otherwise they are appended. Relative paths are made absolute if the program runs
+/// in another working directory
+fn build_command<I, S>(entry: &ProgramEntry, paths: I) -> io::Result<process::Command>
 where
     I: IntoIterator<Item = S>,
-    S: AsRef<std::ffi::OsStr>,
+    S: AsRef<OsStr>,
 {
-    let program = String::from(entry.get_command());
+    let pwd = std::env::current_dir()?;
+    let paths: Vec<OsString> = match entry.get_cwd() {
+        Some(_) => paths
+            .into_iter()
+            .map(|p| pwd.join(p.as_ref()).into_os_string())
+            .collect(),
+        None => paths
+            .into_iter()
+            .map(|p| p.as_ref().to_os_string())
+            .collect(),
+    };
 
-    let mut command = process::Command::new(program);
+    let mut command = process::Command::new(entry.get_command());
     if entry.get_silent() {
         command.stdout(process::Stdio::null());
         command.stderr(process::Stdio::null());
     }
+    match entry.get_cwd() {
+        Some(cwd) => {
+            command.current_dir(cwd);
+            command.env("PWD", cwd);
+        }
+        None => {
+            command.env("PWD", pwd);
+        }
+    }
 
-    let pwd = std::env::current_dir()?;
-    command.env("PWD", pwd);
-    command.args(entry.get_args());
-    command.args(paths);
+    let args = entry.get_args();
+    if args.iter().any(|arg| arg == FILES_PLACEHOLDER) {
+        for arg in args {
+            if arg == FILES_PLACEHOLDER {
+                command.args(&paths);
+            } else {
+                command.arg(arg);
+            }
+        }
+    } else {
+        command.args(args);
+        command.args(&paths);
+    }
+    log::debug!("running {:?}", command);
+    Ok(command)
+}
+
+pub fn fork_execute<I, S>(
+    entry: &ProgramEntry,
+    paths: I,
+    event_tx: mpsc::Sender<AppEvent>,
+) -> std::io::Result<(u32, thread::JoinHandle<()>)>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let mut command = build_command(entry, paths)?;
+    // joshuto keeps using the terminal, so the program must neither read from
+    // nor write to it
+    command.stdin(process::Stdio::null());
+    command.stdout(process::Stdio::null());
+    command.stderr(process::Stdio::null());
 
     let mut child = command.spawn()?;
     let child_id = child.id();
@@ -40,38 +90,35 @@ where
     Ok((child_id, handle))
 }
 
+/// Runs the program in the foreground, the terminal has to be handed over to it
+/// beforehand. With `pager` its output is shown in `$PAGER`, with `confirm_exit`
+/// the user has to press enter once it exits
 pub fn execute_and_wait<I, S>(entry: &ProgramEntry, paths: I) -> std::io::Result<()>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<std::ffi::OsStr>,
 {
-    let program = String::from(entry.get_command());
-
-    let mut command = process::Command::new(program);
-    if entry.get_silent() {
-        command.stdout(process::Stdio::null());
-        command.stderr(process::Stdio::null());
-    }
-
-    let pwd = std::env::current_dir()?;
-    command.env("PWD", pwd);
-    command.args(entry.get_args());
-    command.args(paths);
+    let mut command = build_command(entry, paths)?;
 
     if entry.get_pager() {
         println!("{}", termion::clear::All);
         let pager_env = std::env::var("PAGER").unwrap_or_else(|_| String::from("less"));
         let pager_args: Vec<&str> = pager_env.split_whitespace().collect();
 
-        if let Some(child_stdout) = command.stdout(process::Stdio::piped()).spawn()?.stdout {
+        let mut child = command.stdout(process::Stdio::piped()).spawn()?;
+        if let Some(child_stdout) = child.stdout.take() {
             process::Command::new(pager_args[0])
                 .args(&pager_args[1..])
                 .stdin(child_stdout)
                 .status()?;
         }
+        child.wait()?;
+    } else {
+        command.status()?;
+    }
+    if entry.get_confirm_exit() {
+        wait_for_enter()?;
     }
-    command.status()?;
-
     Ok(())
 }
 
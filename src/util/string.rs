@@ -1,6 +1,56 @@
+use std::ffi::OsStr;
+
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+/// Converts a file name for display, escaping bytes that aren't valid UTF-8 as `\xNN`
+/// and control characters like `\n`, so that distinct names stay distinct
+pub fn escape_os_str(s: &OsStr) -> String {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        s.as_bytes()
+    };
+    #[cfg(not(unix))]
+    let lossy = s.to_string_lossy();
+    #[cfg(not(unix))]
+    let bytes = lossy.as_bytes();
+    escape_bytes(bytes)
+}
+
+/// Like `escape_os_str`, for text of unknown encoding such as the lines of a file
+pub fn escape_bytes(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len());
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(rest) {
+            Ok(s) => (s, &[][..]),
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                let invalid_len = e.error_len().unwrap_or(after.len());
+                // the prefix was just validated
+                let valid = std::str::from_utf8(valid).unwrap_or_default();
+                rest = &after[invalid_len..];
+                (valid, &after[..invalid_len])
+            }
+        };
+        for c in valid.chars() {
+            if c.is_control() {
+                result.extend(c.escape_default());
+            } else {
+                result.push(c);
+            }
+        }
+        for b in invalid {
+            result.push_str(&format!("\\x{:02x}", b));
+        }
+        if invalid.is_empty() {
+            break;
+        }
+    }
+    result
+}
+
 ///Truncates a string to width, less or equal to the specified one.
 ///
 ///In case the point of truncation falls into a full-width character,
@@ -8,6 +58,8 @@ use unicode_width::UnicodeWidthStr;
 ///Otherwise, it will be equal.
 pub trait UnicodeTruncate {
     fn trunc(&self, width: usize) -> String;
+    /// Like `trunc`, but keeps the end of the string
+    fn trunc_start(&self, width: usize) -> String;
 }
 
 impl UnicodeTruncate for str {
@@ -29,6 +81,48 @@ impl UnicodeTruncate for str {
             result
         }
     }
+
+    fn trunc_start(&self, width: usize) -> String {
+        if self.width() <= width {
+            String::from(self)
+        } else {
+            let mut length: usize = 0;
+            let mut graphemes = Vec::new();
+            for grapheme in self.graphemes(true).rev() {
+                length += grapheme.width();
+                if length > width {
+                    break;
+                };
+                graphemes.push(grapheme);
+            }
+            graphemes.into_iter().rev().collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_escape {
+    use super::escape_os_str;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn valid_names_are_unchanged() {
+        assert_eq!(escape_os_str(OsStr::new("r͂o͒͜w̾ 🌕.txt")), "r͂o͒͜w̾ 🌕.txt");
+    }
+
+    #[test]
+    fn control_characters_are_escaped() {
+        assert_eq!(escape_os_str(OsStr::new("a\nb\tc")), "a\\nb\\tc");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_bytes_are_escaped() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"caf\xe9 \xff\xfe.txt");
+        assert_eq!(escape_os_str(name), "caf\\xe9 \\xff\\xfe.txt");
+    }
 }
 
 #[cfg(test)]
@@ -64,4 +158,11 @@ mod tests_trunc {
     fn truncate_within_fullwidth_character_truncates_before_the_character() {
         assert_eq!(String::from("a🌕").trunc(2), String::from("a"));
     }
+
+    #[test]
+    fn truncate_start_keeps_the_end_of_the_string() {
+        assert_eq!(String::from("r͂o͒͜w̾").trunc_start(2), String::from("o͒͜w̾"));
+        assert_eq!(String::from("🌕bc").trunc_start(3), String::from("bc"));
+        assert_eq!(String::from("abc").trunc_start(0), String::from(""));
+    }
 }
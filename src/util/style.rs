@@ -3,6 +3,7 @@ use lscolors::LsColors;
 use ratatui::style::Style;
 use std::path::Path;
 
+use crate::config::clean::theme::AppTheme;
 use crate::fs::{FileType, JoshutoDirEntry, LinkType};
 use crate::util::unix;
 
@@ -30,86 +31,101 @@ pub fn entry_style(entry: &JoshutoDirEntry) -> Style {
     let filetype = metadata.file_type();
     let linktype = metadata.link_type();
 
+    let theme = THEME_T.read().unwrap();
     if entry.is_visual_mode_selected() {
-        return visual_mode_selected_style();
+        return visual_mode_selected_style(&theme);
     }
     if entry.is_permanent_selected() {
-        return permanent_selected_style();
+        return permanent_selected_style(&theme);
     }
 
-    match &THEME_T.lscolors {
+    match &theme.lscolors {
         Some(lscolors) => {
             let path = entry.file_path();
             lscolors_style(lscolors, path)
-                .unwrap_or_else(|| default_style(entry, linktype, filetype))
+                .unwrap_or_else(|| default_style(&theme, entry, linktype, filetype))
         }
-        None => default_style(entry, linktype, filetype),
+        None => default_style(&theme, entry, linktype, filetype),
     }
 }
 
-fn default_style(entry: &JoshutoDirEntry, linktype: &LinkType, filetype: &FileType) -> Style {
+/// The `selection_prefix` of the theme if `entry` is selected and the prefix is set
+pub fn selection_prefix(entry: &JoshutoDirEntry) -> Option<String> {
+    if !entry.is_selected() {
+        return None;
+    }
+    let theme = THEME_T.read().unwrap();
+    Some(theme.selection_prefix.clone()).filter(|s| !s.is_empty())
+}
+
+fn default_style(
+    theme: &AppTheme,
+    entry: &JoshutoDirEntry,
+    linktype: &LinkType,
+    filetype: &FileType,
+) -> Style {
     match linktype {
-        LinkType::Symlink { valid: true, .. } => symlink_valid_style(),
-        LinkType::Symlink { valid: false, .. } => symlink_invalid_style(),
+        LinkType::Symlink { valid: true, .. } => symlink_valid_style(theme),
+        LinkType::Symlink { valid: false, .. } => symlink_invalid_style(theme),
         LinkType::Normal => match filetype {
-            FileType::Directory => directory_style(),
-            FileType::File => file_style(entry),
+            FileType::Directory => directory_style(theme),
+            FileType::File => file_style(theme, entry),
         },
     }
 }
 
-fn visual_mode_selected_style() -> Style {
+fn visual_mode_selected_style(theme: &AppTheme) -> Style {
     Style::default()
-        .fg(THEME_T.visual_mode_selection.fg)
-        .bg(THEME_T.visual_mode_selection.bg)
-        .add_modifier(THEME_T.visual_mode_selection.modifier)
+        .fg(theme.visual_mode_selection.fg)
+        .bg(theme.visual_mode_selection.bg)
+        .add_modifier(theme.visual_mode_selection.modifier)
 }
 
-fn permanent_selected_style() -> Style {
+fn permanent_selected_style(theme: &AppTheme) -> Style {
     Style::default()
-        .fg(THEME_T.selection.fg)
-        .bg(THEME_T.selection.bg)
-        .add_modifier(THEME_T.selection.modifier)
+        .fg(theme.selection.fg)
+        .bg(theme.selection.bg)
+        .add_modifier(theme.selection.modifier)
 }
 
-fn symlink_valid_style() -> Style {
+fn symlink_valid_style(theme: &AppTheme) -> Style {
     Style::default()
-        .fg(THEME_T.link.fg)
-        .bg(THEME_T.link.bg)
-        .add_modifier(THEME_T.link.modifier)
+        .fg(theme.link.fg)
+        .bg(theme.link.bg)
+        .add_modifier(theme.link.modifier)
 }
 
-fn symlink_invalid_style() -> Style {
+fn symlink_invalid_style(theme: &AppTheme) -> Style {
     Style::default()
-        .fg(THEME_T.link_invalid.fg)
-        .bg(THEME_T.link_invalid.bg)
-        .add_modifier(THEME_T.link_invalid.modifier)
+        .fg(theme.link_invalid.fg)
+        .bg(theme.link_invalid.bg)
+        .add_modifier(theme.link_invalid.modifier)
 }
 
-fn directory_style() -> Style {
+fn directory_style(theme: &AppTheme) -> Style {
     Style::default()
-        .fg(THEME_T.directory.fg)
-        .bg(THEME_T.directory.bg)
-        .add_modifier(THEME_T.directory.modifier)
+        .fg(theme.directory.fg)
+        .bg(theme.directory.bg)
+        .add_modifier(theme.directory.modifier)
 }
 
-fn file_style(entry: &JoshutoDirEntry) -> Style {
+fn file_style(theme: &AppTheme, entry: &JoshutoDirEntry) -> Style {
     let regular_style = Style::default()
-        .fg(THEME_T.regular.fg)
-        .bg(THEME_T.regular.bg)
-        .add_modifier(THEME_T.regular.modifier);
+        .fg(theme.regular.fg)
+        .bg(theme.regular.bg)
+        .add_modifier(theme.regular.modifier);
     let metadata = &entry.metadata;
     if unix::is_executable(metadata.mode) {
         Style::default()
-            .fg(THEME_T.executable.fg)
-            .bg(THEME_T.executable.bg)
-            .add_modifier(THEME_T.executable.modifier)
+            .fg(theme.executable.fg)
+            .bg(theme.executable.bg)
+            .add_modifier(theme.executable.modifier)
     } else {
         entry
             .file_path()
             .extension()
             .and_then(|s| s.to_str())
-            .and_then(|s| THEME_T.ext.get(s))
+            .and_then(|s| theme.ext.get(s))
             .map(|theme| {
                 Style::default()
                     .fg(theme.fg)
@@ -0,0 +1,171 @@
+use std::path::Path;
+#[cfg(feature = "syntax_highlight")]
+use std::sync::RwLock;
+
+use ratatui::text::Line;
+
+#[cfg(feature = "syntax_highlight")]
+use ratatui::style::{Color, Modifier, Style};
+#[cfg(feature = "syntax_highlight")]
+use ratatui::text::Span;
+#[cfg(feature = "syntax_highlight")]
+use syntect::easy::HighlightLines;
+#[cfg(feature = "syntax_highlight")]
+use syntect::highlighting::{FontStyle, Style as SyntectStyle, Theme, ThemeSet};
+#[cfg(feature = "syntax_highlight")]
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+#[cfg(feature = "syntax_highlight")]
+use syntect::util::{as_24_bit_terminal_escaped, LinesWithEndings};
+
+#[cfg(feature = "syntax_highlight")]
+use crate::util::unix;
+#[cfg(feature = "syntax_highlight")]
+use crate::THEME_T;
+
+// larger texts are shown without highlighting, it gets slow
+#[cfg(feature = "syntax_highlight")]
+const MAX_HIGHLIGHT_SIZE: usize = 1024 * 1024;
+
+#[cfg(feature = "syntax_highlight")]
+lazy_static::lazy_static! {
+    static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    static ref SYNTAX_THEME: RwLock<Option<Theme>> = RwLock::new(load_theme(THEME_T.read().unwrap().syntax_theme.as_deref()));
+}
+
+/// Loads one of the themes bundled with syntect by name or a `.tmTheme` file
+#[cfg(feature = "syntax_highlight")]
+fn load_theme(name: Option<&str>) -> Option<Theme> {
+    let name = name?;
+    if name.ends_with(".tmTheme") {
+        ThemeSet::get_theme(unix::expand_shell_string(name)).ok()
+    } else {
+        ThemeSet::load_defaults().themes.remove(name)
+    }
+}
+
+/// Finds the syntax of `text` by the file name or extension of `path`, then by its first line
+#[cfg(feature = "syntax_highlight")]
+fn find_syntax(path: &Path, text: &str) -> Option<&'static SyntaxReference> {
+    let by_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .and_then(|name| SYNTAX_SET.find_syntax_by_extension(name));
+    let by_ext = || {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .and_then(|ext| SYNTAX_SET.find_syntax_by_extension(ext))
+    };
+    let by_first_line = || {
+        text.lines()
+            .next()
+            .and_then(|line| SYNTAX_SET.find_syntax_by_first_line(line))
+    };
+    by_name
+        .or_else(by_ext)
+        .or_else(by_first_line)
+        .filter(|syntax| syntax.name != "Plain Text")
+}
+
+#[cfg(feature = "syntax_highlight")]
+fn highlight_with<T>(
+    path: &Path,
+    text: &str,
+    mut f: impl FnMut(&[(SyntectStyle, &str)]) -> T,
+) -> Option<Vec<T>> {
+    if text.len() > MAX_HIGHLIGHT_SIZE {
+        return None;
+    }
+    let theme = SYNTAX_THEME.read().unwrap();
+    let theme = theme.as_ref()?;
+    let syntax = find_syntax(path, text)?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    LinesWithEndings::from(text)
+        .map(|line| {
+            highlighter
+                .highlight_line(line, &SYNTAX_SET)
+                .ok()
+                .map(|ranges| f(&ranges))
+        })
+        .collect()
+}
+
+/// Loads `syntax_theme` again after the theme was reloaded
+#[cfg(feature = "syntax_highlight")]
+pub fn reload_theme() {
+    let theme = load_theme(THEME_T.read().unwrap().syntax_theme.as_deref());
+    *SYNTAX_THEME.write().unwrap() = theme;
+}
+
+#[cfg(feature = "syntax_highlight")]
+fn to_style(style: SyntectStyle) -> Style {
+    let fg = style.foreground;
+    let mut result = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+    if style.font_style.contains(FontStyle::BOLD) {
+        result = result.add_modifier(Modifier::BOLD);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        result = result.add_modifier(Modifier::ITALIC);
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        result = result.add_modifier(Modifier::UNDERLINED);
+    }
+    result
+}
+
+/// `text` with ANSI color codes for source code `path` is recognized as,
+/// `None` if there is no matching syntax or `syntax_theme` is not set
+#[cfg(feature = "syntax_highlight")]
+pub fn highlight_ansi(path: &Path, text: &str) -> Option<String> {
+    let lines = highlight_with(path, text, |ranges| {
+        as_24_bit_terminal_escaped(ranges, false)
+    })?;
+    Some(lines.concat() + "\x1b[0m")
+}
+
+/// Styled `lines` of the source code `path` is recognized as,
+/// `None` if there is no matching syntax or `syntax_theme` is not set
+#[cfg(feature = "syntax_highlight")]
+pub fn highlight_lines(path: &Path, lines: &[String]) -> Option<Vec<Line<'static>>> {
+    let text = lines.join("\n");
+    highlight_with(path, &text, |ranges| {
+        Line::from(
+            ranges
+                .iter()
+                .map(|(style, s)| {
+                    Span::styled(s.trim_end_matches('\n').to_string(), to_style(*style))
+                })
+                .collect::<Vec<Span>>(),
+        )
+    })
+}
+
+#[cfg(not(feature = "syntax_highlight"))]
+pub fn reload_theme() {}
+
+#[cfg(not(feature = "syntax_highlight"))]
+pub fn highlight_ansi(_path: &Path, _text: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(feature = "syntax_highlight"))]
+pub fn highlight_lines(_path: &Path, _lines: &[String]) -> Option<Vec<Line<'static>>> {
+    None
+}
+
+#[cfg(all(test, feature = "syntax_highlight"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_syntax_test() {
+        let syntax = |path: &str, text: &str| find_syntax(Path::new(path), text).map(|s| &s.name);
+        assert_eq!(syntax("main.rs", "").unwrap(), "Rust");
+        assert_eq!(syntax("Makefile", "").unwrap(), "Makefile");
+        assert_eq!(
+            syntax("script", "#!/bin/bash\n").unwrap(),
+            "Bourne Again Shell (bash)"
+        );
+        assert_eq!(syntax("notes.txt", "some text"), None);
+        assert_eq!(syntax("data", "some text"), None);
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+
+// set by `--debug-timing`
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref START: Instant = Instant::now();
+    // name of each phase, when it started and how long it took
+    static ref PHASES: Mutex<Vec<(&'static str, Duration, Duration)>> = Mutex::new(Vec::new());
+}
+
+/// Starts recording phase timings, relative to now
+pub fn enable() {
+    lazy_static::initialize(&START);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, recording how long it took as `phase` if timings are enabled
+pub fn measure<T>(phase: &'static str, f: impl FnOnce() -> T) -> T {
+    if !is_enabled() {
+        return f();
+    }
+    let started = Instant::now();
+    let res = f();
+    if let Ok(mut phases) = PHASES.lock() {
+        phases.push((phase, started - *START, started.elapsed()));
+    }
+    res
+}
+
+/// Records the moment `event` happened, like the first paint
+pub fn mark(event: &'static str) {
+    measure(event, || ());
+}
+
+/// The recorded phases in the order they started, one per line
+pub fn report() -> String {
+    let mut phases = PHASES.lock().map(|p| p.clone()).unwrap_or_default();
+    phases.sort_by_key(|(_, started, _)| *started);
+    let mut report = format!("{:>12} {:>12}  phase\n", "started", "took");
+    for (phase, started, took) in phases {
+        report.push_str(&format!(
+            "{:>12} {:>12}  {}\n",
+            format!("{:.3?}", started),
+            format!("{:.3?}", took),
+            phase
+        ));
+    }
+    report
+}
@@ -0,0 +1,67 @@
+use std::io::{self, Write};
+use std::path;
+
+use crate::HOME_DIR;
+
+// xterm window manipulation: save and restore the title on the terminal's title stack
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
+pub fn push_title() {
+    write_stdout(PUSH_TITLE);
+}
+
+pub fn pop_title() {
+    write_stdout(POP_TITLE);
+}
+
+pub fn set_title(path: &path::Path, tilde_in_titlebar: bool) {
+    let home_dir = if tilde_in_titlebar {
+        HOME_DIR.as_deref()
+    } else {
+        None
+    };
+    // OSC 0: set window and icon title
+    write_stdout(&format!("\x1b]0;{}\x07", title(path, home_dir)));
+}
+
+/// `path` as shown in the title, with control characters removed so a directory name
+/// cannot end the escape sequence early and send its own
+fn title(path: &path::Path, home_dir: Option<&path::Path>) -> String {
+    let title = match home_dir.and_then(|home_dir| path.strip_prefix(home_dir).ok()) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.to_string_lossy()),
+        None => path.to_string_lossy().to_string(),
+    };
+    title.chars().filter(|c| !c.is_control()).collect()
+}
+
+fn write_stdout(s: &str) {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(s.as_bytes());
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_home_dir_test() {
+        let home = path::Path::new("/home/bob");
+        let title = |p: &str| title(path::Path::new(p), Some(home));
+        assert_eq!(title("/home/bob"), "~");
+        assert_eq!(title("/home/bob/src"), "~/src");
+        assert_eq!(title("/home/bob2"), "/home/bob2");
+        assert_eq!(
+            super::title(path::Path::new("/home/bob/src"), None),
+            "/home/bob/src"
+        );
+    }
+
+    #[test]
+    fn title_control_chars_test() {
+        let path = path::Path::new("/tmp/a\x07\x1b]0;evil\x1b\\b");
+        assert_eq!(title(path, None), "/tmp/a]0;evil\\b");
+    }
+}
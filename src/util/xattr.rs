@@ -0,0 +1,138 @@
+use std::ffi::CString;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+fn to_cstring(bytes: &[u8]) -> io::Result<CString> {
+    CString::new(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Lists the extended attributes of `path` as `(name, value)` pairs,
+/// without following symlinks
+#[cfg(target_os = "linux")]
+pub fn list(path: &Path) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let c_path = to_cstring(path.as_os_str().as_bytes())?;
+
+    let size = unsafe { libc::llistxattr(c_path.as_ptr(), std::ptr::null_mut(), 0) };
+    if size < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut names = vec![0u8; size as usize];
+    let size = unsafe {
+        libc::llistxattr(
+            c_path.as_ptr(),
+            names.as_mut_ptr() as *mut libc::c_char,
+            names.len(),
+        )
+    };
+    if size < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    names.truncate(size as usize);
+
+    let mut attrs = Vec::new();
+    // names are separated by nul bytes
+    for name in names.split(|b| *b == 0).filter(|s| !s.is_empty()) {
+        let value = get(path, &String::from_utf8_lossy(name))?;
+        attrs.push((String::from_utf8_lossy(name).to_string(), value));
+    }
+    attrs.sort();
+    Ok(attrs)
+}
+
+/// Reads the value of the extended attribute `name` of `path`
+#[cfg(target_os = "linux")]
+pub fn get(path: &Path, name: &str) -> io::Result<Vec<u8>> {
+    let c_path = to_cstring(path.as_os_str().as_bytes())?;
+    let c_name = to_cstring(name.as_bytes())?;
+
+    let size =
+        unsafe { libc::lgetxattr(c_path.as_ptr(), c_name.as_ptr(), std::ptr::null_mut(), 0) };
+    if size < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut value = vec![0u8; size as usize];
+    let size = unsafe {
+        libc::lgetxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_mut_ptr() as *mut libc::c_void,
+            value.len(),
+        )
+    };
+    if size < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    value.truncate(size as usize);
+    Ok(value)
+}
+
+/// Sets the extended attribute `name` of `path` to `value`
+#[cfg(target_os = "linux")]
+pub fn set(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+    let c_path = to_cstring(path.as_os_str().as_bytes())?;
+    let c_name = to_cstring(name.as_bytes())?;
+
+    let res = unsafe {
+        libc::lsetxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+        )
+    };
+    if res < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "extended attributes are only supported on Linux",
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn list(_path: &Path) -> io::Result<Vec<(String, Vec<u8>)>> {
+    Err(unsupported())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get(_path: &Path, _name: &str) -> io::Result<Vec<u8>> {
+    Err(unsupported())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set(_path: &Path, _name: &str, _value: &[u8]) -> io::Result<()> {
+    Err(unsupported())
+}
+
+/// Formats an attribute value for display, falling back to hex for binary values
+pub fn display_value(value: &[u8]) -> String {
+    // values set by tools often include the terminating nul
+    let trimmed = value.strip_suffix(&[0]).unwrap_or(value);
+    match std::str::from_utf8(trimmed) {
+        Ok(s) if !s.chars().any(|c| c.is_control()) => s.to_string(),
+        _ => {
+            let hex: String = value.iter().map(|b| format!("{:02x}", b)).collect();
+            format!("0x{}", hex)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_value_test() {
+        assert_eq!(display_value(b"user.comment"), "user.comment");
+        assert_eq!(display_value(b"text\0"), "text");
+        assert_eq!(display_value(&[0x01, 0xff]), "0x01ff");
+        assert_eq!(display_value(b""), "");
+    }
+}